@@ -0,0 +1,199 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::commit::Commit;
+use crate::git::repository_root;
+use crate::rule::Rule;
+
+const BASELINE_FILE_NAME: &str = "lintje-baseline.toml";
+const BASELINE_FILE_HEADER: &str = "\
+# Lintje baseline: issues of existing commits that are not reported.
+# Update this file with `lintje --write-baseline <commit range>`.
+";
+
+/// The rules with issues per commit, which are not reported when linting with a baseline.
+///
+/// A baseline is used to adopt Lintje in a repository with a history of commits that cannot be
+/// rewritten.
+#[derive(Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Baseline {
+    #[serde(default)]
+    commits: BTreeMap<String, BTreeSet<Rule>>,
+}
+
+/// A baseline entry of a linted commit that no longer matches an issue of the commit
+#[derive(Debug, PartialEq)]
+pub struct StaleEntry {
+    pub sha: String,
+    pub rule: Rule,
+}
+
+impl Baseline {
+    /// Returns a baseline of the issues of the commits
+    pub fn from_commits(commits: &[Commit]) -> Self {
+        let mut baseline = Self::default();
+        for commit in commits.iter().filter(|commit| !commit.ignored) {
+            if let (Some(sha), false) = (&commit.long_sha, commit.issues.is_empty()) {
+                let rules = commit.issues.iter().map(|issue| issue.rule.clone());
+                baseline.commits.insert(sha.to_string(), rules.collect());
+            }
+        }
+        baseline
+    }
+
+    /// Returns the number of issues in the baseline
+    pub fn issue_count(&self) -> usize {
+        self.commits.values().map(BTreeSet::len).sum()
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = fs::read_to_string(path).map_err(|e| {
+            format!(
+                "Lintje baseline file could not be read: {}\n{}",
+                path.display(),
+                e
+            )
+        })?;
+        toml::from_str(&contents).map_err(|e| {
+            format!(
+                "Error parsing baseline file: {}\n{}",
+                path.display(),
+                e.message()
+            )
+        })
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), String> {
+        let contents = toml::to_string(self)
+            .map_err(|e| format!("Unable to serialize the baseline: {}", e))?;
+        fs::write(path, format!("{}\n{}", BASELINE_FILE_HEADER, contents))
+            .map_err(|e| format!("Unable to write baseline file: {}\n{}", path.display(), e))
+    }
+
+    /// Remove the issues of the commits that are in the baseline. Returns the baseline entries of
+    /// the commits that no longer match an issue, because the issue was fixed or the rule changed.
+    pub fn apply(&self, commits: &mut [Commit]) -> Vec<StaleEntry> {
+        let mut stale = vec![];
+        for commit in commits.iter_mut().filter(|commit| !commit.ignored) {
+            let rules = match commit
+                .long_sha
+                .as_ref()
+                .and_then(|sha| self.commits.get(sha))
+            {
+                Some(rules) => rules,
+                None => continue,
+            };
+            for rule in rules {
+                if !commit.issues.iter().any(|issue| &issue.rule == rule) {
+                    stale.push(StaleEntry {
+                        sha: commit.short_sha.clone().unwrap_or_default(),
+                        rule: rule.clone(),
+                    });
+                }
+            }
+            commit.issues.retain(|issue| !rules.contains(&issue.rule));
+        }
+        stale
+    }
+}
+
+/// Returns the given baseline file path, or the default baseline file in the repository root
+pub fn baseline_path(path: Option<&Path>) -> Result<PathBuf, String> {
+    match path {
+        Some(path) => Ok(path.to_path_buf()),
+        None => Ok(repository_root()?.join(BASELINE_FILE_NAME)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Baseline, StaleEntry};
+    use crate::issue::{Issue, Position};
+    use crate::rule::Rule;
+    use crate::test::*;
+    use std::path::Path;
+
+    fn commit_with_issues(sha: &str, rules: &[Rule]) -> crate::commit::Commit {
+        let mut commit = commit("Fixing bug", "");
+        commit.long_sha = Some(sha.to_string());
+        commit.short_sha = Some(sha[0..7].to_string());
+        for rule in rules {
+            commit.issues.push(Issue::error(
+                rule.clone(),
+                "Issue message".to_string(),
+                Position::Subject { line: 1, column: 1 },
+                vec![],
+            ));
+        }
+        commit
+    }
+
+    #[test]
+    fn write_and_load() {
+        let dir = Path::new(TEST_DIR).join("baseline_write_and_load");
+        prepare_test_dir(&dir);
+        let path = dir.join("lintje-baseline.toml");
+        let mut ignored = commit_with_issues("cccccccccc", &[Rule::SubjectMood]);
+        ignored.ignored = true;
+        let commits = vec![
+            commit_with_issues("aaaaaaaaaa", &[Rule::SubjectMood, Rule::MessagePresence]),
+            commit_with_issues("bbbbbbbbbb", &[]),
+            ignored,
+        ];
+
+        let baseline = Baseline::from_commits(&commits);
+        assert_eq!(baseline.issue_count(), 2);
+        baseline.write(&path).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "# Lintje baseline: issues of existing commits that are not reported.\n\
+            # Update this file with `lintje --write-baseline <commit range>`.\n\
+            \n\
+            [commits]\n\
+            aaaaaaaaaa = [\"SubjectMood\", \"MessagePresence\"]\n"
+        );
+        assert_eq!(Baseline::load(&path).unwrap(), baseline);
+    }
+
+    #[test]
+    fn load_invalid_file() {
+        let dir = Path::new(TEST_DIR).join("baseline_load_invalid_file");
+        prepare_test_dir(&dir);
+        let path = dir.join("lintje-baseline.toml");
+        create_file(&path, b"[commits]\naaaaaaa = [\"UnknownRule\"]");
+        let error = Baseline::load(&path).unwrap_err();
+        assert!(error.starts_with("Error parsing baseline file:"));
+    }
+
+    #[test]
+    fn apply() {
+        let baseline = Baseline::from_commits(&[
+            commit_with_issues("aaaaaaaaaa", &[Rule::SubjectMood, Rule::MessagePresence]),
+            commit_with_issues("bbbbbbbbbb", &[Rule::SubjectLength]),
+        ]);
+        let mut commits = vec![
+            commit_with_issues("aaaaaaaaaa", &[Rule::SubjectMood, Rule::SubjectPunctuation]),
+            commit_with_issues("dddddddddd", &[Rule::SubjectMood]),
+        ];
+
+        let stale = baseline.apply(&mut commits);
+        assert_eq!(
+            stale,
+            vec![StaleEntry {
+                sha: "aaaaaaa".to_string(),
+                rule: Rule::MessagePresence
+            }]
+        );
+        let rules = |commit: &crate::commit::Commit| {
+            commit
+                .issues
+                .iter()
+                .map(|issue| issue.rule.clone())
+                .collect::<Vec<Rule>>()
+        };
+        assert_eq!(rules(&commits[0]), vec![Rule::SubjectPunctuation]);
+        assert_eq!(rules(&commits[1]), vec![Rule::SubjectMood]);
+    }
+}
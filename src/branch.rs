@@ -1,3 +1,4 @@
+use crate::config::ValidationContext;
 use crate::issue::Issue;
 use crate::rule::Rule;
 
@@ -21,16 +22,29 @@ impl Branch {
         self.issues.is_empty()
     }
 
-    pub fn validate(&mut self) {
-        self.validate_rule(Rule::BranchNameLength);
-        self.validate_rule(Rule::BranchNameTicketNumber);
-        self.validate_rule(Rule::BranchNamePunctuation);
-        self.validate_rule(Rule::BranchNameCliche);
+    pub fn validate(&mut self, context: &ValidationContext) {
+        self.validate_rule(Rule::BranchNameLength, context);
+        self.validate_rule(Rule::BranchNameTicketNumber, context);
+        self.validate_rule(Rule::BranchNamePunctuation, context);
+        self.validate_rule(Rule::BranchNameCliche, context);
+        // The branch name format rule is opt-in, because every project uses another format
+        if context.config.rule_enabled(&Rule::BranchNameFormat) == Some(true) {
+            self.validate_rule(Rule::BranchNameFormat, context);
+        }
     }
 
-    fn validate_rule(&mut self, rule: Rule) {
-        match rule.validate_branch(self) {
+    fn validate_rule(&mut self, rule: Rule, context: &ValidationContext) {
+        if context.config.rule_enabled(&rule) == Some(false) {
+            debug!("Rule '{}' is disabled", rule);
+            return;
+        }
+        match rule.validate_branch(self, &context.config) {
             Some(mut issues) => {
+                if let Some(severity) = context.config.rule_severity(&rule) {
+                    for issue in &mut issues {
+                        issue.r#type = severity.clone();
+                    }
+                }
                 self.issues.append(&mut issues);
             }
             None => {
@@ -66,11 +80,14 @@ impl std::fmt::Display for Branch {
 #[cfg(test)]
 mod tests {
     use super::Branch;
+    use crate::config::file::Config;
+    use crate::config::ValidationContext;
+    use crate::rule::Rule;
 
     #[test]
     fn display() {
         let mut branch = Branch::new("branch-name!".to_string());
-        branch.validate();
+        branch.validate(&ValidationContext::default());
         let display_branch = format!("{}", branch);
         assert_eq!(
             display_branch,
@@ -81,4 +98,31 @@ mod tests {
             display_branch
         );
     }
+
+    #[test]
+    fn disabled_rule() {
+        let mut branch = Branch::new("branch-name!".to_string());
+        let config = Config::parse("[rules.BranchNamePunctuation]\nenabled = false").unwrap();
+        branch.validate(&ValidationContext {
+            config,
+            ..ValidationContext::default()
+        });
+        assert!(!branch.checked_rules.contains(&Rule::BranchNamePunctuation));
+        assert!(branch.is_valid());
+    }
+
+    #[test]
+    fn branch_name_format_rule_when_enabled() {
+        let mut branch = Branch::new("feature/login-form".to_string());
+        branch.validate(&ValidationContext::default());
+        assert!(!branch.checked_rules.contains(&Rule::BranchNameFormat));
+
+        let config = Config::parse("[rules.BranchNameFormat]\nenabled = true").unwrap();
+        branch.validate(&ValidationContext {
+            config,
+            ..ValidationContext::default()
+        });
+        assert!(branch.checked_rules.contains(&Rule::BranchNameFormat));
+        assert_eq!(branch.issues[0].rule, Rule::BranchNameFormat);
+    }
 }
@@ -0,0 +1,321 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use crate::commit::Commit;
+use crate::config::ValidationContext;
+use crate::git::rewrite::git_path;
+use crate::issue::Issue;
+use crate::rule::Rule;
+use crate::utils::stable_hash;
+
+const CACHE_FILE: &str = "lintje/cache.json";
+// The number of cached commit results after which the results of commits that were not linted in
+// the current run are removed
+const MAX_CACHED_COMMITS: usize = 10_000;
+
+/// The validation results of commits from earlier runs, stored in the Git directory.
+///
+/// Commits do not change, so the result of a commit only changes when Lintje is run with a
+/// different version or configuration. The cache is keyed by a hash of both, and cleared when the
+/// hash changes. The cache is pruned when it holds more than `MAX_CACHED_COMMITS` results.
+pub struct ResultCache {
+    path: PathBuf,
+    key: String,
+    commits: BTreeMap<String, serde_json::Value>,
+    // The SHAs of the commits restored from or stored in the cache in this run
+    used: BTreeSet<String>,
+    changed: bool,
+}
+
+#[derive(Deserialize, Serialize)]
+struct CacheFile {
+    key: String,
+    commits: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct CachedCommit {
+    issues: Vec<Issue>,
+    checked_rules: Vec<Rule>,
+}
+
+#[derive(Serialize)]
+struct CachedCommitRef<'a> {
+    issues: &'a [Issue],
+    checked_rules: &'a [Rule],
+}
+
+impl ResultCache {
+    /// Load the cache of the repository for the validation context
+    pub fn load(context: &ValidationContext) -> Result<Self, String> {
+        Ok(Self::load_from(cache_path()?, context))
+    }
+
+    fn load_from(path: PathBuf, context: &ValidationContext) -> Self {
+        let key = cache_key(context);
+        let commits = match fs::read_to_string(&path) {
+            Ok(contents) => match serde_json::from_str::<CacheFile>(&contents) {
+                Ok(file) if file.key == key => file.commits,
+                Ok(_) => {
+                    debug!("Cache is cleared for a different version or configuration");
+                    BTreeMap::new()
+                }
+                Err(e) => {
+                    debug!("Unable to parse cache file: {}\n{}", path.display(), e);
+                    BTreeMap::new()
+                }
+            },
+            Err(e) => {
+                if e.kind() != ErrorKind::NotFound {
+                    debug!("Unable to read cache file: {}\n{}", path.display(), e);
+                }
+                BTreeMap::new()
+            }
+        };
+        Self {
+            path,
+            key,
+            commits,
+            used: BTreeSet::new(),
+            changed: false,
+        }
+    }
+
+    /// Set the cached result on the commit. Returns false if the commit has no cached result.
+    pub fn restore(&mut self, commit: &mut Commit) -> bool {
+        let (sha, cached) = match commit
+            .long_sha
+            .as_ref()
+            .and_then(|sha| self.commits.get_key_value(sha))
+        {
+            Some(entry) => entry,
+            None => return false,
+        };
+        match CachedCommit::deserialize(cached) {
+            Ok(cached) => {
+                self.used.insert(sha.to_string());
+                commit.issues = cached.issues;
+                commit.checked_rules = cached.checked_rules;
+                true
+            }
+            Err(e) => {
+                debug!("Unable to parse cached commit result: {}", e);
+                false
+            }
+        }
+    }
+
+    /// Store the results of the validated commits
+    pub fn update(&mut self, commits: &[Commit]) {
+        for commit in commits.iter().filter(|commit| !commit.ignored) {
+            if let Some(sha) = &commit.long_sha {
+                let cached = CachedCommitRef {
+                    issues: &commit.issues,
+                    checked_rules: &commit.checked_rules,
+                };
+                match serde_json::to_value(cached) {
+                    Ok(value) => {
+                        self.used.insert(sha.to_string());
+                        // Restored results are already cached
+                        if self.commits.get(sha) != Some(&value) {
+                            self.commits.insert(sha.to_string(), value);
+                            self.changed = true;
+                        }
+                    }
+                    Err(e) => debug!("Unable to cache commit result: {}", e),
+                }
+            }
+        }
+    }
+
+    /// Write the cache file if any results were added. The file is replaced at once, so other
+    /// Lintje processes never read a partially written cache.
+    pub fn save(mut self) -> Result<(), String> {
+        self.prune(MAX_CACHED_COMMITS);
+        if !self.changed {
+            return Ok(());
+        }
+        let file = CacheFile {
+            key: self.key,
+            commits: self.commits,
+        };
+        let contents = serde_json::to_string(&file)
+            .map_err(|e| format!("Unable to serialize the cache: {}", e))?;
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).map_err(|e| {
+                format!("Unable to create cache directory: {}\n{}", dir.display(), e)
+            })?;
+        }
+        let temp_path = self
+            .path
+            .with_extension(format!("json.{}.tmp", std::process::id()));
+        let result = fs::write(&temp_path, contents)
+            .and_then(|_| fs::rename(&temp_path, &self.path))
+            .map_err(|e| format!("Unable to write cache file: {}\n{}", self.path.display(), e));
+        if result.is_err() {
+            let _ = fs::remove_file(&temp_path);
+        }
+        result
+    }
+
+    // Remove the results of the commits that were not used in this run when the cache holds more
+    // than the maximum number of results
+    fn prune(&mut self, max: usize) {
+        if self.commits.len() <= max {
+            return;
+        }
+        let used = &self.used;
+        self.commits.retain(|sha, _| used.contains(sha));
+        self.changed = true;
+    }
+}
+
+/// Remove the cache file of the repository. Returns the path of the removed file, or None if there
+/// was no cache file.
+pub fn clear_cache() -> Result<Option<PathBuf>, String> {
+    let path = cache_path()?;
+    remove_cache_file(&path).map(|removed| removed.then_some(path))
+}
+
+fn remove_cache_file(path: &Path) -> Result<bool, String> {
+    match fs::remove_file(path) {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(format!(
+            "Unable to remove cache file: {}\n{}",
+            path.display(),
+            e
+        )),
+    }
+}
+
+fn cache_path() -> Result<PathBuf, String> {
+    git_path(CACHE_FILE)
+}
+
+// The version and the validation context, which includes the configuration, determine the result
+// of a commit.
+fn cache_key(context: &ValidationContext) -> String {
+    stable_hash(&format!("{} {:?}", env!("CARGO_PKG_VERSION"), context))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{remove_cache_file, ResultCache};
+    use crate::config::file::Config;
+    use crate::config::ValidationContext;
+    use crate::issue::{Issue, Position};
+    use crate::rule::Rule;
+    use crate::test::*;
+    use std::path::Path;
+
+    fn validated_commit(sha: &str, subject: &str) -> crate::commit::Commit {
+        let mut commit = commit(subject, "Some message about the change.");
+        commit.long_sha = Some(sha.to_string());
+        commit.checked_rules = vec![Rule::SubjectMood];
+        commit.issues.push(Issue::error(
+            Rule::SubjectMood,
+            "The subject does not use the imperative grammatical mood".to_string(),
+            Position::Subject { line: 1, column: 1 },
+            vec![],
+        ));
+        commit
+    }
+
+    #[test]
+    fn cache_results() {
+        let dir = Path::new(TEST_DIR).join("cache_results");
+        prepare_test_dir(&dir);
+        let path = dir.join("lintje/cache.json");
+        let context = ValidationContext::default();
+
+        let mut cache = ResultCache::load_from(path.clone(), &context);
+        let mut ignored = validated_commit("bbbbbbb", "Merge pull request #123 from repo");
+        ignored.ignored = true;
+        cache.update(&[validated_commit("aaaaaaa", "Fixing bug"), ignored]);
+        cache.save().unwrap();
+
+        let mut cache = ResultCache::load_from(path.clone(), &context);
+        let mut cached = commit("Fixing bug", "Some message about the change.");
+        cached.long_sha = Some("aaaaaaa".to_string());
+        assert!(cache.restore(&mut cached));
+        assert_eq!(cached.checked_rules, vec![Rule::SubjectMood]);
+        assert_eq!(cached.issues[0].rule, Rule::SubjectMood);
+        assert_eq!(
+            cached.issues[0].position,
+            Position::Subject { line: 1, column: 1 }
+        );
+
+        let mut unknown = commit("Fixing bug", "");
+        unknown.long_sha = Some("bbbbbbb".to_string());
+        assert!(!cache.restore(&mut unknown));
+        let mut without_sha = commit("Fixing bug", "");
+        assert!(!cache.restore(&mut without_sha));
+
+        // A different configuration does not use the cached results
+        let context = ValidationContext {
+            config: Config::parse("[rules.SubjectMood]\nenabled = false").unwrap(),
+            ..ValidationContext::default()
+        };
+        let mut cache = ResultCache::load_from(path.clone(), &context);
+        assert!(!cache.restore(&mut cached));
+
+        assert!(remove_cache_file(&path).unwrap());
+        assert!(!remove_cache_file(&path).unwrap());
+    }
+
+    #[test]
+    fn save_changes_only() {
+        let dir = Path::new(TEST_DIR).join("cache_save_changes_only");
+        prepare_test_dir(&dir);
+        let path = dir.join("lintje/cache.json");
+        let context = ValidationContext::default();
+
+        let mut cache = ResultCache::load_from(path.clone(), &context);
+        cache.update(&[validated_commit("aaaaaaa", "Fixing bug")]);
+        cache.save().unwrap();
+        assert!(path.is_file());
+        // No temporary files are left behind
+        assert_eq!(std::fs::read_dir(dir.join("lintje")).unwrap().count(), 1);
+
+        // Without new results the cache file is not written again
+        let mut cache = ResultCache::load_from(path.clone(), &context);
+        let mut cached = commit("Fixing bug", "Some message about the change.");
+        cached.long_sha = Some("aaaaaaa".to_string());
+        assert!(cache.restore(&mut cached));
+        cache.update(&[cached]);
+        assert!(remove_cache_file(&path).unwrap());
+        cache.save().unwrap();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn prune() {
+        let dir = Path::new(TEST_DIR).join("cache_prune");
+        prepare_test_dir(&dir);
+        let path = dir.join("lintje/cache.json");
+        let context = ValidationContext::default();
+
+        let mut cache = ResultCache::load_from(path.clone(), &context);
+        cache.update(&[
+            validated_commit("aaaaaaa", "Fixing bug"),
+            validated_commit("bbbbbbb", "Fixing bug"),
+        ]);
+        cache.save().unwrap();
+
+        let mut cache = ResultCache::load_from(path.clone(), &context);
+        assert!(cache.restore(&mut validated_commit("aaaaaaa", "Fixing bug")));
+        cache.update(&[validated_commit("ccccccc", "Fixing bug")]);
+        cache.prune(3);
+        assert_eq!(cache.commits.len(), 3);
+        // Only the commits of this run are kept when the cache holds too many results
+        cache.prune(2);
+        assert_eq!(
+            cache.commits.keys().collect::<Vec<&String>>(),
+            vec!["aaaaaaa", "ccccccc"]
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use std::ffi::OsStr;
-use std::io::Write;
+use std::io::{BufRead, BufReader, Read, Write};
 use std::process::{Command, Output, Stdio};
 
 #[derive(Debug)]
@@ -110,14 +110,21 @@ pub fn run_command<S: AsRef<OsStr> + std::fmt::Display>(
     cmd: &str,
     args: &[S],
 ) -> Result<String, FailedCommand> {
+    run_command_for_bytes(cmd, args).map(|stdout| String::from_utf8_lossy(&stdout).to_string())
+}
+
+/// Run the command and return STDOUT as is, for output that is not (valid) UTF-8
+pub fn run_command_for_bytes<S: AsRef<OsStr> + std::fmt::Display>(
+    cmd: &str,
+    args: &[S],
+) -> Result<Vec<u8>, FailedCommand> {
     let mut command = Command::new(cmd);
     command.args(args);
     match command.output() {
         Ok(output) => {
             let status = output.status;
-            let stdout = String::from_utf8_lossy(&output.stdout);
             if status.success() {
-                Ok(stdout.to_string())
+                Ok(output.stdout)
             } else {
                 // The program was run, but exited with a failure.
                 //
@@ -131,6 +138,55 @@ pub fn run_command<S: AsRef<OsStr> + std::fmt::Display>(
     }
 }
 
+/// Run the command and read STDOUT while the command writes it, rather than buffering all output in
+/// memory first.
+pub fn run_command_with_reader<S, T, F>(cmd: &str, args: &[S], read: F) -> Result<T, FailedCommand>
+where
+    S: AsRef<OsStr> + std::fmt::Display,
+    F: FnOnce(&mut dyn BufRead) -> T,
+{
+    let command = Command::new(cmd)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn();
+    let mut child = match command {
+        Ok(handler) => handler,
+        Err(error) => {
+            return Err(FailedCommand::from_error(cmd, args, &error));
+        }
+    };
+    // Read STDERR on a separate thread so the command doesn't block on a full STDERR pipe while
+    // STDOUT is being read.
+    let mut child_stderr = child.stderr.take().expect("Lintje failed to open stderr");
+    let stderr_reader = std::thread::spawn(move || {
+        let mut stderr = vec![];
+        // Any STDERR output that could be read is still reported if reading fails halfway
+        let _ = child_stderr.read_to_end(&mut stderr);
+        stderr
+    });
+    let stdout = child.stdout.take().expect("Lintje failed to open stdout");
+    let result = read(&mut BufReader::new(stdout));
+    let status = child.wait();
+    let stderr = stderr_reader.join().unwrap_or_default();
+    match status {
+        Ok(status) => {
+            if status.success() {
+                Ok(result)
+            } else {
+                let output = Output {
+                    status,
+                    stdout: vec![],
+                    stderr,
+                };
+                Err(FailedCommand::from_output(cmd, args, output))
+            }
+        }
+        Err(error) => Err(FailedCommand::from_error(cmd, args, &error)),
+    }
+}
+
 pub fn run_command_with_stdin<S: AsRef<OsStr> + std::fmt::Display>(
     cmd: &str,
     args: &[S],
@@ -175,7 +231,7 @@ pub fn run_command_with_stdin<S: AsRef<OsStr> + std::fmt::Display>(
 
 #[cfg(test)]
 mod tests {
-    use super::{run_command, run_command_with_stdin};
+    use super::{run_command, run_command_with_reader, run_command_with_stdin};
     use std::os::unix::process::ExitStatusExt;
     use std::process::{ExitStatus, Output};
 
@@ -240,6 +296,51 @@ mod tests {
         }
     }
 
+    #[test]
+    fn run_with_reader_success() {
+        let result = run_command_with_reader("echo", &["-n", "123", "456"], |reader| {
+            let mut output = String::new();
+            reader.read_to_string(&mut output).map(|_| output)
+        });
+        match result {
+            Ok(output) => assert_eq!(output.unwrap(), "123 456"),
+            Err(e) => panic!("Unexpected failure: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn run_with_reader_exit_failure() {
+        let result =
+            run_command_with_reader("support/test/failure_script", &["5", "hello"], |reader| {
+                reader.read_to_end(&mut vec![]).map(|_| ())
+            });
+        match result {
+            Ok(result) => panic!("Unexpected success: {:?}", result),
+            Err(e) => {
+                // STDOUT is read by the reader
+                assert_eq!(e.message(), "STDERR message\n");
+                assert!(e.error.is_exit_code(5));
+            }
+        }
+    }
+
+    #[test]
+    fn run_with_reader_large_stderr() {
+        // More STDERR output than fits in the pipe buffer, written before any STDOUT output
+        let result = run_command_with_reader(
+            "sh",
+            &["-c", "head -c 1000000 /dev/zero >&2; echo -n done"],
+            |reader| {
+                let mut output = String::new();
+                reader.read_to_string(&mut output).map(|_| output)
+            },
+        );
+        match result {
+            Ok(output) => assert_eq!(output.unwrap(), "done"),
+            Err(e) => panic!("Unexpected failure: {:?}", e),
+        }
+    }
+
     #[test]
     fn run_with_stdin_success() {
         match run_command_with_stdin("cat", &["-u"], "Hello stdin".to_string()) {
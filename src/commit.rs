@@ -1,6 +1,7 @@
 use crate::config::ValidationContext;
 use crate::issue::Issue;
 use crate::rule::{rule_by_name, Rule};
+use crate::rules::conventional_commit::ConventionalSubject;
 
 #[derive(Debug)]
 pub struct Commit {
@@ -14,6 +15,8 @@ pub struct Commit {
     pub issues: Vec<Issue>,
     pub ignored_rules: Vec<Rule>,
     pub checked_rules: Vec<Rule>,
+    pub conventional_subject: Option<ConventionalSubject>,
+    pub ignored: bool,
 }
 
 impl Commit {
@@ -48,6 +51,8 @@ impl Commit {
             ignored_rules,
             issues: Vec::<Issue>::new(),
             checked_rules: Vec::<Rule>::new(),
+            conventional_subject: None,
+            ignored: false,
         }
     }
 
@@ -55,6 +60,21 @@ impl Commit {
         !self.file_changes.is_empty()
     }
 
+    /// Returns the part of the subject that describes the change and its bytes index in the
+    /// subject.
+    ///
+    /// For Conventional Commits this is the description after the type and scope. For other
+    /// commits it's the entire subject.
+    pub fn subject_description(&self) -> (usize, &str) {
+        match &self.conventional_subject {
+            Some(conventional) => (
+                conventional.description.start,
+                &self.subject[conventional.description.clone()],
+            ),
+            None => (0, &self.subject),
+        }
+    }
+
     fn find_ignored_rules(message: &str, trailers: &str) -> Vec<Rule> {
         let mut ignored = vec![];
         ignored.append(&mut Self::find_ignored_rules_for(message));
@@ -85,39 +105,59 @@ impl Commit {
     }
 
     pub fn validate(&mut self, context: &ValidationContext) {
-        self.validate_rule(Rule::MergeCommit);
-        self.validate_rule(Rule::RebaseCommit);
+        // In Conventional Commits mode the subject prefix is validated by the ConventionalCommit
+        // rule, and the other subject rules validate the description.
+        let conventional = context.config.rule_enabled(&Rule::ConventionalCommit) == Some(true);
+        if conventional {
+            self.conventional_subject = ConventionalSubject::parse(&self.subject);
+        }
+        self.validate_rule(Rule::MergeCommit, context);
+        self.validate_rule(Rule::RebaseCommit, context);
 
         // If a commit has a MergeCommit or RebaseCommit issue, other rules are skipped,
         // because the commit itself will need to be rebased into other commits. So the format
         // of the commit won't matter.
         if !self.has_issue(&Rule::MergeCommit) && !self.has_issue(&Rule::RebaseCommit) {
-            self.validate_rule(Rule::SubjectCliche);
-            self.validate_rule(Rule::SubjectLength);
-            self.validate_rule(Rule::SubjectMood);
-            self.validate_rule(Rule::SubjectWhitespace);
-            self.validate_rule(Rule::SubjectPrefix);
-            self.validate_rule(Rule::SubjectCapitalization);
-            self.validate_rule(Rule::SubjectBuildTag);
-            self.validate_rule(Rule::SubjectPunctuation);
-            self.validate_rule(Rule::SubjectTicketNumber);
-            self.validate_rule(Rule::MessageTicketNumber);
-            self.validate_rule(Rule::MessageEmptyFirstLine);
-            self.validate_rule(Rule::MessagePresence);
-            self.validate_rule(Rule::MessageLineLength);
-            self.validate_rule(Rule::MessageTrailerLine);
-            self.validate_rule(Rule::MessageSkipBuildTag);
-            if context.changesets {
-                self.validate_rule(Rule::DiffChangeset);
+            self.validate_rule(Rule::SubjectCliche, context);
+            self.validate_rule(Rule::SubjectLength, context);
+            self.validate_rule(Rule::SubjectMood, context);
+            self.validate_rule(Rule::SubjectWhitespace, context);
+            if conventional {
+                self.validate_rule(Rule::ConventionalCommit, context);
+            } else {
+                self.validate_rule(Rule::SubjectPrefix, context);
+                self.validate_rule(Rule::SubjectCapitalization, context);
+            }
+            self.validate_rule(Rule::SubjectBuildTag, context);
+            self.validate_rule(Rule::SubjectPunctuation, context);
+            self.validate_rule(Rule::SubjectTicketNumber, context);
+            self.validate_rule(Rule::MessageTicketNumber, context);
+            self.validate_rule(Rule::MessageEmptyFirstLine, context);
+            self.validate_rule(Rule::MessagePresence, context);
+            self.validate_rule(Rule::MessageLineLength, context);
+            self.validate_rule(Rule::MessageTrailerLine, context);
+            self.validate_rule(Rule::MessageSkipBuildTag, context);
+            if context.changesets || context.config.rule_enabled(&Rule::DiffChangeset) == Some(true)
+            {
+                self.validate_rule(Rule::DiffChangeset, context);
             }
         }
-        self.validate_rule(Rule::DiffPresence);
+        self.validate_rule(Rule::DiffPresence, context);
     }
 
-    fn validate_rule(&mut self, rule: Rule) {
+    fn validate_rule(&mut self, rule: Rule, context: &ValidationContext) {
+        if context.config.rule_enabled(&rule) == Some(false) {
+            debug!("Rule '{}' is disabled", rule);
+            return;
+        }
         if !self.rule_ignored(&rule) {
-            match rule.validate_commit(self) {
+            match rule.validate_commit(self, context) {
                 Some(mut issues) => {
+                    if let Some(severity) = context.config.rule_severity(&rule) {
+                        for issue in &mut issues {
+                            issue.r#type = severity.clone();
+                        }
+                    }
                     self.issues.append(&mut issues);
                 }
                 None => {
@@ -191,12 +231,21 @@ impl std::fmt::Display for Commit {
 #[cfg(test)]
 mod tests {
     use super::Commit;
+    use crate::config::file::Config;
     use crate::config::ValidationContext;
+    use crate::issue::IssueType;
     use crate::rule::Rule;
     use crate::test::*;
 
     fn default_context() -> ValidationContext {
-        ValidationContext { changesets: false }
+        ValidationContext::default()
+    }
+
+    fn changesets_context() -> ValidationContext {
+        ValidationContext {
+            changesets: true,
+            ..ValidationContext::default()
+        }
     }
 
     #[test]
@@ -233,7 +282,7 @@ mod tests {
     #[test]
     fn check_validated_rules_default() {
         let mut commit = commit("".to_string(), "Intentionally invalid commit".to_string());
-        commit.validate(&ValidationContext { changesets: false });
+        commit.validate(&default_context());
         // Test specific order of rules because they may depend on one another
         assert_eq!(
             commit.checked_rules,
@@ -266,7 +315,7 @@ mod tests {
             "Merge branch 'develop' of github.com/org/repo into develop".to_string(),
             "".to_string(),
         );
-        commit.validate(&ValidationContext { changesets: false });
+        commit.validate(&default_context());
         // Test specific order of rules because they may depend on one another.
         // A lot of rules are skipped for these types of commits because they do not apply.
         assert_eq!(
@@ -278,7 +327,7 @@ mod tests {
     #[test]
     fn check_validated_rules_fixup_commit() {
         let mut commit = commit("fixup! Some commit".to_string(), "".to_string());
-        commit.validate(&ValidationContext { changesets: false });
+        commit.validate(&default_context());
         // Test specific order of rules because they may depend on one another.
         // A lot of rules are skipped for these types of commits because they do not apply.
         assert_eq!(
@@ -290,17 +339,105 @@ mod tests {
     #[test]
     fn does_not_validate_changeset_rule_when_changeset_mode_is_false() {
         let mut commit = commit("".to_string(), "Intentionally invalid commit".to_string());
-        commit.validate(&ValidationContext { changesets: false });
+        commit.validate(&default_context());
         assert!(!commit.checked_rules.contains(&Rule::DiffChangeset));
     }
 
     #[test]
     fn validate_changeset_rule_when_changeset_mode_is_true() {
         let mut commit = commit("".to_string(), "Intentionally invalid commit".to_string());
-        commit.validate(&ValidationContext { changesets: true });
+        commit.validate(&changesets_context());
+        assert!(commit.checked_rules.contains(&Rule::DiffChangeset));
+    }
+
+    #[test]
+    fn validate_changeset_rule_when_enabled_in_config() {
+        let mut commit = commit("".to_string(), "Intentionally invalid commit".to_string());
+        let config = Config::parse("[rules.DiffChangeset]\nenabled = true").unwrap();
+        commit.validate(&ValidationContext {
+            config,
+            ..ValidationContext::default()
+        });
         assert!(commit.checked_rules.contains(&Rule::DiffChangeset));
     }
 
+    #[test]
+    fn disabled_rule_in_config() {
+        let mut commit = commit("fix: some bug".to_string(), "".to_string());
+        let config = Config::parse(
+            "[rules.SubjectPrefix]\n\
+            enabled = false\n\
+            [rules.MessagePresence]\n\
+            enabled = false",
+        )
+        .unwrap();
+        commit.validate(&ValidationContext {
+            config,
+            ..ValidationContext::default()
+        });
+        assert!(!commit.checked_rules.contains(&Rule::SubjectPrefix));
+        assert!(!commit.has_issue(&Rule::SubjectPrefix));
+        assert!(!commit.has_issue(&Rule::MessagePresence));
+    }
+
+    #[test]
+    fn rule_severity_in_config() {
+        let mut commit = commit("fix: some bug".to_string(), "".to_string());
+        let config = Config::parse(
+            "[rules.SubjectPrefix]\n\
+            severity = \"hint\"\n\
+            [rules.MessageTicketNumber]\n\
+            severity = \"error\"",
+        )
+        .unwrap();
+        commit.validate(&ValidationContext {
+            config,
+            ..ValidationContext::default()
+        });
+        let types = commit
+            .issues
+            .iter()
+            .filter(|issue| {
+                issue.rule == Rule::SubjectPrefix || issue.rule == Rule::MessageTicketNumber
+            })
+            .map(|issue| (&issue.rule, &issue.r#type))
+            .collect::<Vec<(&Rule, &IssueType)>>();
+        assert_eq!(
+            types,
+            vec![
+                (&Rule::SubjectPrefix, &IssueType::Hint),
+                (&Rule::MessageTicketNumber, &IssueType::Error)
+            ]
+        );
+    }
+
+    #[test]
+    fn check_validated_rules_conventional_commit() {
+        let mut commit = commit(
+            "feat(api): Add endpoint".to_string(),
+            "Intentionally invalid commit".to_string(),
+        );
+        let config = Config::parse("[rules.ConventionalCommit]\nenabled = true").unwrap();
+        commit.validate(&ValidationContext {
+            config,
+            ..ValidationContext::default()
+        });
+        assert!(commit.checked_rules.contains(&Rule::ConventionalCommit));
+        assert!(!commit.checked_rules.contains(&Rule::SubjectPrefix));
+        assert!(!commit.checked_rules.contains(&Rule::SubjectCapitalization));
+        assert!(!commit.has_issue(&Rule::ConventionalCommit));
+        assert_eq!(commit.subject_description(), (11, "Add endpoint"));
+    }
+
+    #[test]
+    fn subject_description_without_conventional_commits() {
+        let mut commit = commit("feat(api): Add endpoint".to_string(), "".to_string());
+        commit.validate(&default_context());
+        assert!(!commit.checked_rules.contains(&Rule::ConventionalCommit));
+        assert!(commit.has_issue(&Rule::SubjectPrefix));
+        assert_eq!(commit.subject_description(), (0, "feat(api): Add endpoint"));
+    }
+
     #[test]
     fn ignored_rule() {
         let mut ignored_rule = commit_with_trailers(
@@ -345,7 +482,7 @@ mod tests {
             "Co-authored-by: Person A <email@domain.com>\nSigned-off-by: Person A <email@domain.com>".to_string(),
             vec!["src/main.rs".to_string(), "README.md".to_string()]
         );
-        commit.validate(&ValidationContext { changesets: true });
+        commit.validate(&changesets_context());
         let display_commit = format!("{}", commit);
         assert_eq!(
             display_commit,
@@ -1,9 +1,15 @@
-use clap::{AppSettings, Parser};
+pub mod file;
+
+use clap::{AppSettings, ArgGroup, Parser, Subcommand};
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use crate::formatter::OutputFormat;
 use crate::git::hooks::CommitHook;
+use crate::issue::{issue_type_by_name, IssueType};
+use crate::rule::{rule_by_name, Rule};
+use file::{load_config, Config};
 
 const IGNORED_CLAP_ERRORS: [clap::error::ErrorKind; 2] = [
     clap::error::ErrorKind::DisplayHelp,
@@ -17,7 +23,10 @@ const IGNORED_CLAP_ERRORS: [clap::error::ErrorKind; 2] = [
     version,
     long_version = long_version_output(),
     verbatim_doc_comment,
-    setting(AppSettings::DeriveDisplayOrder)
+    setting(AppSettings::DeriveDisplayOrder),
+    disable_help_subcommand = true,
+    args_override_self = true,
+    group(ArgGroup::new("message-input").args(&["stdin", "message"]))
 )]
 /**
 Lint Git commits and branch name.
@@ -41,18 +50,111 @@ Homepage: https://lintje.dev
     lintje main..develop
       Validate the difference between the main and develop branch.
 
+    lintje --max-commits 1000 origin/main~5000..origin/main
+      Validate the latest 1000 commits of the given range.
+
     lintje --hook-message-file=.git/COMMIT_EDITMSG
       Lints the given commit message file from the commit-msg hook.
 
+    lintje --hook-prepare-message-file=.git/COMMIT_EDITMSG
+      Writes a commit message template to the given commit message file from
+      the prepare-commit-msg hook.
+
+    echo "$MESSAGE" | lintje --stdin
+      Lints the commit message read from STDIN, without a Git repository.
+
+    lintje --message "Add login form" --message "Explain why..."
+      Lints the given commit message, with every message as a paragraph.
+
+    lintje --message "Add login form" --files src/login.rs
+      Lints the given commit message, with the given files as the changes of
+      the commit.
+
+    lintje --patch 0001-add-login-form.patch 0002-fix-login-form.patch
+      Lints the commits of the given patch files, like the output of
+      `git format-patch` and mbox archives, before applying them.
+
+    lintje --hook-message-file=.git/COMMIT_EDITMSG --fix
+      Fixes the issues that can be fixed safely in the commit message file,
+      such as subject punctuation, and lints the fixed message.
+
+    lintje --hook-pre-push origin
+      Lints the commits and branches pushed to the origin remote, as read
+      from STDIN in the pre-push hook.
+
+    lintje --hook-pre-receive
+      Lints the pushed commits and new branches in the pre-receive hook of a
+      Git server, as read from STDIN. Prints the issues in the compact format.
+
+    lintje reword main..HEAD
+      Reword the commits with errors in the difference between the main
+      branch and the current branch in the Git editor.
+
+    lintje --install-hook commit-msg
+      Install Lintje in the commit-msg hook to lint every new commit.
+
+    lintje --hook-status
+      Print in which Git hooks Lintje is installed.
+
     lintje --no-branch
       Disable branch name validation.
 
+    lintje --branch feature/login-form
+      Validate the given branch name instead of the current branch name.
+
+    lintje --all-branches
+      Validate the names of all local branches, without validating commits.
+
+    lintje --remote-branches origin
+      Validate the names of all branches of the origin remote, without
+      validating commits.
+
+    lintje --disable SubjectPrefix --disable SubjectCapitalization
+      Disable the SubjectPrefix and SubjectCapitalization rules.
+
+    lintje --severity MessageTicketNumber=error
+      Report MessageTicketNumber issues as errors rather than hints.
+
     lintje --color
       Enable color output.
 
+    lintje --no-cache HEAD~20..HEAD
+      Validate the last 20 commits again, rather than reusing the results of
+      commits that were validated before.
+
+    lintje --clear-cache
+      Remove the cached results of validated commits.
+
+    lintje --write-baseline main~200..main
+      Record the issues of the last 200 commits of the main branch in the
+      `lintje-baseline.toml` file, to adopt Lintje in an existing repository.
+
+    lintje --baseline main..HEAD
+      Lints the commits without reporting the issues in the baseline file.
+      Baseline entries that no longer match an issue are reported.
+
     lintje --verbose
       Print the validated commit and branch above the detected issues.
 
+    lintje --format json
+      Print the lint result as JSON, for other tools to parse.
+
+    lintje --format sarif
+      Print the lint result as a SARIF 2.1.0 log, for code scanning tools.
+
+    lintje --format junit
+      Print the lint result as a JUnit XML report, for CI test result panels.
+
+    lintje --format github
+      Print the issues as GitHub Actions workflow commands, to annotate pull
+      requests.
+
+    lintje --format gitlab
+      Print the issues as a GitLab Code Quality report.
+
+    lintje --format compact
+      Print every issue on a single line, without context.
+
 ## Options file
 
     Use an options file to add defaults every Lintje call. Configure the
@@ -75,16 +177,175 @@ Homepage: https://lintje.dev
          --color --no-hints
 
     http://r.lintje.dev/d/options-file
+
+## Config file
+
+    Use a `lintje.toml` config file to share the Lintje configuration of a
+    project. Lintje looks for the config file in the repository root and its
+    parent directories. Options from the options file and CLI flags take
+    precedence over the config file options.
+
+         # lintje.toml
+         # Disable hints
+         hints = false
+
+         [rules.SubjectPrefix]
+         enabled = false
+
+         [rules.MessageTicketNumber]
+         # Report issues as errors rather than hints
+         severity = "error"
+
+         [rules.SubjectLength]
+         max_width = 60
+         min_width = 5
+
+         [rules.MessageLineLength]
+         max_width = 80
+
+         [rules.MessagePresence]
+         min_length = 20
+
+         [rules.SubjectMood]
+         # Detect "Deploys", "Deployed" and "Deploying"
+         verbs = ["deploy"]
+         irregular_verbs = { spun = "spin" }
+         exceptions = ["Nested"]
+
+## Conventional Commits
+
+    Enable the ConventionalCommit rule to validate subjects in the
+    `<type>(<scope>)!: <description>` format. It replaces the SubjectPrefix
+    and SubjectCapitalization rules, and the other subject rules validate the
+    description after the prefix.
+
+         # lintje.toml
+         [rules.ConventionalCommit]
+         enabled = true
+         types = ["feat", "fix", "docs", "chore"]
+         scope_pattern = "^[a-z-]+$"
+
+## Branch name format
+
+    Enable the BranchNameFormat rule to validate branch names in a format of
+    `<type>`, `<ticket>` and `<slug>` placeholders and separators. The rule
+    reports the part of the branch name that does not match the format, and
+    suggests a branch name in the format.
+
+         # lintje.toml
+         [rules.BranchNameFormat]
+         enabled = true
+         format = "<type>/<ticket>-<slug>"
+         types = ["feature", "fix", "chore"]
+
+## Commit message template
+
+    Install Lintje in the prepare-commit-msg hook to start new commit
+    messages from a template. The template references the ticket of the
+    branch, explains the enabled rules, and adds placeholders for the
+    configured trailers, all in comments. Uncomment the ticket reference and
+    trailers to add them to the commit message.
+
+         # lintje.toml
+         [template]
+         trailers = ["Reviewed-by", "Signed-off-by"]
+
+## Ticket references
+
+    The SubjectTicketNumber, MessageTicketNumber, MessagePresence and
+    BranchNameTicketNumber rules recognize ticket references in the configured
+    syntaxes. The built-in syntaxes are `url`, `repository` (org/repo#123),
+    `number` (#123) and `jira` (AB-123). Add patterns for other syntaxes, and
+    restrict syntaxes with a `project` group to a list of projects.
+
+         # lintje.toml
+         [tickets]
+         syntaxes = ["number", "jira", "azure", "internal"]
+
+         [tickets.patterns.jira]
+         projects = ["ENG", "OPS"]
+
+         [tickets.patterns.azure]
+         pattern = 'AB#\d+'
+
+         [tickets.patterns.internal]
+         pattern = 'TKT:\d{5}'
+
+    Require a ticket reference in every commit with the `required` setting of
+    the MessageTicketNumber rule. When the branch name contains a ticket
+    reference, like `feature/ENG-123-login`, every commit needs to reference
+    that same ticket, like `Part of ENG-123`, and no other tickets. The checked
+    out branch is used for the latest commit and ranges ending at `HEAD`. For
+    other commits, specify the branch with the `--branch` option.
+
+         # lintje.toml
+         [rules.MessageTicketNumber]
+         required = true
 */
 pub struct Lint {
     /// Disable branch validation
-    #[clap(long = "no-branch", help_heading = "RULES", parse(from_flag = std::ops::Not::not))]
+    #[clap(
+        long = "no-branch",
+        help_heading = "RULES",
+        overrides_with = "enable-branch-validation",
+        parse(from_flag = std::ops::Not::not)
+    )]
     pub branch_validation: bool,
 
+    /// Enable branch validation, even if it's disabled in the config file or options file
+    #[clap(
+        long = "branch-validation",
+        help_heading = "RULES",
+        overrides_with = "branch-validation"
+    )]
+    pub enable_branch_validation: bool,
+
     /// Disable hints
-    #[clap(long = "no-hints", help_heading = "RULES", parse(from_flag = std::ops::Not::not))]
+    #[clap(
+        long = "no-hints",
+        help_heading = "RULES",
+        overrides_with = "enable-hints",
+        parse(from_flag = std::ops::Not::not)
+    )]
     pub hints: bool,
 
+    /// Enable hints, even if they're disabled in the config file or options file
+    #[clap(long = "hints", help_heading = "RULES", overrides_with = "hints")]
+    pub enable_hints: bool,
+
+    /// Enable the rule, even if it's disabled in the config file or by default.
+    /// Specify this option multiple times to enable multiple rules.
+    #[clap(
+        long,
+        value_name = "RULE",
+        multiple_occurrences(true),
+        parse(try_from_str = parse_rule),
+        help_heading = "RULES"
+    )]
+    pub enable: Vec<Rule>,
+
+    /// Disable the rule for all commits and the branch.
+    /// Specify this option multiple times to disable multiple rules.
+    #[clap(
+        long,
+        value_name = "RULE",
+        multiple_occurrences(true),
+        parse(try_from_str = parse_rule),
+        help_heading = "RULES"
+    )]
+    pub disable: Vec<Rule>,
+
+    /// Report the issues of a rule as errors or hints, e.g. `SubjectPrefix=hint`.
+    /// Specify this option multiple times to configure multiple rules.
+    #[clap(
+        long,
+        value_name = "RULE=error|hint",
+        multiple_occurrences(true),
+        parse(try_from_str = parse_rule_severity),
+        help_heading = "RULES"
+    )]
+    pub severity: Vec<(Rule, IssueType)>,
+
     /// Enable color output
     #[clap(long = "color", help_heading = "OUTPUT")]
     pub color: bool,
@@ -94,17 +355,73 @@ pub struct Lint {
     pub no_color: bool,
 
     /// Install Lintje hook in the given Git hook file.
-    /// Installs a different command based on the hook type selected.
+    /// Installs a different command based on the hook type selected. The hook file is created in
+    /// the hooks directory Git uses, which can be configured with `core.hooksPath`. Hook files that
+    /// already call Lintje are not changed.
     /// For more information about Git hooks read: https://git-scm.com/docs/githooks
     #[clap(
         long,
         arg_enum,
         name = "hook file name",
         help_heading = "INSTALLATION",
-        conflicts_with_all(&["commit (range)", "commit message file path"])
+        conflicts_with_all(&["commit (range)", "commit message file path", "hook-pre-push"])
     )]
     pub install_hook: Option<CommitHook>,
 
+    /// Remove Lintje from the given Git hook file.
+    /// The hook file is removed if no other commands remain.
+    #[clap(
+        long,
+        arg_enum,
+        value_name = "HOOK",
+        help_heading = "INSTALLATION",
+        conflicts_with_all(&[
+            "commit (range)",
+            "commit message file path",
+            "hook file name",
+            "hook-pre-push"
+        ])
+    )]
+    pub uninstall_hook: Option<CommitHook>,
+
+    /// Print in which Git hooks Lintje is installed.
+    #[clap(
+        long,
+        help_heading = "INSTALLATION",
+        conflicts_with_all(&["hook file name", "uninstall-hook"])
+    )]
+    pub hook_status: bool,
+
+    /// Write a commit message template to the Git hook prepare-commit-msg commit message file.
+    /// The template contains the ticket reference of the branch, guidance for the enabled rules,
+    /// and placeholders for the trailers configured in the config file.
+    #[clap(
+        long,
+        value_name = "PATH",
+        parse(from_os_str),
+        conflicts_with_all(&[
+            "commit (range)",
+            "commit message file path",
+            "hook file name",
+            "uninstall-hook",
+            "hook-pre-push",
+            "hook-pre-receive"
+        ]),
+        help_heading = "SELECTION"
+    )]
+    pub hook_prepare_message_file: Option<PathBuf>,
+
+    /// The source of the commit message, as given to the prepare-commit-msg hook.
+    /// No template is written for messages from `-m`, templates, merges, squashes and amended
+    /// commits.
+    #[clap(
+        long,
+        value_name = "SOURCE",
+        requires = "hook-prepare-message-file",
+        help_heading = "SELECTION"
+    )]
+    pub hook_message_source: Option<String>,
+
     /// Lint the contents the Git hook commit-msg commit message file.
     /// This will usually be `.git/COMMIT_EDITMSG`.
     #[clap(
@@ -116,6 +433,202 @@ pub struct Lint {
     )]
     pub hook_message_file: Option<PathBuf>,
 
+    /// Fix the issues that can be fixed safely in the commit message file, before linting it.
+    /// Only available in combination with `--hook-message-file`.
+    #[clap(
+        long,
+        requires = "commit message file path",
+        help_heading = "SELECTION"
+    )]
+    pub fix: bool,
+
+    /// Lint the commits and branches that are pushed to the remote, in the Git pre-push hook.
+    /// Reads the pushed refs from STDIN. The commits of new branches are compared to the remote's
+    /// default branch.
+    #[clap(
+        long,
+        value_name = "REMOTE",
+        conflicts_with_all(&[
+            "commit (range)",
+            "commit message file path",
+            "branch-name",
+            "all-branches",
+            "remote-branches"
+        ]),
+        help_heading = "SELECTION"
+    )]
+    pub hook_pre_push: Option<String>,
+
+    /// Lint the pushed commits and new branches in the Git pre-receive hook of a (bare) server
+    /// repository. Reads the ref updates from STDIN. Commits that are already part of a branch or
+    /// tag in the repository are not linted again.
+    #[clap(
+        long,
+        conflicts_with_all(&[
+            "commit (range)",
+            "commit message file path",
+            "branch-name",
+            "all-branches",
+            "remote-branches",
+            "hook-pre-push"
+        ]),
+        help_heading = "SELECTION"
+    )]
+    pub hook_pre_receive: bool,
+
+    /// Lint the commit message read from STDIN.
+    /// Like the message of the `--hook-message-file` option, comments are removed from the
+    /// message. The staged changes are not looked up; specify them with `--files`.
+    #[clap(
+        long,
+        conflicts_with_all(&[
+            "commit (range)",
+            "commit message file path",
+            "hook-prepare-message-file",
+            "hook-pre-push",
+            "hook-pre-receive",
+            "all-branches",
+            "remote-branches"
+        ]),
+        help_heading = "SELECTION"
+    )]
+    pub stdin: bool,
+
+    /// Lint the given commit message.
+    /// Specify this option multiple times to add paragraphs to the message, like
+    /// `git commit --message`.
+    #[clap(
+        long,
+        value_name = "MESSAGE",
+        multiple_occurrences(true),
+        conflicts_with_all(&[
+            "commit (range)",
+            "commit message file path",
+            "hook-prepare-message-file",
+            "hook-pre-push",
+            "hook-pre-receive",
+            "all-branches",
+            "remote-branches"
+        ]),
+        help_heading = "SELECTION"
+    )]
+    pub message: Vec<String>,
+
+    /// The files changed by the commit of the `--stdin` or `--message` option.
+    /// Without files, the DiffPresence and DiffChangeset rules are skipped.
+    #[clap(
+        long,
+        value_name = "FILE",
+        multiple_occurrences(true),
+        multiple_values(true),
+        requires = "message-input",
+        help_heading = "SELECTION"
+    )]
+    pub files: Vec<String>,
+
+    /// Lint the commits of the given patch files, like the output of `git format-patch` and mbox
+    /// archives of mailing lists. The `[PATCH]` tag is removed from the subject of every patch, and
+    /// the changed files are read from the diff.
+    #[clap(
+        long,
+        value_name = "FILE",
+        multiple_occurrences(true),
+        multiple_values(true),
+        parse(from_os_str),
+        conflicts_with_all(&[
+            "commit (range)",
+            "commit message file path",
+            "hook-prepare-message-file",
+            "hook-pre-push",
+            "hook-pre-receive",
+            "message-input",
+            "all-branches",
+            "remote-branches"
+        ]),
+        help_heading = "SELECTION"
+    )]
+    pub patch: Vec<PathBuf>,
+
+    /// Lint the given branch name instead of the name of the current branch.
+    /// The branch does not need to be checked out.
+    #[clap(
+        long = "branch",
+        value_name = "NAME",
+        overrides_with = "branch-validation",
+        conflicts_with_all(&["all-branches", "remote-branches"]),
+        help_heading = "SELECTION"
+    )]
+    pub branch_name: Option<String>,
+
+    /// Lint the names of all local branches. No commits are linted.
+    #[clap(
+        long,
+        conflicts_with_all(&[
+            "commit (range)",
+            "commit message file path",
+            "hook file name",
+            "branch-validation",
+            "remote-branches"
+        ]),
+        help_heading = "SELECTION"
+    )]
+    pub all_branches: bool,
+
+    /// Lint the names of all branches of the given remote, e.g. `origin`. No commits are linted.
+    #[clap(
+        long,
+        value_name = "REMOTE",
+        conflicts_with_all(&[
+            "commit (range)",
+            "commit message file path",
+            "hook file name",
+            "branch-validation"
+        ]),
+        help_heading = "SELECTION"
+    )]
+    pub remote_branches: Option<String>,
+
+    /// Do not use the cached results of commits that were linted before, and do not cache the
+    /// results of this run.
+    #[clap(long = "no-cache", parse(from_flag = std::ops::Not::not), help_heading = "CACHE")]
+    pub cache: bool,
+
+    /// Remove the cached results of linted commits from the Git directory.
+    #[clap(long, conflicts_with = "cache", help_heading = "CACHE")]
+    pub clear_cache: bool,
+
+    /// Do not report the issues recorded in the baseline file. Reports baseline entries that no
+    /// longer match an issue of a linted commit. Defaults to `lintje-baseline.toml` in the
+    /// repository root.
+    #[clap(
+        long,
+        value_name = "FILE",
+        require_equals(true),
+        help_heading = "BASELINE"
+    )]
+    pub baseline: Option<Option<String>>,
+
+    /// Record the issues of the linted commits in the baseline file, rather than reporting them.
+    /// Defaults to `lintje-baseline.toml` in the repository root.
+    #[clap(
+        long,
+        value_name = "FILE",
+        require_equals(true),
+        conflicts_with = "baseline",
+        help_heading = "BASELINE"
+    )]
+    pub write_baseline: Option<Option<String>>,
+
+    /// Lint at most the given number of commits, starting with the latest commit of the selection.
+    /// A warning is printed when the selection contains more commits.
+    #[clap(long, value_name = "NUMBER", help_heading = "SELECTION")]
+    pub max_commits: Option<usize>,
+
+    /// Output format of the lint result. Defaults to `text`, or `compact` with
+    /// `--hook-pre-receive`.
+    #[clap(long, arg_enum, help_heading = "OUTPUT")]
+    pub format: Option<OutputFormat>,
+
     /// Prints debug information
     #[clap(long, help_heading = "OUTPUT")]
     pub debug: bool,
@@ -128,6 +641,26 @@ pub struct Lint {
     /// defaults to linting the latest commit.
     #[clap(name = "commit (range)", help_heading = "SELECTION")]
     pub selection: Option<String>,
+
+    #[clap(subcommand)]
+    pub command: Option<LintCommand>,
+
+    /// The `lintje.toml` config file, if any
+    #[clap(skip)]
+    pub config: Config,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum LintCommand {
+    /// Reword the commits with errors in the selected range of commits.
+    /// Opens the commit message of every commit with errors in the Git editor, with the issues
+    /// listed below the message. Commits that are already part of the upstream branch cannot be
+    /// reworded.
+    Reword {
+        /// The range of commits to reword, e.g. `main..HEAD`
+        #[clap(name = "commit range")]
+        selection: String,
+    },
 }
 
 impl Lint {
@@ -139,30 +672,140 @@ impl Lint {
         if self.color {
             return true;
         }
-        true // By default color is turned on
+        self.config.color.unwrap_or(true) // By default color is turned on
+    }
+
+    /// Use the config file values for the options that are not set with the options file or CLI
+    /// flags, and select the rules to validate
+    fn apply_config(&mut self, config: Config) {
+        if self.branch_validation && !self.enable_branch_validation && self.branch_name.is_none() {
+            self.branch_validation = config.branch.unwrap_or(true);
+        }
+        if self.hints && !self.enable_hints {
+            self.hints = config.hints.unwrap_or(true);
+        }
+        self.config = config;
+        self.config
+            .select_rules(&self.enable, &self.disable, &self.severity);
+    }
+
+    /// Returns true if the linted commits are on the checked out branch: the commit of the commit
+    /// hook, the latest commit, or a range of commits ending at `HEAD`.
+    pub fn selects_current_branch(&self) -> bool {
+        if self.hook_message_file.is_some() {
+            return true;
+        }
+        if self.message_input()
+            || !self.patch.is_empty()
+            || self.hook_pre_push.is_some()
+            || self.hook_pre_receive
+            || self.lint_branches_only()
+        {
+            return false;
+        }
+        match &self.selection {
+            Some(selection) => {
+                let end = match selection.rsplit_once("..") {
+                    Some((_start, end)) => end,
+                    None => selection,
+                };
+                end.is_empty()
+                    || end == "HEAD"
+                    || end.starts_with("HEAD~")
+                    || end.starts_with("HEAD^")
+            }
+            None => true,
+        }
+    }
+
+    /// Returns true if only the names of branches are linted, and no commits
+    pub fn lint_branches_only(&self) -> bool {
+        self.all_branches || self.remote_branches.is_some()
+    }
+
+    /// Returns true if the results of the linted commits are cached. Only commits read from the
+    /// repository are cached, as other commits can change.
+    pub fn cache_results(&self) -> bool {
+        self.cache
+            && self.hook_message_file.is_none()
+            && self.patch.is_empty()
+            && !self.message_input()
+    }
+
+    /// Returns true if the commit message is given with `--stdin` or `--message`, rather than
+    /// read from Git
+    pub fn message_input(&self) -> bool {
+        self.stdin || !self.message.is_empty()
     }
 
-    pub fn merge(&mut self, options: Vec<String>) {
-        self.update_from(options);
+    /// Return the output format, defaulting to the text format, or the compact format in the
+    /// pre-receive hook
+    pub fn format(&self) -> OutputFormat {
+        match &self.format {
+            Some(format) => format.clone(),
+            None if self.hook_pre_receive => OutputFormat::Compact,
+            None => OutputFormat::Text,
+        }
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct ValidationContext {
     pub changesets: bool,
+    pub config: Config,
+    /// The name of the current branch, if needed by the rules
+    pub branch: Option<String>,
 }
 
 pub fn fetch_options() -> Lint {
-    let cli_opts = cli_options();
-    match file_options(env::var("LINTJE_OPTIONS_PATH")) {
-        Some((path, file_options)) => {
-            // Merge CLI options with options file if a options file was successfully
-            // parsed.
-            let mut opts = parse_file_options(&path, &file_options);
-            opts.merge(cli_opts);
-            opts
+    // Options from the options file are the defaults, which the CLI flags override. All options
+    // are parsed as one list of arguments, in which later arguments override earlier ones.
+    let mut options = vec![];
+    if let Some((path, mut file_options)) = file_options(env::var("LINTJE_OPTIONS_PATH")) {
+        validate_file_options(&path, &file_options);
+        options.append(&mut file_options);
+    }
+    let mut opts = Lint::parse_from(combined_args(cli_options(), options));
+    match load_config() {
+        Ok(config) => opts.apply_config(config),
+        Err(e) => {
+            eprintln!("ERROR: {}", e);
+            std::process::exit(2)
+        }
+    };
+    opts
+}
+
+// Insert the default options between the program name and the CLI arguments
+fn combined_args(cli_args: Vec<String>, options: Vec<String>) -> Vec<String> {
+    let mut cli_args = cli_args.into_iter();
+    let program = cli_args.next().unwrap_or_else(|| "lintje".to_string());
+    std::iter::once(program)
+        .chain(options)
+        .chain(cli_args)
+        .collect()
+}
+
+fn parse_rule(name: &str) -> Result<Rule, String> {
+    rule_by_name(name).ok_or_else(|| format!("Unknown rule: {}", name))
+}
+
+fn parse_rule_severity(value: &str) -> Result<(Rule, IssueType), String> {
+    match value.split_once('=') {
+        Some((name, severity)) => {
+            let rule = parse_rule(name)?;
+            match issue_type_by_name(severity) {
+                Some(issue_type) => Ok((rule, issue_type)),
+                None => Err(format!(
+                    "Unknown severity: {}. Use `error` or `hint`.",
+                    severity
+                )),
+            }
         }
-        None => Lint::parse_from(cli_opts),
+        None => Err(format!(
+            "Invalid rule severity: {}. Use the `<rule>=<error|hint>` format.",
+            value
+        )),
     }
 }
 
@@ -217,19 +860,18 @@ fn parse_options_file(contents: &str) -> Vec<String> {
         .collect::<Vec<String>>()
 }
 
-fn parse_file_options(path: &Path, options: &[String]) -> Lint {
+// Exit with an error if the options file contains invalid options, so the error is reported for
+// the options file rather than the CLI arguments
+fn validate_file_options(path: &Path, options: &[String]) {
     let mut opts = vec!["lintje".to_string()];
     opts.append(&mut options.to_owned());
-    match Lint::try_parse_from(&opts) {
-        Ok(opts) => opts,
-        Err(e) => {
-            // Only print parse error when clap errors aren't used to print help or version
-            // information
-            if !IGNORED_CLAP_ERRORS.contains(&e.kind()) {
-                eprintln!("ERROR: Error parsing options file: {:?}", path);
-            }
-            e.exit()
+    if let Err(e) = Lint::try_parse_from(&opts) {
+        // Only print parse error when clap errors aren't used to print help or version
+        // information
+        if !IGNORED_CLAP_ERRORS.contains(&e.kind()) {
+            eprintln!("ERROR: Error parsing options file: {:?}", path);
         }
+        e.exit()
     }
 }
 
@@ -244,7 +886,10 @@ fn long_version_output() -> &'static str {
 
 #[cfg(test)]
 mod tests {
-    use super::{file_options, parse_options_file, Lint};
+    use super::{combined_args, file_options, parse_options_file, Config, Lint, LintCommand};
+    use crate::formatter::OutputFormat;
+    use crate::issue::IssueType;
+    use crate::rule::Rule;
     use crate::test::*;
     use clap::Parser;
     use std::path::{Path, PathBuf};
@@ -269,23 +914,242 @@ mod tests {
     }
 
     #[test]
-    fn merge_options() {
-        let mut opts = Lint::parse_from(vec![
-            "lintje".to_string(),
-            "--color".to_string(),
-            "--no-branch".to_string(),
-        ]);
+    fn current_branch_selection() {
+        let selects_current_branch =
+            |args: &[&str]| Lint::parse_from([&["lintje"], args].concat()).selects_current_branch();
+        assert!(selects_current_branch(&[]));
+        assert!(selects_current_branch(&["HEAD"]));
+        assert!(selects_current_branch(&["HEAD~2"]));
+        assert!(selects_current_branch(&["main.."]));
+        assert!(selects_current_branch(&["main..HEAD"]));
+        assert!(selects_current_branch(&["main...HEAD"]));
+        assert!(selects_current_branch(&[
+            "--hook-message-file",
+            "COMMIT_EDITMSG"
+        ]));
+        assert!(!selects_current_branch(&["3a5f5e1"]));
+        assert!(!selects_current_branch(&["main..feature"]));
+        assert!(!selects_current_branch(&["--stdin"]));
+        assert!(!selects_current_branch(&["--patch", "0001-fix.patch"]));
+        assert!(!selects_current_branch(&["--hook-pre-push", "origin"]));
+    }
+
+    #[test]
+    fn config_file_defaults() {
+        let config = || Config::parse("hints = false\nbranch = false\ncolor = false").unwrap();
+
+        // Config file values are used when no flags are set
+        let mut opts = Lint::parse_from(["lintje"]);
+        opts.apply_config(config());
+        assert!(!opts.hints);
+        assert!(!opts.branch_validation);
+        assert!(!opts.color());
+
+        // Flags override the config file values
+        let mut opts = Lint::parse_from(["lintje", "--hints", "--branch-validation", "--color"]);
+        opts.apply_config(config());
         assert!(opts.hints);
-        opts.merge(vec![
-            "lintje".to_string(),
-            "--no-color".to_string(),
-            "--no-hints".to_string(),
+        assert!(opts.branch_validation);
+        assert!(opts.color());
+
+        // The last flag is leading
+        let opts = Lint::parse_from(["lintje", "--hints", "--no-hints", "--no-branch"]);
+        assert!(!opts.hints);
+        assert!(!opts.enable_hints);
+        let opts = Lint::parse_from([
+            "lintje",
+            "--no-hints",
+            "--hints",
+            "--no-branch",
+            "--branch-validation",
         ]);
-        assert!(opts.color);
-        assert!(opts.no_color);
-        assert!(!opts.color());
+        assert!(opts.hints);
+        assert!(opts.branch_validation);
+
+        // Without a config file everything is enabled by default
+        let mut opts = Lint::parse_from(["lintje"]);
+        opts.apply_config(Config::default());
+        assert!(opts.hints);
+        assert!(opts.branch_validation);
+        assert!(opts.color());
+    }
+
+    #[test]
+    fn combined_options() {
+        let args = combined_args(
+            vec!["lintje".to_string(), "--format=text".to_string()],
+            vec![
+                "--no-branch".to_string(),
+                "--format=json".to_string(),
+                "--disable=SubjectPrefix".to_string(),
+            ],
+        );
+        assert_eq!(
+            args,
+            vec![
+                "lintje",
+                "--no-branch",
+                "--format=json",
+                "--disable=SubjectPrefix",
+                "--format=text"
+            ]
+        );
+        // The CLI arguments override the options given before them
+        let opts = Lint::parse_from(&args);
+        assert_eq!(opts.format, Some(OutputFormat::Text));
         assert!(!opts.branch_validation);
+        assert_eq!(opts.disable, vec![Rule::SubjectPrefix]);
+
+        let opts = Lint::parse_from(combined_args(
+            vec![
+                "lintje".to_string(),
+                "reword".to_string(),
+                "main..HEAD".to_string(),
+            ],
+            vec!["--no-hints".to_string()],
+        ));
         assert!(!opts.hints);
+        assert!(matches!(
+            opts.command,
+            Some(LintCommand::Reword { selection }) if selection == "main..HEAD"
+        ));
+
+        // Flags given in both are allowed
+        let opts = Lint::parse_from(combined_args(
+            vec![
+                "lintje".to_string(),
+                "--disable=SubjectMood".to_string(),
+                "--no-hints".to_string(),
+            ],
+            vec![
+                "--disable=SubjectPrefix".to_string(),
+                "--no-hints".to_string(),
+            ],
+        ));
+        assert_eq!(opts.disable, vec![Rule::SubjectPrefix, Rule::SubjectMood]);
+    }
+
+    #[test]
+    fn rule_selection_options() {
+        let opts = Lint::parse_from([
+            "lintje",
+            "--enable",
+            "DiffChangeset",
+            "--disable",
+            "SubjectPrefix",
+            "--disable=BranchNameLength",
+            "--severity",
+            "SubjectCapitalization=hint",
+        ]);
+        assert_eq!(opts.enable, vec![Rule::DiffChangeset]);
+        assert_eq!(
+            opts.disable,
+            vec![Rule::SubjectPrefix, Rule::BranchNameLength]
+        );
+        assert_eq!(
+            opts.severity,
+            vec![(Rule::SubjectCapitalization, IssueType::Hint)]
+        );
+    }
+
+    #[test]
+    fn rule_selection_options_invalid() {
+        assert!(Lint::try_parse_from(["lintje", "--disable", "Unknown"]).is_err());
+        assert!(Lint::try_parse_from(["lintje", "--severity", "SubjectPrefix"]).is_err());
+        assert!(Lint::try_parse_from(["lintje", "--severity", "SubjectPrefix=warning"]).is_err());
+    }
+
+    #[test]
+    fn pre_receive_output_format() {
+        let opts = Lint::parse_from(["lintje", "--hook-pre-receive"]);
+        assert_eq!(opts.format(), OutputFormat::Compact);
+        let opts = Lint::parse_from(["lintje", "--hook-pre-receive", "--format", "json"]);
+        assert_eq!(opts.format(), OutputFormat::Json);
+        assert_eq!(Lint::parse_from(["lintje"]).format(), OutputFormat::Text);
+    }
+
+    #[test]
+    fn branch_selection_options() {
+        let opts = Lint::parse_from(["lintje", "--branch", "feature-login"]);
+        assert_eq!(opts.branch_name, Some("feature-login".to_string()));
+        assert!(!opts.lint_branches_only());
+
+        assert!(Lint::parse_from(["lintje", "--all-branches"]).lint_branches_only());
+        let opts = Lint::parse_from(["lintje", "--remote-branches", "origin"]);
+        assert_eq!(opts.remote_branches, Some("origin".to_string()));
+        assert!(opts.lint_branches_only());
+
+        assert!(Lint::try_parse_from(["lintje", "--all-branches", "HEAD~2..HEAD"]).is_err());
+        assert!(Lint::try_parse_from(["lintje", "--all-branches", "--no-branch"]).is_err());
+
+        // The last of the `--no-branch` and `--branch` options is leading, so the CLI arguments
+        // override the options file
+        let args = combined_args(
+            vec![
+                "lintje".to_string(),
+                "--branch".to_string(),
+                "feature-login".to_string(),
+            ],
+            vec!["--no-branch".to_string()],
+        );
+        let mut opts = Lint::parse_from(&args);
+        opts.apply_config(Config::parse("branch = false").unwrap());
+        assert_eq!(opts.branch_name, Some("feature-login".to_string()));
+        assert!(opts.branch_validation);
+        let opts = Lint::parse_from(["lintje", "--branch", "feature-login", "--no-branch"]);
+        assert_eq!(opts.branch_name, None);
+        assert!(!opts.branch_validation);
+        assert!(
+            Lint::try_parse_from(["lintje", "--branch", "a", "--remote-branches", "b"]).is_err()
+        );
+    }
+
+    #[test]
+    fn message_input_options() {
+        assert!(!Lint::parse_from(["lintje"]).message_input());
+        assert!(Lint::parse_from(["lintje", "--stdin"]).message_input());
+        let opts = Lint::parse_from([
+            "lintje",
+            "--message",
+            "Subject",
+            "--message",
+            "Body",
+            "--files",
+            "a.rs",
+            "b.rs",
+        ]);
+        assert!(opts.message_input());
+        assert_eq!(opts.message, vec!["Subject", "Body"]);
+        assert_eq!(opts.files, vec!["a.rs", "b.rs"]);
+
+        assert!(Lint::try_parse_from(["lintje", "--stdin", "--message", "Subject"]).is_err());
+        assert!(Lint::try_parse_from(["lintje", "--stdin", "HEAD~2..HEAD"]).is_err());
+        assert!(Lint::try_parse_from(["lintje", "--files", "a.rs"]).is_err());
+    }
+
+    #[test]
+    fn baseline_options() {
+        let opts = Lint::parse_from(["lintje", "--baseline", "HEAD~2..HEAD"]);
+        assert_eq!(opts.baseline, Some(None));
+        assert_eq!(opts.selection, Some("HEAD~2..HEAD".to_string()));
+        let opts = Lint::parse_from(["lintje", "--write-baseline=baseline.toml"]);
+        assert_eq!(opts.write_baseline, Some(Some("baseline.toml".to_string())));
+        assert_eq!(opts.baseline, None);
+
+        assert!(Lint::try_parse_from(["lintje", "--baseline", "--write-baseline"]).is_err());
+    }
+
+    #[test]
+    fn patch_options() {
+        let opts = Lint::parse_from(["lintje", "--patch", "0001.patch", "0002.patch"]);
+        assert_eq!(
+            opts.patch,
+            vec![PathBuf::from("0001.patch"), PathBuf::from("0002.patch")]
+        );
+        assert!(Lint::try_parse_from(["lintje", "--patch", "0001.patch", "--stdin"]).is_err());
+        assert!(
+            Lint::try_parse_from(["lintje", "--patch", "0001.patch", "--all-branches"]).is_err()
+        );
     }
 
     #[test]
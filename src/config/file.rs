@@ -0,0 +1,767 @@
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::git::repository_root;
+use crate::issue::{issue_type_by_name, IssueType};
+use crate::rule::{rule_by_name, Rule};
+use crate::rules::branch_name_format::BranchFormat;
+use crate::rules::{TicketReferences, BUILT_IN_TICKET_SYNTAXES};
+
+pub const CONFIG_FILE_NAME: &str = "lintje.toml";
+
+/// Lintje configuration as read from the `lintje.toml` file.
+///
+/// All values default to Lintje's built-in behavior when no config file is found.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Config {
+    pub path: Option<PathBuf>,
+    pub hints: Option<bool>,
+    pub branch: Option<bool>,
+    pub color: Option<bool>,
+    pub rules: BTreeMap<Rule, RuleConfig>,
+    pub subject_length: SubjectLengthConfig,
+    pub subject_mood: SubjectMoodConfig,
+    pub message_line_length: MessageLineLengthConfig,
+    pub message_presence: MessagePresenceConfig,
+    pub message_ticket_number: MessageTicketNumberConfig,
+    pub conventional_commit: ConventionalCommitConfig,
+    pub branch_name_format: BranchNameFormatConfig,
+    pub tickets: TicketConfig,
+    pub template: TemplateConfig,
+}
+
+/// Settings that apply to every rule.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RuleConfig {
+    pub enabled: Option<bool>,
+    pub severity: Option<IssueType>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct SubjectLengthConfig {
+    pub max_width: usize,
+    pub min_width: usize,
+}
+
+impl Default for SubjectLengthConfig {
+    fn default() -> Self {
+        Self {
+            max_width: 50,
+            min_width: 5,
+        }
+    }
+}
+
+/// Extends the word lists the `SubjectMood` rule uses to detect verbs that are not in the imperative
+/// mood. Words are matched case-insensitively.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct SubjectMoodConfig {
+    /// Verbs in the imperative mood, like `deploy`, to detect `deploys`, `deployed` and
+    /// `deploying` and suggest the imperative form.
+    pub verbs: Vec<String>,
+    /// Irregular verb forms with their imperative form, like `spun = "spin"`.
+    pub irregular_verbs: BTreeMap<String, String>,
+    /// Words that are never reported, like nouns that end in `-ed`, `-ing` or `-s`.
+    pub exceptions: Vec<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct MessageLineLengthConfig {
+    pub max_width: usize,
+}
+
+impl Default for MessageLineLengthConfig {
+    fn default() -> Self {
+        Self { max_width: 72 }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct MessagePresenceConfig {
+    pub min_length: usize,
+}
+
+impl Default for MessagePresenceConfig {
+    fn default() -> Self {
+        Self { min_length: 10 }
+    }
+}
+
+/// With `required` enabled, commits without a ticket reference are reported as errors. If the
+/// branch name contains a ticket reference, commits need to reference that same ticket.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct MessageTicketNumberConfig {
+    pub required: bool,
+}
+
+/// The commit message template written in the prepare-commit-msg hook. Placeholders are added for
+/// the trailers, like `Signed-off-by`.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct TemplateConfig {
+    pub trailers: Vec<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConventionalCommitConfig {
+    pub types: Vec<String>,
+    pub scope_pattern: Option<Pattern>,
+}
+
+impl Default for ConventionalCommitConfig {
+    fn default() -> Self {
+        Self {
+            types: [
+                "build", "chore", "ci", "docs", "feat", "fix", "perf", "refactor", "revert",
+                "style", "test",
+            ]
+            .iter()
+            .map(|t| t.to_string())
+            .collect(),
+            scope_pattern: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct BranchNameFormatConfig {
+    pub format: BranchFormat,
+    pub types: Vec<String>,
+}
+
+impl Default for BranchNameFormatConfig {
+    fn default() -> Self {
+        Self {
+            format: BranchFormat::try_from("<type>/<ticket>-<slug>".to_string())
+                .expect("Invalid default branch name format"),
+            types: ["feature", "fix", "chore"]
+                .iter()
+                .map(|t| t.to_string())
+                .collect(),
+        }
+    }
+}
+
+/// The ticket reference syntaxes recognized by the rules that check for ticket references.
+///
+/// The syntaxes are compiled into one set of expressions when the config file is parsed.
+#[derive(Clone, Debug)]
+pub struct TicketConfig {
+    pub syntaxes: Vec<TicketSyntax>,
+    references: TicketReferences,
+    // True if the syntaxes are configured with a `[tickets]` table in the config file
+    configured: bool,
+}
+
+impl TicketConfig {
+    pub fn new(syntaxes: Vec<TicketSyntax>) -> Result<Self, String> {
+        let references = TicketReferences::new(&syntaxes)?;
+        Ok(Self {
+            syntaxes,
+            references,
+            configured: false,
+        })
+    }
+
+    pub fn references(&self) -> &TicketReferences {
+        &self.references
+    }
+
+    /// Returns true if the ticket syntaxes are configured in the config file, rather than the
+    /// built-in defaults.
+    pub fn is_configured(&self) -> bool {
+        self.configured
+    }
+
+    fn parse(file: TicketsFile) -> Result<Self, String> {
+        let mut patterns = file.patterns;
+        let names = match file.syntaxes {
+            Some(names) => names,
+            // Use all built-in syntaxes and the syntaxes defined in the config file
+            None => BUILT_IN_TICKET_SYNTAXES
+                .iter()
+                .map(|(name, _)| name.to_string())
+                .chain(
+                    patterns
+                        .keys()
+                        .filter(|name| built_in_ticket_pattern(name).is_none())
+                        .cloned(),
+                )
+                .collect(),
+        };
+        let mut syntaxes = vec![];
+        for name in names {
+            let definition = patterns.remove(&name).unwrap_or_default();
+            let pattern = match (definition.pattern, built_in_ticket_pattern(&name)) {
+                (Some(pattern), _) => pattern,
+                (None, Some(pattern)) => Pattern::try_from(pattern.to_string())?,
+                (None, None) => return Err(format!("Unknown ticket syntax: {}", name)),
+            };
+            if !definition.projects.is_empty()
+                && !pattern.capture_names().any(|group| group == "project")
+            {
+                return Err(format!(
+                    "The pattern of ticket syntax {} has no `project` group to match the \
+                    projects against",
+                    name
+                ));
+            }
+            syntaxes.push(TicketSyntax {
+                name,
+                pattern,
+                projects: definition.projects,
+            });
+        }
+        if let Some(name) = patterns.keys().next() {
+            return Err(format!(
+                "Ticket syntax {} is defined, but not listed in `syntaxes`",
+                name
+            ));
+        }
+        Ok(Self {
+            configured: true,
+            ..Self::new(syntaxes)?
+        })
+    }
+}
+
+lazy_static! {
+    // Compiled once, because the default config is used for every validation without a config file
+    static ref DEFAULT_TICKET_CONFIG: TicketConfig = {
+        let syntaxes = BUILT_IN_TICKET_SYNTAXES
+            .iter()
+            .map(|(name, pattern)| TicketSyntax {
+                name: name.to_string(),
+                pattern: Pattern::try_from(pattern.to_string())
+                    .expect("Invalid built-in ticket syntax"),
+                projects: vec![],
+            })
+            .collect();
+        TicketConfig::new(syntaxes).expect("Invalid built-in ticket syntaxes")
+    };
+}
+
+impl Default for TicketConfig {
+    fn default() -> Self {
+        DEFAULT_TICKET_CONFIG.clone()
+    }
+}
+
+impl PartialEq for TicketConfig {
+    fn eq(&self, other: &Self) -> bool {
+        self.syntaxes == other.syntaxes
+    }
+}
+
+/// A named ticket reference syntax, like `AB#123` for Azure DevOps work items.
+///
+/// If a list of projects is configured, only references of which the `project` group matches one
+/// of the projects are recognized as ticket references.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TicketSyntax {
+    pub name: String,
+    pub pattern: Pattern,
+    pub projects: Vec<String>,
+}
+
+fn built_in_ticket_pattern(name: &str) -> Option<&'static str> {
+    BUILT_IN_TICKET_SYNTAXES
+        .iter()
+        .find(|(built_in_name, _)| *built_in_name == name)
+        .map(|(_, pattern)| *pattern)
+}
+
+/// A regular expression configured in the config file.
+///
+/// The expression is compiled when the config file is parsed, so invalid patterns are reported
+/// as config file errors.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(try_from = "String")]
+pub struct Pattern(Regex);
+
+impl Pattern {
+    pub fn is_match(&self, value: &str) -> bool {
+        self.0.is_match(value)
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    pub fn capture_names(&self) -> impl Iterator<Item = &str> {
+        self.0.capture_names().flatten()
+    }
+}
+
+impl TryFrom<String> for Pattern {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Regex::new(&value)
+            .map(Pattern)
+            .map_err(|e| format!("Invalid pattern {:?}: {}", value, e))
+    }
+}
+
+impl PartialEq for Pattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+// The raw representation of the config file. The rule tables are parsed per rule in
+// `Config::parse`, because every rule accepts different settings.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    hints: Option<bool>,
+    branch: Option<bool>,
+    color: Option<bool>,
+    #[serde(default)]
+    rules: BTreeMap<String, toml::Table>,
+    tickets: Option<TicketsFile>,
+    template: Option<TemplateConfig>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TicketsFile {
+    syntaxes: Option<Vec<String>>,
+    #[serde(default)]
+    patterns: BTreeMap<String, TicketPatternFile>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TicketPatternFile {
+    pattern: Option<Pattern>,
+    #[serde(default)]
+    projects: Vec<String>,
+}
+
+impl Config {
+    /// Returns the rule setting from the config file, if configured.
+    pub fn rule_enabled(&self, rule: &Rule) -> Option<bool> {
+        self.rules.get(rule).and_then(|config| config.enabled)
+    }
+
+    /// Returns the issue type the issues of the rule are reported as, if configured.
+    pub fn rule_severity(&self, rule: &Rule) -> Option<&IssueType> {
+        self.rules
+            .get(rule)
+            .and_then(|config| config.severity.as_ref())
+    }
+
+    /// Override the config file rule settings with the rules selected with CLI options.
+    pub fn select_rules(
+        &mut self,
+        enabled: &[Rule],
+        disabled: &[Rule],
+        severities: &[(Rule, IssueType)],
+    ) {
+        for rule in enabled {
+            self.rules.entry(rule.clone()).or_default().enabled = Some(true);
+        }
+        for rule in disabled {
+            self.rules.entry(rule.clone()).or_default().enabled = Some(false);
+        }
+        for (rule, severity) in severities {
+            self.rules.entry(rule.clone()).or_default().severity = Some(severity.clone());
+        }
+    }
+
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let file: ConfigFile = toml::from_str(contents).map_err(|e| e.to_string())?;
+        let mut config = Self {
+            hints: file.hints,
+            branch: file.branch,
+            color: file.color,
+            ..Self::default()
+        };
+        if let Some(tickets) = file.tickets {
+            config.tickets = TicketConfig::parse(tickets)?;
+        }
+        if let Some(template) = file.template {
+            config.template = template;
+        }
+        for (name, mut table) in file.rules {
+            let rule = match rule_by_name(&name) {
+                Some(rule) => rule,
+                None => return Err(format!("Unknown rule in config file: {}", name)),
+            };
+            let enabled = match table.remove("enabled") {
+                Some(toml::Value::Boolean(value)) => Some(value),
+                Some(value) => {
+                    return Err(format!(
+                        "Invalid value for `enabled` of rule {}: {}",
+                        rule, value
+                    ))
+                }
+                None => None,
+            };
+            let severity = match table.remove("severity") {
+                Some(toml::Value::String(value)) => match issue_type_by_name(&value) {
+                    Some(issue_type) => Some(issue_type),
+                    None => {
+                        return Err(format!(
+                            "Invalid value for `severity` of rule {}: {:?}",
+                            rule, value
+                        ))
+                    }
+                },
+                Some(value) => {
+                    return Err(format!(
+                        "Invalid value for `severity` of rule {}: {}",
+                        rule, value
+                    ))
+                }
+                None => None,
+            };
+            config.parse_rule_settings(&rule, table)?;
+            config.rules.insert(rule, RuleConfig { enabled, severity });
+        }
+        Ok(config)
+    }
+
+    fn parse_rule_settings(&mut self, rule: &Rule, table: toml::Table) -> Result<(), String> {
+        let value = toml::Value::Table(table);
+        let result = match rule {
+            Rule::SubjectLength => value.try_into().map(|c| self.subject_length = c),
+            Rule::SubjectMood => value.try_into().map(|c| self.subject_mood = c),
+            Rule::MessageLineLength => value.try_into().map(|c| self.message_line_length = c),
+            Rule::MessagePresence => value.try_into().map(|c| self.message_presence = c),
+            Rule::MessageTicketNumber => value.try_into().map(|c| self.message_ticket_number = c),
+            Rule::ConventionalCommit => value.try_into().map(|c| self.conventional_commit = c),
+            Rule::BranchNameFormat => value.try_into().map(|c| self.branch_name_format = c),
+            _ => {
+                return match value.as_table().and_then(|t| t.keys().next()) {
+                    Some(key) => Err(format!("Unknown setting for rule {}: {}", rule, key)),
+                    None => Ok(()),
+                }
+            }
+        };
+        result.map_err(|e| format!("Invalid settings for rule {}: {}", rule, e))
+    }
+}
+
+/// Load the config file from the repository root or one of its parent directories.
+///
+/// When Lintje is not run from a Git repository, it starts looking from the current directory.
+pub fn load_config() -> Result<Config, String> {
+    let start = match repository_root() {
+        Ok(root) => root,
+        Err(_) => match std::env::current_dir() {
+            Ok(dir) => dir,
+            Err(_) => return Ok(Config::default()),
+        },
+    };
+    match find_config_file(&start) {
+        Some(path) => read_config_file(&path),
+        None => Ok(Config::default()),
+    }
+}
+
+fn find_config_file(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(CONFIG_FILE_NAME))
+        .find(|path| path.is_file())
+}
+
+fn read_config_file(path: &Path) -> Result<Config, String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| format!("Lintje config file could not be read: {:?}\n{}", path, e))?;
+    let mut config = Config::parse(&contents)
+        .map_err(|e| format!("Error parsing config file: {:?}\n{}", path, e))?;
+    config.path = Some(path.to_path_buf());
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_config_file, read_config_file, Config, RuleConfig, TicketConfig};
+    use crate::issue::IssueType;
+    use crate::rule::Rule;
+    use crate::test::*;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    fn test_dir(name: &str) -> PathBuf {
+        Path::new(TEST_DIR).join(name)
+    }
+
+    #[test]
+    fn parse_empty() {
+        assert_eq!(Config::parse(""), Ok(Config::default()));
+    }
+
+    #[test]
+    fn parse_options() {
+        let config = Config::parse("hints = false\nbranch = false\ncolor = true").unwrap();
+        assert_eq!(config.hints, Some(false));
+        assert_eq!(config.branch, Some(false));
+        assert_eq!(config.color, Some(true));
+    }
+
+    #[test]
+    fn parse_rule_settings() {
+        let config = Config::parse(
+            "[rules.SubjectLength]\n\
+            max_width = 60\n\
+            \n\
+            [rules.MessageLineLength]\n\
+            max_width = 80\n\
+            \n\
+            [rules.MessagePresence]\n\
+            min_length = 20\n\
+            \n\
+            [rules.SubjectPrefix]\n\
+            enabled = false",
+        )
+        .unwrap();
+        assert_eq!(config.subject_length.max_width, 60);
+        assert_eq!(config.subject_length.min_width, 5);
+        assert_eq!(config.message_line_length.max_width, 80);
+        assert_eq!(config.message_presence.min_length, 20);
+        assert_eq!(config.rule_enabled(&Rule::SubjectPrefix), Some(false));
+        assert_eq!(config.rule_enabled(&Rule::SubjectLength), None);
+        assert_eq!(
+            config.rules.get(&Rule::SubjectLength),
+            Some(&RuleConfig {
+                enabled: None,
+                severity: None
+            })
+        );
+    }
+
+    #[test]
+    fn parse_message_ticket_number_settings() {
+        let config = Config::parse("[rules.MessageTicketNumber]\nrequired = true").unwrap();
+        assert!(config.message_ticket_number.required);
+        assert!(!Config::default().message_ticket_number.required);
+    }
+
+    #[test]
+    fn parse_rule_severity() {
+        let config = Config::parse(
+            "[rules.SubjectPrefix]\n\
+            severity = \"hint\"\n\
+            [rules.MessageTicketNumber]\n\
+            severity = \"error\"",
+        )
+        .unwrap();
+        assert_eq!(
+            config.rule_severity(&Rule::SubjectPrefix),
+            Some(&IssueType::Hint)
+        );
+        assert_eq!(
+            config.rule_severity(&Rule::MessageTicketNumber),
+            Some(&IssueType::Error)
+        );
+        assert_eq!(config.rule_severity(&Rule::SubjectLength), None);
+    }
+
+    #[test]
+    fn parse_invalid_rule_severity() {
+        assert_eq!(
+            Config::parse("[rules.SubjectPrefix]\nseverity = \"warning\""),
+            Err("Invalid value for `severity` of rule SubjectPrefix: \"warning\"".to_string())
+        );
+    }
+
+    #[test]
+    fn select_rules() {
+        let mut config = Config::parse(
+            "[rules.SubjectPrefix]\n\
+            enabled = true\n\
+            [rules.SubjectMood]\n\
+            enabled = false",
+        )
+        .unwrap();
+        config.select_rules(
+            &[Rule::SubjectMood, Rule::DiffChangeset],
+            &[Rule::SubjectPrefix],
+            &[(Rule::SubjectLength, IssueType::Hint)],
+        );
+        assert_eq!(config.rule_enabled(&Rule::SubjectPrefix), Some(false));
+        assert_eq!(config.rule_enabled(&Rule::SubjectMood), Some(true));
+        assert_eq!(config.rule_enabled(&Rule::DiffChangeset), Some(true));
+        assert_eq!(config.rule_enabled(&Rule::SubjectLength), None);
+        assert_eq!(
+            config.rule_severity(&Rule::SubjectLength),
+            Some(&IssueType::Hint)
+        );
+    }
+
+    #[test]
+    fn parse_branch_rule() {
+        let config = Config::parse("[rules.BranchNameLength]\nenabled = false").unwrap();
+        assert_eq!(config.rule_enabled(&Rule::BranchNameLength), Some(false));
+    }
+
+    #[test]
+    fn parse_unknown_rule() {
+        assert_eq!(
+            Config::parse("[rules.Unknown]\nenabled = false"),
+            Err("Unknown rule in config file: Unknown".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_unknown_rule_setting() {
+        assert_eq!(
+            Config::parse("[rules.SubjectPrefix]\nmax_width = 1"),
+            Err("Unknown setting for rule SubjectPrefix: max_width".to_string())
+        );
+        let error = Config::parse("[rules.SubjectLength]\nwidth = 1").unwrap_err();
+        assert!(error.starts_with("Invalid settings for rule SubjectLength: "));
+    }
+
+    #[test]
+    fn parse_subject_mood_settings() {
+        let config = Config::parse(
+            "[rules.SubjectMood]\n\
+            verbs = [\"deploy\"]\n\
+            irregular_verbs = { spun = \"spin\" }\n\
+            exceptions = [\"Kubernetes\"]",
+        )
+        .unwrap();
+        assert_eq!(config.subject_mood.verbs, vec!["deploy"]);
+        assert_eq!(
+            config.subject_mood.irregular_verbs.get("spun"),
+            Some(&"spin".to_string())
+        );
+        assert_eq!(config.subject_mood.exceptions, vec!["Kubernetes"]);
+    }
+
+    #[test]
+    fn parse_conventional_commit_settings() {
+        let config = Config::parse(
+            "[rules.ConventionalCommit]\n\
+            enabled = true\n\
+            types = [\"feat\", \"fix\"]\n\
+            scope_pattern = \"^[a-z]+$\"",
+        )
+        .unwrap();
+        assert_eq!(config.rule_enabled(&Rule::ConventionalCommit), Some(true));
+        assert_eq!(config.conventional_commit.types, vec!["feat", "fix"]);
+        let pattern = config.conventional_commit.scope_pattern.unwrap();
+        assert!(pattern.is_match("api"));
+        assert!(!pattern.is_match("API"));
+
+        let default = Config::parse("[rules.ConventionalCommit]\nenabled = true").unwrap();
+        assert!(default
+            .conventional_commit
+            .types
+            .contains(&"feat".to_string()));
+        assert_eq!(default.conventional_commit.scope_pattern, None);
+    }
+
+    #[test]
+    fn parse_invalid_pattern() {
+        let error =
+            Config::parse("[rules.ConventionalCommit]\nscope_pattern = \"[a-z\"").unwrap_err();
+        assert!(error.starts_with("Invalid settings for rule ConventionalCommit: "));
+        assert!(error.contains("Invalid pattern \"[a-z\""));
+    }
+
+    #[test]
+    fn parse_invalid_enabled() {
+        assert_eq!(
+            Config::parse("[rules.SubjectPrefix]\nenabled = \"no\""),
+            Err("Invalid value for `enabled` of rule SubjectPrefix: \"no\"".to_string())
+        );
+    }
+
+    #[test]
+    fn find_config_file_in_parent_directory() {
+        let dir = test_dir("config_file_parent");
+        prepare_test_dir(&dir);
+        let sub_dir = dir.join("sub/dir");
+        fs::create_dir_all(&sub_dir).unwrap();
+        create_file(&dir.join("lintje.toml"), b"hints = false");
+
+        let path = find_config_file(&sub_dir).expect("No config file found");
+        assert_eq!(path, dir.join("lintje.toml"));
+        let config = read_config_file(&path).unwrap();
+        assert_eq!(config.path, Some(path));
+        assert_eq!(config.hints, Some(false));
+    }
+
+    #[test]
+    fn parse_ticket_syntaxes() {
+        let config = Config::parse(TICKET_SYNTAXES).unwrap();
+        let syntaxes = &config.tickets.syntaxes;
+        assert_eq!(
+            syntaxes
+                .iter()
+                .map(|s| s.name.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["number", "jira", "azure", "internal"]
+        );
+        assert_eq!(syntaxes[1].pattern.as_str(), "(?P<project>[A-Z]{2,})-\\d+");
+        assert_eq!(syntaxes[1].projects, vec!["ENG", "OPS"]);
+        assert_eq!(syntaxes[2].pattern.as_str(), "AB#\\d+");
+        assert!(syntaxes[2].projects.is_empty());
+    }
+
+    #[test]
+    fn parse_ticket_patterns_without_syntaxes() {
+        let config = Config::parse("[tickets.patterns.azure]\npattern = 'AB#\\d+'").unwrap();
+        assert_eq!(
+            config
+                .tickets
+                .syntaxes
+                .iter()
+                .map(|s| s.name.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["url", "repository", "number", "jira", "azure"]
+        );
+        assert_eq!(
+            Config::parse("[tickets]").unwrap().tickets,
+            TicketConfig::default()
+        );
+    }
+
+    #[test]
+    fn parse_invalid_ticket_syntaxes() {
+        assert_eq!(
+            Config::parse("[tickets]\nsyntaxes = [\"linear\"]"),
+            Err("Unknown ticket syntax: linear".to_string())
+        );
+        assert_eq!(
+            Config::parse(
+                "[tickets]\n\
+                syntaxes = [\"jira\"]\n\
+                [tickets.patterns.azure]\n\
+                pattern = 'AB#\\d+'"
+            ),
+            Err("Ticket syntax azure is defined, but not listed in `syntaxes`".to_string())
+        );
+        assert_eq!(
+            Config::parse("[tickets.patterns.azure]\npattern = 'AB#\\d+'\nprojects = [\"AB\"]"),
+            Err(
+                "The pattern of ticket syntax azure has no `project` group to match the \
+                projects against"
+                    .to_string()
+            )
+        );
+        let error = Config::parse("[tickets.patterns.azure]\npattern = 'AB#(\\d+'").unwrap_err();
+        assert!(
+            error.contains("Invalid pattern \"AB#(\\\\d+\""),
+            "{}",
+            error
+        );
+    }
+}
@@ -0,0 +1,293 @@
+use core::cmp::Reverse;
+use core::ops::Range;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use crate::config::ValidationContext;
+use crate::git::{commit_file_lines, is_commit_ignored, parse_commit_file};
+use crate::issue::{Applicability, Edit, Issue};
+
+/// Apply the fixes for the issues in the commit message file that can be fixed without changing
+/// the meaning of the message.
+///
+/// Returns the issues that were fixed.
+pub fn fix_commit_message_file(
+    path: &Path,
+    context: &ValidationContext,
+) -> Result<Vec<Issue>, String> {
+    let contents = fs::read_to_string(path).map_err(|e| {
+        format!(
+            "Unable to read commit message file contents: {}\n{}",
+            path.to_string_lossy(),
+            e
+        )
+    })?;
+    let mut commit = parse_commit_file(&contents);
+    if is_commit_ignored(&commit) {
+        return Ok(vec![]);
+    }
+    commit.validate(context);
+
+    let lines = commit_file_lines(&contents);
+    let (fixed_contents, fixed_issues) = fix_contents(&contents, &lines, commit.issues);
+    if fixed_issues.is_empty() {
+        return Ok(fixed_issues);
+    }
+    fs::write(path, fixed_contents).map_err(|e| {
+        format!(
+            "Unable to write fixes to commit message file: {}\n{}",
+            path.to_string_lossy(),
+            e
+        )
+    })?;
+    Ok(fixed_issues)
+}
+
+// Apply the safe suggestions of the given issues to the file contents. The lines are the commit
+// message lines with their index in the file contents.
+fn fix_contents(
+    contents: &str,
+    lines: &[(usize, String)],
+    issues: Vec<Issue>,
+) -> (String, Vec<Issue>) {
+    let fixable_issues = issues
+        .into_iter()
+        .filter(|issue| issue.is_fixable())
+        .collect::<Vec<Issue>>();
+    // The edits with the index of the issue they fix
+    let mut edits = vec![];
+    for (issue_index, issue) in fixable_issues.iter().enumerate() {
+        for suggestion in &issue.suggestions {
+            if suggestion.applicability == Applicability::Safe {
+                edits.extend(
+                    suggestion
+                        .edits
+                        .iter()
+                        .map(|edit| (issue_index, edit.clone())),
+                );
+            }
+        }
+    }
+    // An issue is only fixed when all its edits were applied
+    let mut fixed = vec![true; fixable_issues.len()];
+    let fixed_issues = |fixed: Vec<bool>| {
+        fixable_issues
+            .into_iter()
+            .zip(fixed)
+            .filter_map(|(issue, fixed)| fixed.then_some(issue))
+            .collect::<Vec<Issue>>()
+    };
+    if edits.is_empty() {
+        return (contents.to_string(), fixed_issues(fixed));
+    }
+
+    let mut file_lines = contents
+        .lines()
+        .map(|line| line.to_string())
+        .collect::<Vec<String>>();
+    let file_index = |line: usize| lines.get(line - 1).map(|(index, _)| *index);
+    let end_index = lines.last().map_or(0, |(index, _)| index + 1);
+    let mut replacements = BTreeMap::<usize, Vec<(usize, Range<usize>, String)>>::new();
+    let mut removals = vec![];
+    let mut insertions = BTreeMap::<usize, Vec<String>>::new();
+    for (issue_index, edit) in edits {
+        match edit {
+            Edit::Replace { line, range, text } => match file_index(line) {
+                Some(index) => {
+                    replacements
+                        .entry(index)
+                        .or_default()
+                        .push((issue_index, range, text));
+                }
+                None => fixed[issue_index] = false,
+            },
+            Edit::RemoveLine { line } => match file_index(line) {
+                Some(index) => removals.push(index),
+                None => fixed[issue_index] = false,
+            },
+            Edit::InsertLine { line, text } => {
+                let index = file_index(line).unwrap_or(end_index);
+                insertions.entry(index).or_default().push(text);
+            }
+            Edit::AppendLine { text } => {
+                insertions.entry(end_index).or_default().push(text);
+            }
+            // The branch name is not part of the commit message file
+            Edit::ReplaceBranch { .. } => fixed[issue_index] = false,
+        }
+    }
+
+    for (index, mut line_replacements) in replacements {
+        // Replace from the end of the line, so the ranges of earlier replacements stay valid.
+        // Overlapping replacements are skipped, so their issues are not fixed.
+        line_replacements.sort_by_key(|(_, range, _)| Reverse(range.start));
+        let mut previous_start = usize::MAX;
+        for (issue_index, range, text) in line_replacements {
+            let line = file_lines
+                .get_mut(index)
+                .filter(|line| range.end <= previous_start && line.get(range.clone()).is_some());
+            match line {
+                Some(line) => {
+                    line.replace_range(range.clone(), &text);
+                    previous_start = range.start;
+                }
+                None => fixed[issue_index] = false,
+            }
+        }
+    }
+
+    let mut fixed_lines = vec![];
+    for index in 0..=file_lines.len() {
+        if let Some(new_lines) = insertions.remove(&index) {
+            fixed_lines.extend(new_lines);
+        }
+        if removals.contains(&index) {
+            continue;
+        }
+        if let Some(line) = file_lines.get(index) {
+            fixed_lines.push(line.to_string());
+        }
+    }
+    let mut fixed_contents = fixed_lines.join("\n");
+    if contents.ends_with('\n') {
+        fixed_contents.push('\n');
+    }
+    (fixed_contents, fixed_issues(fixed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fix_contents;
+    use crate::commit::Commit;
+    use crate::config::ValidationContext;
+    use crate::issue::{Edit, Issue, Position, Suggestion};
+    use crate::rule::Rule;
+    use crate::test::*;
+
+    // Lines of the test messages, which contain no comments.
+    fn message_lines(contents: &str) -> Vec<(usize, String)> {
+        let mut lines = contents
+            .lines()
+            .enumerate()
+            .skip_while(|(_, line)| line.is_empty())
+            .map(|(index, line)| (index, line.trim_end().to_string()))
+            .collect::<Vec<(usize, String)>>();
+        while let Some((_, line)) = lines.last() {
+            if !line.starts_with('#') {
+                break;
+            }
+            lines.pop();
+        }
+        lines
+    }
+
+    fn fix(commit: Commit, contents: &str) -> (String, Vec<Rule>) {
+        let mut commit = commit;
+        commit.validate(&ValidationContext::default());
+        let (fixed, issues) = fix_contents(contents, &message_lines(contents), commit.issues);
+        (fixed, issues.into_iter().map(|issue| issue.rule).collect())
+    }
+
+    #[test]
+    fn fix_subject() {
+        let contents = " fix the bug.\n\nSome message body.\n# Please enter the commit message\n";
+        let subject_commit = commit(
+            " fix the bug.",
+            "\nSome message body.\n\nlintje:disable MessageTicketNumber",
+        );
+        let (fixed, rules) = fix(subject_commit, contents);
+        assert_eq!(
+            fixed,
+            "fix the bug\n\nSome message body.\n# Please enter the commit message\n"
+        );
+        assert_eq!(
+            rules,
+            vec![Rule::SubjectWhitespace, Rule::SubjectPunctuation]
+        );
+
+        let contents = "fix the bug\n\nSome message body.\n";
+        let subject_commit = commit("fix the bug", "\nSome message body.");
+        let (fixed, rules) = fix(subject_commit, contents);
+        assert_eq!(fixed, "Fix the bug\n\nSome message body.\n");
+        assert_eq!(rules, vec![Rule::SubjectCapitalization]);
+    }
+
+    #[test]
+    fn fix_empty_first_line() {
+        let contents = "Fix the bug\nSome message body.\n";
+        let subject_commit = commit("Fix the bug", "Some message body.");
+        let (fixed, rules) = fix(subject_commit, contents);
+        assert_eq!(fixed, "Fix the bug\n\nSome message body.\n");
+        assert_eq!(rules, vec![Rule::MessageEmptyFirstLine]);
+    }
+
+    #[test]
+    fn fix_trailer_lines() {
+        let contents = "Fix the bug\n\
+            \n\
+            Co-authored-by: Person A <a@example.com>\n\
+            Some message body.\n\
+            # Please enter the commit message\n";
+        let subject_commit = commit(
+            "Fix the bug",
+            "\nCo-authored-by: Person A <a@example.com>\nSome message body.",
+        );
+        let (fixed, rules) = fix(subject_commit, contents);
+        assert_eq!(
+            fixed,
+            "Fix the bug\n\
+            \n\
+            Some message body.\n\
+            \n\
+            Co-authored-by: Person A <a@example.com>\n\
+            # Please enter the commit message\n"
+        );
+        assert_eq!(rules, vec![Rule::MessageTrailerLine]);
+    }
+
+    #[test]
+    fn unfixable_issues() {
+        let contents = "Fixed bug\n";
+        let subject_commit = commit("Fixed bug", "");
+        let (fixed, rules) = fix(subject_commit, contents);
+        assert_eq!(fixed, contents);
+        assert_eq!(rules, Vec::<Rule>::new());
+    }
+
+    #[test]
+    fn overlapping_suggestions() {
+        let contents = "fixed the bug.\n";
+        let replacement = |rule: Rule, range, text: &str| {
+            Issue::error(
+                rule,
+                "Some issue".to_string(),
+                Position::Subject { line: 1, column: 1 },
+                vec![],
+            )
+            .with_suggestion(Suggestion::safe(
+                "Some suggestion".to_string(),
+                vec![Edit::Replace {
+                    line: 1,
+                    range,
+                    text: text.to_string(),
+                }],
+            ))
+        };
+        let issues = vec![
+            replacement(Rule::SubjectMood, 0..5, "Fix"),
+            replacement(Rule::SubjectCapitalization, 0..1, "F"),
+            replacement(Rule::SubjectPunctuation, 13..14, ""),
+        ];
+        let (fixed, issues) = fix_contents(contents, &message_lines(contents), issues);
+        assert_eq!(fixed, "Fix the bug\n");
+        // The overlapping replacement was not applied
+        assert_eq!(
+            issues
+                .into_iter()
+                .map(|issue| issue.rule)
+                .collect::<Vec<Rule>>(),
+            vec![Rule::SubjectMood, Rule::SubjectPunctuation]
+        );
+    }
+}
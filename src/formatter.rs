@@ -1,3 +1,11 @@
+pub mod compact;
+pub mod github;
+pub mod gitlab;
+pub mod json;
+pub mod junit;
+pub mod sarif;
+
+use serde::Serialize;
 use std::fmt;
 use std::io;
 use termcolor::{Color, ColorSpec, WriteColor};
@@ -7,6 +15,55 @@ use crate::commit::Commit;
 use crate::issue::{Context, ContextType, Issue, IssueType, Position};
 use crate::utils::display_width;
 
+#[derive(clap::ArgEnum, Clone, Debug, PartialEq)]
+pub enum OutputFormat {
+    Text,
+    Json,
+    Sarif,
+    Junit,
+    Github,
+    Gitlab,
+    Compact,
+}
+
+/// The issue counts of all inspected commits and branches.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct Summary {
+    pub commit_count: usize,
+    pub ignored_commit_count: usize,
+    pub branch_count: usize,
+    pub error_count: usize,
+    pub hint_count: usize,
+}
+
+impl Summary {
+    pub fn new(commits: &[Commit], branches: &[Branch]) -> Self {
+        let mut summary = Self::default();
+        for commit in commits {
+            if commit.ignored {
+                summary.ignored_commit_count += 1;
+                continue;
+            }
+            summary.commit_count += 1;
+            summary.count_issues(&commit.issues);
+        }
+        for branch in branches {
+            summary.branch_count += 1;
+            summary.count_issues(&branch.issues);
+        }
+        summary
+    }
+
+    fn count_issues(&mut self, issues: &[Issue]) {
+        for issue in issues {
+            match issue.r#type {
+                IssueType::Error => self.error_count += 1,
+                IssueType::Hint => self.hint_count += 1,
+            }
+        }
+    }
+}
+
 enum Prefix {
     Pipe,
     Scissors,
@@ -75,6 +132,13 @@ pub fn issue_type_color(issue_type: &IssueType) -> ColorSpec {
     }
 }
 
+/// Returns the issues to report, without hints if hints are disabled.
+pub fn shown_issues(issues: &[Issue], hints: bool) -> impl Iterator<Item = &Issue> {
+    issues
+        .iter()
+        .filter(move |issue| hints || issue.r#type != IssueType::Hint)
+}
+
 pub fn formatted_position(out: &mut impl WriteColor, position: &Position) -> io::Result<()> {
     match position {
         Position::Subject { line, column } | Position::MessageLine { line, column } => {
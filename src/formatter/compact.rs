@@ -0,0 +1,108 @@
+use std::io::{self, Write};
+use termcolor::Buffer;
+
+use crate::branch::Branch;
+use crate::commit::Commit;
+use crate::formatter::{formatted_position, shown_issues};
+use crate::issue::Issue;
+
+/// Write every issue on a single line, without context. Suited for output that is prefixed per
+/// line, like the `remote:` lines Git prints for the output of server-side hooks.
+pub fn write_issues(
+    out: &mut impl Write,
+    commits: &[Commit],
+    branches: &[Branch],
+    hints: bool,
+) -> io::Result<()> {
+    for commit in commits.iter().filter(|commit| !commit.ignored) {
+        let sha = commit.short_sha.as_deref().unwrap_or("0000000");
+        for issue in shown_issues(&commit.issues, hints) {
+            write_issue(out, issue, sha)?;
+        }
+    }
+    for branch in branches {
+        for issue in shown_issues(&branch.issues, hints) {
+            write_issue(out, issue, &format!("Branch {}", branch.name))?;
+        }
+    }
+    Ok(())
+}
+
+fn write_issue(out: &mut impl Write, issue: &Issue, location: &str) -> io::Result<()> {
+    let mut position = Buffer::no_color();
+    formatted_position(&mut position, &issue.position)?;
+    writeln!(
+        out,
+        "{}{}: {}[{}]: {}",
+        location,
+        String::from_utf8_lossy(position.as_slice()),
+        issue.r#type,
+        issue.rule,
+        issue.message
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::write_issues;
+    use crate::branch::Branch;
+    use crate::commit::Commit;
+    use crate::issue::{Issue, Position};
+    use crate::rule::Rule;
+    use crate::test::*;
+
+    fn issues(commits: &[Commit], branches: &[Branch], hints: bool) -> String {
+        let mut out = vec![];
+        write_issues(&mut out, commits, branches, hints).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn commit_and_branch_issues() {
+        let mut ignored_commit = commit("Merge pull request #123 from repo", "");
+        ignored_commit.ignored = true;
+        ignored_commit.issues.push(Issue::error(
+            Rule::SubjectLength,
+            "The subject is too long".to_string(),
+            Position::Subject { line: 1, column: 1 },
+            vec![],
+        ));
+        let mut commit = commit("Fixing bug", "");
+        commit.short_sha = Some("1234567".to_string());
+        commit.issues.push(Issue::error(
+            Rule::SubjectMood,
+            "The subject does not use the imperative grammatical mood".to_string(),
+            Position::Subject { line: 1, column: 1 },
+            vec![],
+        ));
+        commit.issues.push(Issue::hint(
+            Rule::MessageTicketNumber,
+            "The message body does not contain a ticket or issue number".to_string(),
+            Position::MessageLine { line: 3, column: 1 },
+            vec![],
+        ));
+        let mut branch = Branch::new("fix-123".to_string());
+        branch.issues.push(Issue::error(
+            Rule::BranchNameTicketNumber,
+            "A ticket number was detected in the branch name".to_string(),
+            Position::Branch { column: 1 },
+            vec![],
+        ));
+        let commits = vec![commit, ignored_commit];
+
+        assert_eq!(
+            issues(&commits, &[branch], true),
+            "1234567:1:1: Error[SubjectMood]: \
+            The subject does not use the imperative grammatical mood\n\
+            1234567:3:1: Hint[MessageTicketNumber]: \
+            The message body does not contain a ticket or issue number\n\
+            Branch fix-123:1: Error[BranchNameTicketNumber]: \
+            A ticket number was detected in the branch name\n"
+        );
+        assert_eq!(
+            issues(&commits, &[], false),
+            "1234567:1:1: Error[SubjectMood]: \
+            The subject does not use the imperative grammatical mood\n"
+        );
+    }
+}
@@ -0,0 +1,161 @@
+use std::io::{self, Write};
+use termcolor::Buffer;
+
+use crate::branch::Branch;
+use crate::commit::Commit;
+use crate::formatter::{formatted_position, shown_issues};
+use crate::issue::{Issue, IssueType};
+
+/// Write the issues as GitHub Actions workflow commands, so they are shown as annotations.
+///
+/// For more information about workflow commands, see:
+/// <https://docs.github.com/en/actions/using-workflows/workflow-commands-for-github-actions>
+pub fn write_commands(
+    out: &mut impl Write,
+    commits: &[Commit],
+    branches: &[Branch],
+    hints: bool,
+) -> io::Result<()> {
+    for commit in commits.iter().filter(|commit| !commit.ignored) {
+        let sha = commit.short_sha.as_deref().unwrap_or("0000000");
+        for issue in shown_issues(&commit.issues, hints) {
+            let location = format!("{}{}: {}", sha, position(issue)?, commit.subject);
+            write_command(out, issue, &location)?;
+        }
+    }
+    for branch in branches {
+        for issue in shown_issues(&branch.issues, hints) {
+            let location = format!("Branch{}: {}", position(issue)?, branch.name);
+            write_command(out, issue, &location)?;
+        }
+    }
+    Ok(())
+}
+
+fn position(issue: &Issue) -> io::Result<String> {
+    let mut out = Buffer::no_color();
+    formatted_position(&mut out, &issue.position)?;
+    Ok(String::from_utf8_lossy(out.as_slice()).to_string())
+}
+
+fn write_command(out: &mut impl Write, issue: &Issue, location: &str) -> io::Result<()> {
+    let command = match issue.r#type {
+        IssueType::Error => "error",
+        IssueType::Hint => "notice",
+    };
+    let message = format!(
+        "{}\n{}\nhelp: {}",
+        issue.message,
+        location,
+        issue.rule.link()
+    );
+    writeln!(
+        out,
+        "::{} title={}::{}",
+        command,
+        escape_property(&issue.rule.to_string()),
+        escape_data(&message)
+    )
+}
+
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{escape_data, escape_property, write_commands};
+    use crate::branch::Branch;
+    use crate::commit::Commit;
+    use crate::issue::{Issue, Position};
+    use crate::rule::Rule;
+    use crate::test::*;
+
+    fn commands(commits: &[Commit], branches: &[Branch], hints: bool) -> String {
+        let mut out = vec![];
+        write_commands(&mut out, commits, branches, hints).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn escape_values() {
+        assert_eq!(escape_data("100%\r\nDone: a, b"), "100%25%0D%0ADone: a, b");
+        assert_eq!(escape_property("a: b, c"), "a%3A b%2C c");
+    }
+
+    #[test]
+    fn commit_issues() {
+        let mut commit = commit("Fixing bug", "");
+        commit.issues.push(Issue::error(
+            Rule::SubjectMood,
+            "The subject does not use the imperative grammatical mood".to_string(),
+            Position::Subject { line: 1, column: 1 },
+            vec![],
+        ));
+        commit.issues.push(Issue::hint(
+            Rule::MessageTicketNumber,
+            "The message body does not contain a ticket or issue number".to_string(),
+            Position::MessageLine { line: 3, column: 1 },
+            vec![],
+        ));
+        let mut ignored_commit = crate::test::commit("Merge pull request #1 from repo", "");
+        ignored_commit.ignored = true;
+        ignored_commit.issues.push(Issue::error(
+            Rule::SubjectLength,
+            "Ignored".to_string(),
+            Position::Subject { line: 1, column: 1 },
+            vec![],
+        ));
+
+        assert_eq!(
+            commands(&[commit, ignored_commit], &[], true),
+            "::error title=SubjectMood::\
+            The subject does not use the imperative grammatical mood%0A\
+            aaaaaaa:1:1: Fixing bug%0A\
+            help: https://r.lintje.dev/r/SubjectMood\n\
+            ::notice title=MessageTicketNumber::\
+            The message body does not contain a ticket or issue number%0A\
+            aaaaaaa:3:1: Fixing bug%0A\
+            help: https://r.lintje.dev/r/MessageTicketNumber\n"
+        );
+    }
+
+    #[test]
+    fn without_hints() {
+        let mut commit = commit("Fix bug", "");
+        commit.issues.push(Issue::hint(
+            Rule::MessageTicketNumber,
+            "The message body does not contain a ticket or issue number".to_string(),
+            Position::MessageLine { line: 3, column: 1 },
+            vec![],
+        ));
+
+        assert_eq!(commands(&[commit], &[], false), "");
+    }
+
+    #[test]
+    fn branch_issues() {
+        let mut branch = Branch::new("fix-123".to_string());
+        branch.issues.push(Issue::error(
+            Rule::BranchNameTicketNumber,
+            "A ticket number was detected in the branch name".to_string(),
+            Position::Branch { column: 1 },
+            vec![],
+        ));
+
+        assert_eq!(
+            commands(&[], &[branch], true),
+            "::error title=BranchNameTicketNumber::\
+            A ticket number was detected in the branch name%0A\
+            Branch:1: fix-123%0A\
+            help: https://r.lintje.dev/r/BranchNameTicketNumber\n"
+        );
+    }
+}
@@ -0,0 +1,184 @@
+use serde::Serialize;
+use std::io;
+
+use crate::branch::Branch;
+use crate::commit::Commit;
+use crate::formatter::shown_issues;
+use crate::issue::{Issue, IssueType, Position};
+use crate::utils::stable_hash;
+
+/// The issues as a GitLab Code Quality report.
+///
+/// Commits and the branch have no file in the repository, so the location path describes the
+/// commit SHA or branch name instead.
+///
+/// For more information about the report format, see:
+/// <https://docs.gitlab.com/ee/ci/testing/code_quality.html#implement-a-custom-tool>
+#[derive(Debug, Serialize)]
+#[serde(transparent)]
+pub struct Report {
+    violations: Vec<Violation>,
+}
+
+#[derive(Debug, Serialize)]
+struct Violation {
+    description: String,
+    check_name: String,
+    fingerprint: String,
+    severity: &'static str,
+    location: Location,
+}
+
+#[derive(Debug, Serialize)]
+struct Location {
+    path: String,
+    lines: Lines,
+}
+
+#[derive(Debug, Serialize)]
+struct Lines {
+    begin: usize,
+}
+
+impl Report {
+    pub fn new(commits: &[Commit], branches: &[Branch], hints: bool) -> Self {
+        let mut violations = vec![];
+        for commit in commits.iter().filter(|commit| !commit.ignored) {
+            let sha = commit.short_sha.as_deref().unwrap_or("0000000");
+            // The long SHA identifies the commit in the fingerprint, even when the short SHA of
+            // two commits is the same.
+            let id = commit.long_sha.as_deref().unwrap_or(sha);
+            for issue in shown_issues(&commit.issues, hints) {
+                violations.push(violation(issue, format!("Commit {}", sha), id));
+            }
+        }
+        for branch in branches {
+            for issue in shown_issues(&branch.issues, hints) {
+                violations.push(violation(
+                    issue,
+                    format!("Branch {}", branch.name),
+                    &branch.name,
+                ));
+            }
+        }
+        Self { violations }
+    }
+
+    pub fn write(&self, out: &mut impl io::Write) -> io::Result<()> {
+        serde_json::to_writer(&mut *out, self)?;
+        writeln!(out)
+    }
+}
+
+fn violation(issue: &Issue, path: String, id: &str) -> Violation {
+    let line = match issue.position {
+        Position::Subject { line, .. } | Position::MessageLine { line, .. } => line,
+        Position::Branch { .. } | Position::Diff => 1,
+    };
+    let severity = match issue.r#type {
+        IssueType::Error => "major",
+        IssueType::Hint => "info",
+    };
+    Violation {
+        description: format!("{} ({})", issue.message, issue.rule.link()),
+        check_name: issue.rule.to_string(),
+        fingerprint: stable_hash(&format!(
+            "{}\0{}\0{:?}\0{}",
+            id, issue.rule, issue.position, issue.message
+        )),
+        severity,
+        location: Location {
+            path,
+            lines: Lines { begin: line },
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Report;
+    use crate::branch::Branch;
+    use crate::issue::{Issue, Position};
+    use crate::rule::Rule;
+    use crate::test::*;
+    use serde_json::json;
+
+    fn report_json(report: &Report) -> serde_json::Value {
+        let mut out = vec![];
+        report.write(&mut out).unwrap();
+        serde_json::from_slice(&out).unwrap()
+    }
+
+    #[test]
+    fn commit_and_branch_issues() {
+        let mut commit = commit("Fixing bug", "");
+        commit.issues.push(Issue::error(
+            Rule::SubjectMood,
+            "The subject does not use the imperative grammatical mood".to_string(),
+            Position::Subject { line: 1, column: 1 },
+            vec![],
+        ));
+        commit.issues.push(Issue::hint(
+            Rule::MessageTicketNumber,
+            "The message body does not contain a ticket or issue number".to_string(),
+            Position::MessageLine { line: 3, column: 1 },
+            vec![],
+        ));
+        let mut branch = Branch::new("fix-123".to_string());
+        branch.issues.push(Issue::error(
+            Rule::BranchNameTicketNumber,
+            "A ticket number was detected in the branch name".to_string(),
+            Position::Branch { column: 1 },
+            vec![],
+        ));
+        let report = Report::new(&[commit], &[branch], true);
+        let json = report_json(&report);
+
+        let violations = json.as_array().unwrap();
+        assert_eq!(violations.len(), 3);
+        let mut first = violations[0].clone();
+        let fingerprint = first["fingerprint"].take();
+        assert_eq!(
+            first,
+            json!({
+                "description": "The subject does not use the imperative grammatical mood \
+                    (https://r.lintje.dev/r/SubjectMood)",
+                "check_name": "SubjectMood",
+                "fingerprint": null,
+                "severity": "major",
+                "location": {
+                    "path": "Commit aaaaaaa",
+                    "lines": { "begin": 1 }
+                }
+            })
+        );
+        assert_eq!(fingerprint.as_str().unwrap().len(), 16);
+        assert_eq!(violations[1]["severity"], "info");
+        assert_eq!(violations[1]["location"]["lines"]["begin"], 3);
+        assert_eq!(violations[2]["check_name"], "BranchNameTicketNumber");
+        assert_eq!(violations[2]["location"]["path"], "Branch fix-123");
+        assert_ne!(violations[0]["fingerprint"], violations[1]["fingerprint"]);
+    }
+
+    #[test]
+    fn without_hints_and_ignored_commits() {
+        let mut commit = commit("Fix bug", "");
+        commit.issues.push(Issue::hint(
+            Rule::MessageTicketNumber,
+            "The message body does not contain a ticket or issue number".to_string(),
+            Position::MessageLine { line: 3, column: 1 },
+            vec![],
+        ));
+        let mut ignored_commit = crate::test::commit("Merge pull request #1 from repo", "");
+        ignored_commit.ignored = true;
+        ignored_commit.issues.push(Issue::error(
+            Rule::SubjectLength,
+            "Ignored".to_string(),
+            Position::Subject { line: 1, column: 1 },
+            vec![],
+        ));
+        let report = Report::new(&[commit, ignored_commit], &[], false);
+
+        assert_eq!(report_json(&report), json!([]));
+    }
+}
@@ -0,0 +1,275 @@
+use serde::Serialize;
+use std::io;
+
+use crate::branch::Branch;
+use crate::commit::Commit;
+use crate::formatter::{shown_issues, Summary};
+use crate::issue::{Issue, IssueType};
+use crate::rule::Rule;
+
+/// The lint result in the JSON output format.
+#[derive(Debug, Serialize)]
+pub struct Report<'a> {
+    commits: Vec<CommitReport<'a>>,
+    branches: Vec<BranchReport<'a>>,
+    summary: Summary,
+}
+
+#[derive(Debug, Serialize)]
+struct CommitReport<'a> {
+    sha: Option<&'a str>,
+    short_sha: Option<&'a str>,
+    subject: &'a str,
+    ignored: bool,
+    checked_rules: &'a [Rule],
+    ignored_rules: &'a [Rule],
+    issues: Vec<&'a Issue>,
+}
+
+#[derive(Debug, Serialize)]
+struct BranchReport<'a> {
+    name: &'a str,
+    checked_rules: &'a [Rule],
+    issues: Vec<&'a Issue>,
+}
+
+impl<'a> Report<'a> {
+    pub fn new(
+        commits: &'a [Commit],
+        branches: &'a [Branch],
+        summary: &'a Summary,
+        hints: bool,
+    ) -> Self {
+        let commits = commits
+            .iter()
+            .map(|commit| CommitReport {
+                sha: commit.long_sha.as_deref(),
+                short_sha: commit.short_sha.as_deref(),
+                subject: &commit.subject,
+                ignored: commit.ignored,
+                checked_rules: &commit.checked_rules,
+                ignored_rules: &commit.ignored_rules,
+                issues: shown_issues(&commit.issues, hints).collect(),
+            })
+            .collect::<Vec<CommitReport>>();
+        let branches = branches
+            .iter()
+            .map(|branch| BranchReport {
+                name: &branch.name,
+                checked_rules: &branch.checked_rules,
+                issues: shown_issues(&branch.issues, hints).collect(),
+            })
+            .collect::<Vec<BranchReport>>();
+        // Only count the hints that are part of the report
+        let hint_count = commits
+            .iter()
+            .flat_map(|commit| &commit.issues)
+            .chain(branches.iter().flat_map(|branch| &branch.issues))
+            .filter(|issue| issue.r#type == IssueType::Hint)
+            .count();
+        Self {
+            commits,
+            branches,
+            summary: Summary {
+                hint_count,
+                ..summary.clone()
+            },
+        }
+    }
+
+    pub fn write(&self, out: &mut impl io::Write) -> io::Result<()> {
+        serde_json::to_writer(&mut *out, self)?;
+        writeln!(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Report;
+    use crate::branch::Branch;
+    use crate::formatter::Summary;
+    use crate::issue::{Context, Edit, Issue, Position, Suggestion};
+    use crate::rule::Rule;
+    use crate::test::*;
+    use core::ops::Range;
+    use serde_json::json;
+
+    fn report_json(report: &Report) -> serde_json::Value {
+        let mut out = vec![];
+        report.write(&mut out).unwrap();
+        serde_json::from_slice(&out).unwrap()
+    }
+
+    #[test]
+    fn commit_with_issues() {
+        let mut commit = commit("Fixing bug", "");
+        commit.checked_rules = vec![Rule::SubjectMood];
+        commit.issues.push(Issue::error(
+            Rule::SubjectMood,
+            "The subject does not use the imperative grammatical mood".to_string(),
+            Position::Subject { line: 1, column: 1 },
+            vec![Context::subject_error(
+                "Fixing bug".to_string(),
+                Range { start: 0, end: 6 },
+                "Use the imperative mood for the subject".to_string(),
+            )],
+        ));
+        commit.issues.push(Issue::hint(
+            Rule::MessageTicketNumber,
+            "The message body does not contain a ticket or issue number".to_string(),
+            Position::MessageLine { line: 3, column: 1 },
+            vec![],
+        ));
+        let commits = vec![commit];
+        let summary = Summary::new(&commits, &[]);
+        let report = Report::new(&commits, &[], &summary, true);
+
+        assert_eq!(
+            report_json(&report),
+            json!({
+                "commits": [{
+                    "sha": "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+                    "short_sha": "aaaaaaa",
+                    "subject": "Fixing bug",
+                    "ignored": false,
+                    "checked_rules": ["SubjectMood"],
+                    "ignored_rules": [],
+                    "issues": [
+                        {
+                            "type": "error",
+                            "rule": "SubjectMood",
+                            "message": "The subject does not use the imperative grammatical mood",
+                            "position": { "type": "subject", "line": 1, "column": 1 },
+                            "context": [{
+                                "type": "error",
+                                "line": 1,
+                                "content": "Fixing bug",
+                                "range": { "start": 0, "end": 6 },
+                                "message": "Use the imperative mood for the subject"
+                            }],
+                            "suggestions": []
+                        },
+                        {
+                            "type": "hint",
+                            "rule": "MessageTicketNumber",
+                            "message": "The message body does not contain a ticket or issue number",
+                            "position": { "type": "message_line", "line": 3, "column": 1 },
+                            "context": [],
+                            "suggestions": []
+                        }
+                    ]
+                }],
+                "branches": [],
+                "summary": {
+                    "commit_count": 1,
+                    "ignored_commit_count": 0,
+                    "branch_count": 0,
+                    "error_count": 1,
+                    "hint_count": 1
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn without_hints() {
+        let mut commit = commit("Fix bug", "");
+        commit.issues.push(Issue::hint(
+            Rule::MessageTicketNumber,
+            "The message body does not contain a ticket or issue number".to_string(),
+            Position::MessageLine { line: 3, column: 1 },
+            vec![],
+        ));
+        let commits = vec![commit];
+        let summary = Summary::new(&commits, &[]);
+        let report = Report::new(&commits, &[], &summary, false);
+        let json = report_json(&report);
+
+        assert_eq!(json["commits"][0]["issues"], json!([]));
+        assert_eq!(json["summary"]["hint_count"], json!(0));
+    }
+
+    #[test]
+    fn ignored_commit_and_branch() {
+        let mut commit = commit("Merge pull request #123 from repo", "");
+        commit.ignored = true;
+        let commits = vec![commit];
+        let mut branch = Branch::new("fix-123".to_string());
+        branch.checked_rules = vec![Rule::BranchNameTicketNumber];
+        branch.issues.push(Issue::error(
+            Rule::BranchNameTicketNumber,
+            "A ticket number was detected in the branch name".to_string(),
+            Position::Branch { column: 1 },
+            vec![],
+        ));
+        let branches = vec![branch];
+        let summary = Summary::new(&commits, &branches);
+        let report = Report::new(&commits, &branches, &summary, true);
+        let json = report_json(&report);
+
+        assert_eq!(json["commits"][0]["ignored"], json!(true));
+        assert_eq!(
+            json["branches"],
+            json!([{
+                "name": "fix-123",
+                "checked_rules": ["BranchNameTicketNumber"],
+                "issues": [{
+                    "type": "error",
+                    "rule": "BranchNameTicketNumber",
+                    "message": "A ticket number was detected in the branch name",
+                    "position": { "type": "branch", "column": 1 },
+                    "context": [],
+                    "suggestions": []
+                }]
+            }])
+        );
+        assert_eq!(
+            json["summary"],
+            json!({
+                "commit_count": 0,
+                "ignored_commit_count": 1,
+                "branch_count": 1,
+                "error_count": 1,
+                "hint_count": 0
+            })
+        );
+    }
+
+    #[test]
+    fn issue_with_suggestion() {
+        let mut commit = commit("Fix bug.", "");
+        commit.issues.push(
+            Issue::error(
+                Rule::SubjectPunctuation,
+                "The subject ends with a punctuation character: `.`".to_string(),
+                Position::Subject { line: 1, column: 8 },
+                vec![],
+            )
+            .with_suggestion(Suggestion::safe(
+                "Remove punctuation from the end of the subject".to_string(),
+                vec![Edit::Replace {
+                    line: 1,
+                    range: Range { start: 7, end: 8 },
+                    text: "".to_string(),
+                }],
+            )),
+        );
+        let commits = vec![commit];
+        let summary = Summary::new(&commits, &[]);
+        let report = Report::new(&commits, &[], &summary, true);
+
+        assert_eq!(
+            report_json(&report)["commits"][0]["issues"][0]["suggestions"],
+            json!([{
+                "message": "Remove punctuation from the end of the subject",
+                "applicability": "safe",
+                "edits": [{
+                    "type": "replace",
+                    "line": 1,
+                    "range": { "start": 7, "end": 8 },
+                    "text": ""
+                }]
+            }])
+        );
+    }
+}
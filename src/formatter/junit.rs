@@ -0,0 +1,314 @@
+use std::io::{self, Write};
+use termcolor::Buffer;
+
+use crate::branch::Branch;
+use crate::commit::Commit;
+use crate::formatter::{formatted_branch_issue, formatted_commit_issue};
+use crate::issue::{Issue, IssueType};
+
+// A test case in the report: an inspected commit or the branch.
+struct TestCase {
+    classname: &'static str,
+    name: String,
+    failures: Vec<(String, String, String)>,
+    skipped: Option<(String, String)>,
+}
+
+/// Write the lint result as a `JUnit` XML report.
+///
+/// Every commit and the branch is a test case. Errors are reported as failures. Commits without
+/// errors, but with hints, and ignored commits are reported as skipped.
+pub fn write_report(
+    out: &mut impl Write,
+    commits: &[Commit],
+    branches: &[Branch],
+    hints: bool,
+) -> io::Result<()> {
+    let mut test_cases = vec![];
+    for commit in commits {
+        let sha = commit.short_sha.as_deref().unwrap_or("0000000");
+        let name = format!("{} {}", sha, commit.subject);
+        if commit.ignored {
+            test_cases.push(TestCase {
+                classname: "commit",
+                name,
+                failures: vec![],
+                skipped: Some(("Commit ignored".to_string(), "".to_string())),
+            });
+            continue;
+        }
+        test_cases.push(test_case(
+            "commit",
+            name,
+            &commit.issues,
+            hints,
+            |out, issue| formatted_commit_issue(out, commit, issue),
+        )?);
+    }
+    for branch in branches {
+        test_cases.push(test_case(
+            "branch",
+            branch.name.to_string(),
+            &branch.issues,
+            hints,
+            |out, issue| formatted_branch_issue(out, branch, issue),
+        )?);
+    }
+
+    let tests = test_cases.len();
+    let failures = test_cases.iter().filter(|t| !t.failures.is_empty()).count();
+    let skipped = test_cases.iter().filter(|t| t.skipped.is_some()).count();
+    writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        out,
+        r#"<testsuites name="Lintje" tests="{}" failures="{}" errors="0" skipped="{}">"#,
+        tests, failures, skipped
+    )?;
+    writeln!(
+        out,
+        r#"  <testsuite name="Lintje" tests="{}" failures="{}" errors="0" skipped="{}">"#,
+        tests, failures, skipped
+    )?;
+    for test_case in test_cases {
+        write!(
+            out,
+            r#"    <testcase classname="{}" name="{}""#,
+            test_case.classname,
+            escape(&test_case.name)
+        )?;
+        if test_case.failures.is_empty() && test_case.skipped.is_none() {
+            writeln!(out, "/>")?;
+            continue;
+        }
+        writeln!(out, ">")?;
+        for (rule, message, body) in &test_case.failures {
+            writeln!(
+                out,
+                r#"      <failure type="{}" message="{}">{}</failure>"#,
+                rule,
+                escape(message),
+                escape(body)
+            )?;
+        }
+        if let Some((message, body)) = &test_case.skipped {
+            if body.is_empty() {
+                writeln!(out, r#"      <skipped message="{}"/>"#, escape(message))?;
+            } else {
+                writeln!(
+                    out,
+                    r#"      <skipped message="{}">{}</skipped>"#,
+                    escape(message),
+                    escape(body)
+                )?;
+            }
+        }
+        writeln!(out, "    </testcase>")?;
+    }
+    writeln!(out, "  </testsuite>")?;
+    writeln!(out, "</testsuites>")?;
+    Ok(())
+}
+
+fn test_case<F>(
+    classname: &'static str,
+    name: String,
+    issues: &[Issue],
+    hints: bool,
+    format_issue: F,
+) -> io::Result<TestCase>
+where
+    F: Fn(&mut Buffer, &Issue) -> io::Result<()>,
+{
+    let mut failures = vec![];
+    let mut hint_messages = vec![];
+    let mut hint_body = Buffer::no_color();
+    for issue in issues {
+        match issue.r#type {
+            IssueType::Error => {
+                let mut body = Buffer::no_color();
+                format_issue(&mut body, issue)?;
+                failures.push((
+                    issue.rule.to_string(),
+                    issue.message.to_string(),
+                    String::from_utf8_lossy(body.as_slice()).to_string(),
+                ));
+            }
+            IssueType::Hint => {
+                if hints {
+                    hint_messages.push(format!("{}[{}]", issue.r#type, issue.rule));
+                    format_issue(&mut hint_body, issue)?;
+                }
+            }
+        }
+    }
+    // A test case with only hints did not fail, but is not entirely valid either.
+    let skipped = if failures.is_empty() && !hint_messages.is_empty() {
+        Some((
+            hint_messages.join(", "),
+            String::from_utf8_lossy(hint_body.as_slice()).to_string(),
+        ))
+    } else {
+        None
+    };
+    Ok(TestCase {
+        classname,
+        name,
+        failures,
+        skipped,
+    })
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for character in value.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(character),
+            // Other control characters are not allowed in XML documents, not even as character
+            // references, so they are replaced.
+            '\u{0}'..='\u{1f}' => escaped.push(char::REPLACEMENT_CHARACTER),
+            _ => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{escape, write_report};
+    use crate::branch::Branch;
+    use crate::commit::Commit;
+    use crate::issue::{Context, Issue, Position};
+    use crate::rule::Rule;
+    use crate::test::*;
+    use core::ops::Range;
+
+    fn report(commits: &[Commit], branches: &[Branch], hints: bool) -> String {
+        let mut out = vec![];
+        write_report(&mut out, commits, branches, hints).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    fn mood_issue() -> Issue {
+        Issue::error(
+            Rule::SubjectMood,
+            "The subject does not use the imperative grammatical mood".to_string(),
+            Position::Subject { line: 1, column: 1 },
+            vec![Context::subject_error(
+                "Fixing <bug>".to_string(),
+                Range { start: 0, end: 6 },
+                "Use the imperative mood for the subject".to_string(),
+            )],
+        )
+    }
+
+    fn ticket_hint() -> Issue {
+        Issue::hint(
+            Rule::MessageTicketNumber,
+            "The message body does not contain a ticket or issue number".to_string(),
+            Position::MessageLine { line: 3, column: 1 },
+            vec![],
+        )
+    }
+
+    #[test]
+    fn escape_values() {
+        assert_eq!(
+            escape(r#"Fix <a href="x">&'s"#),
+            "Fix &lt;a href=&quot;x&quot;&gt;&amp;&apos;s"
+        );
+        assert_eq!(
+            escape("Fix\u{0}bug\u{1b}[31m\tin\r\nmessage\u{8}"),
+            "Fix\u{fffd}bug\u{fffd}[31m\tin\r\nmessage\u{fffd}"
+        );
+    }
+
+    #[test]
+    fn valid_commit() {
+        let output = report(&[commit("Fix bug", "")], &[], true);
+        assert_eq!(
+            output,
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+            <testsuites name=\"Lintje\" tests=\"1\" failures=\"0\" errors=\"0\" skipped=\"0\">\n  \
+            <testsuite name=\"Lintje\" tests=\"1\" failures=\"0\" errors=\"0\" skipped=\"0\">\n    \
+            <testcase classname=\"commit\" name=\"aaaaaaa Fix bug\"/>\n  \
+            </testsuite>\n\
+            </testsuites>\n"
+        );
+    }
+
+    #[test]
+    fn commit_with_error() {
+        let mut commit = commit("Fixing <bug>", "");
+        commit.issues.push(mood_issue());
+        commit.issues.push(ticket_hint());
+        let output = report(&[commit], &[], true);
+
+        assert!(output.contains(r#"tests="1" failures="1" errors="0" skipped="0""#));
+        assert!(output.contains(
+            "    <testcase classname=\"commit\" name=\"aaaaaaa Fixing &lt;bug&gt;\">\n      \
+            <failure type=\"SubjectMood\" \
+            message=\"The subject does not use the imperative grammatical mood\">\
+            Error[SubjectMood]: The subject does not use the imperative grammatical mood\n  \
+            aaaaaaa:1:1: Fixing &lt;bug&gt;\n"
+        ));
+        assert!(output.contains("^^^^^^ Use the imperative mood for the subject"));
+        assert!(!output.contains("MessageTicketNumber"));
+    }
+
+    #[test]
+    fn commit_with_hints_and_ignored_commit() {
+        let mut hint_commit = commit("Fix bug", "");
+        hint_commit.issues.push(ticket_hint());
+        let mut ignored_commit = commit("Merge pull request #1 from repo", "");
+        ignored_commit.ignored = true;
+        let output = report(&[hint_commit, ignored_commit], &[], true);
+
+        assert!(output.contains(r#"tests="2" failures="0" errors="0" skipped="2""#));
+        assert!(output.contains(
+            "    <testcase classname=\"commit\" name=\"aaaaaaa Fix bug\">\n      \
+            <skipped message=\"Hint[MessageTicketNumber]\">\
+            Hint[MessageTicketNumber]: The message body does not contain a ticket or issue number\n"
+        ));
+        assert!(output.contains(
+            "    <testcase classname=\"commit\" name=\"aaaaaaa Merge pull request #1 from repo\">\n      \
+            <skipped message=\"Commit ignored\"/>\n    \
+            </testcase>\n"
+        ));
+    }
+
+    #[test]
+    fn commit_without_hints() {
+        let mut commit = commit("Fix bug", "");
+        commit.issues.push(ticket_hint());
+        let output = report(&[commit], &[], false);
+
+        assert!(output.contains(r#"tests="1" failures="0" errors="0" skipped="0""#));
+        assert!(output.contains("<testcase classname=\"commit\" name=\"aaaaaaa Fix bug\"/>"));
+    }
+
+    #[test]
+    fn branch_with_error() {
+        let mut branch = Branch::new("fix-123".to_string());
+        branch.issues.push(Issue::error(
+            Rule::BranchNameTicketNumber,
+            "A ticket number was detected in the branch name".to_string(),
+            Position::Branch { column: 1 },
+            vec![],
+        ));
+        let output = report(&[], &[branch], true);
+
+        assert!(output.contains(r#"tests="1" failures="1" errors="0" skipped="0""#));
+        assert!(output.contains(
+            "    <testcase classname=\"branch\" name=\"fix-123\">\n      \
+            <failure type=\"BranchNameTicketNumber\" \
+            message=\"A ticket number was detected in the branch name\">\
+            Error[BranchNameTicketNumber]: A ticket number was detected in the branch name\n  \
+            Branch:1: fix-123\n"
+        ));
+    }
+}
@@ -0,0 +1,635 @@
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::io;
+
+use crate::branch::Branch;
+use crate::commit::Commit;
+use crate::formatter::shown_issues;
+use crate::issue::{Applicability, Edit, Issue, IssueType, Position, Suggestion};
+use crate::rule::Rule;
+use crate::utils::character_count_for_bytes_index;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+
+/// The lint result as a SARIF 2.1.0 log.
+///
+/// Commits and the branch have no file on disk, so issues are reported with a logical location
+/// of the commit SHA or branch name. The line and column of the issue are reported in the region
+/// of a physical location without a URI.
+///
+/// Suggestions of issues are reported as fixes, with the replacements in the regions of the
+/// commit message or branch name.
+#[derive(Debug, Serialize)]
+pub struct Log {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<Run>,
+}
+
+#[derive(Debug, Serialize)]
+struct Run {
+    tool: Tool,
+    results: Vec<RunResult>,
+}
+
+#[derive(Debug, Serialize)]
+struct Tool {
+    driver: ToolComponent,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ToolComponent {
+    name: &'static str,
+    version: &'static str,
+    information_uri: &'static str,
+    rules: Vec<ReportingDescriptor>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReportingDescriptor {
+    id: String,
+    help_uri: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RunResult {
+    rule_id: String,
+    rule_index: usize,
+    level: &'static str,
+    message: Message,
+    locations: Vec<Location>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    fixes: Vec<Fix>,
+}
+
+#[derive(Debug, Serialize)]
+struct Message {
+    text: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Location {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    physical_location: Option<PhysicalLocation>,
+    logical_locations: Vec<LogicalLocation>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocation {
+    artifact_location: ArtifactLocation,
+    region: Region,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Fix {
+    description: Message,
+    artifact_changes: Vec<ArtifactChange>,
+    properties: FixProperties,
+}
+
+#[derive(Debug, Serialize)]
+struct FixProperties {
+    applicability: Applicability,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ArtifactChange {
+    artifact_location: ArtifactLocation,
+    replacements: Vec<Replacement>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Replacement {
+    deleted_region: Region,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    inserted_content: Option<Message>,
+}
+
+#[derive(Debug, Serialize)]
+struct ArtifactLocation {
+    description: Message,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Region {
+    start_line: usize,
+    start_column: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end_line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end_column: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LogicalLocation {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fully_qualified_name: Option<String>,
+    kind: &'static str,
+}
+
+impl Log {
+    pub fn new(commits: &[Commit], branches: &[Branch], hints: bool) -> Self {
+        let commits = commits
+            .iter()
+            .filter(|commit| !commit.ignored)
+            .collect::<Vec<&Commit>>();
+        let rules = reported_rules(&commits, branches);
+        let mut results = vec![];
+        for commit in &commits {
+            let sha = commit.short_sha.as_deref().unwrap_or("0000000");
+            let lines = commit_lines(commit);
+            for issue in shown_issues(&commit.issues, hints) {
+                let logical_location = LogicalLocation {
+                    name: sha.to_string(),
+                    fully_qualified_name: commit.long_sha.clone(),
+                    kind: "commit",
+                };
+                let description = format!("Commit {}", sha);
+                results.push(result(&rules, issue, description, logical_location, &lines));
+            }
+        }
+        for branch in branches {
+            for issue in shown_issues(&branch.issues, hints) {
+                let logical_location = LogicalLocation {
+                    name: branch.name.to_string(),
+                    fully_qualified_name: None,
+                    kind: "branch",
+                };
+                let description = format!("Branch {}", branch.name);
+                results.push(result(
+                    &rules,
+                    issue,
+                    description,
+                    logical_location,
+                    &[&branch.name],
+                ));
+            }
+        }
+
+        Self {
+            schema: SARIF_SCHEMA,
+            version: SARIF_VERSION,
+            runs: vec![Run {
+                tool: Tool {
+                    driver: ToolComponent {
+                        name: "Lintje",
+                        version: env!("CARGO_PKG_VERSION"),
+                        information_uri: "https://lintje.dev",
+                        rules: rules
+                            .iter()
+                            .map(|rule| ReportingDescriptor {
+                                id: rule.to_string(),
+                                help_uri: rule.link(),
+                            })
+                            .collect(),
+                    },
+                },
+                results,
+            }],
+        }
+    }
+
+    pub fn write(&self, out: &mut impl io::Write) -> io::Result<()> {
+        serde_json::to_writer(&mut *out, self)?;
+        writeln!(out)
+    }
+}
+
+// All rules that were checked or reported an issue, in a stable order so the rule index of each
+// result can be looked up.
+fn reported_rules(commits: &[&Commit], branches: &[Branch]) -> Vec<Rule> {
+    let mut rules = BTreeSet::new();
+    for commit in commits {
+        rules.extend(commit.checked_rules.iter().cloned());
+        rules.extend(commit.issues.iter().map(|issue| issue.rule.clone()));
+    }
+    for branch in branches {
+        rules.extend(branch.checked_rules.iter().cloned());
+        rules.extend(branch.issues.iter().map(|issue| issue.rule.clone()));
+    }
+    rules.into_iter().collect()
+}
+
+fn level(issue_type: &IssueType) -> &'static str {
+    match issue_type {
+        IssueType::Error => "error",
+        IssueType::Hint => "note",
+    }
+}
+
+// The lines of the commit message as numbered by the issue positions, including the trailers.
+fn commit_lines(commit: &Commit) -> Vec<&str> {
+    let mut lines = vec![commit.subject.as_str()];
+    lines.extend(commit.message.lines());
+    if !commit.trailers.is_empty() {
+        lines.push("");
+        lines.extend(commit.trailers.lines());
+    }
+    lines
+}
+
+fn result(
+    rules: &[Rule],
+    issue: &Issue,
+    description: String,
+    logical_location: LogicalLocation,
+    lines: &[&str],
+) -> RunResult {
+    let region = match issue.position {
+        Position::Subject { line, column } | Position::MessageLine { line, column } => {
+            Some(Region {
+                start_line: line,
+                start_column: column,
+                end_line: None,
+                end_column: None,
+            })
+        }
+        Position::Branch { column } => Some(Region {
+            start_line: 1,
+            start_column: column,
+            end_line: None,
+            end_column: None,
+        }),
+        Position::Diff => None,
+    };
+    RunResult {
+        rule_id: issue.rule.to_string(),
+        rule_index: rules
+            .iter()
+            .position(|rule| rule == &issue.rule)
+            .unwrap_or_default(),
+        level: level(&issue.r#type),
+        message: Message {
+            text: issue.message.to_string(),
+        },
+        fixes: issue
+            .suggestions
+            .iter()
+            .map(|suggestion| fix(suggestion, &description, lines))
+            .collect(),
+        locations: vec![Location {
+            physical_location: region.map(|region| PhysicalLocation {
+                artifact_location: ArtifactLocation {
+                    description: Message { text: description },
+                },
+                region,
+            }),
+            logical_locations: vec![logical_location],
+        }],
+    }
+}
+
+fn fix(suggestion: &Suggestion, description: &str, lines: &[&str]) -> Fix {
+    Fix {
+        description: Message {
+            text: suggestion.message.to_string(),
+        },
+        artifact_changes: vec![ArtifactChange {
+            artifact_location: ArtifactLocation {
+                description: Message {
+                    text: description.to_string(),
+                },
+            },
+            replacements: suggestion
+                .edits
+                .iter()
+                .map(|edit| replacement(edit, lines))
+                .collect(),
+        }],
+        properties: FixProperties {
+            applicability: suggestion.applicability.clone(),
+        },
+    }
+}
+
+fn replacement(edit: &Edit, lines: &[&str]) -> Replacement {
+    let column = |line: usize, index: usize| {
+        lines
+            .get(line - 1)
+            .map_or(1, |content| character_count_for_bytes_index(content, index))
+    };
+    // Lines are added after the end of the last line
+    let last_line = lines.len().max(1);
+    let end_of_last_line = Region {
+        start_line: last_line,
+        start_column: column(last_line, lines.last().map_or(0, |line| line.len())),
+        end_line: None,
+        end_column: None,
+    };
+    let (deleted_region, text) = match edit {
+        Edit::Replace { line, range, text } => (
+            Region {
+                start_line: *line,
+                start_column: column(*line, range.start),
+                end_line: None,
+                end_column: Some(column(*line, range.end)),
+            },
+            text.to_string(),
+        ),
+        Edit::ReplaceBranch { range, text } => (
+            Region {
+                start_line: 1,
+                start_column: column(1, range.start),
+                end_line: None,
+                end_column: Some(column(1, range.end)),
+            },
+            text.to_string(),
+        ),
+        Edit::InsertLine { line, text } if *line <= lines.len() => (
+            Region {
+                start_line: *line,
+                start_column: 1,
+                end_line: None,
+                end_column: Some(1),
+            },
+            format!("{}\n", text),
+        ),
+        Edit::InsertLine { text, .. } | Edit::AppendLine { text } => {
+            (end_of_last_line, format!("\n{}", text))
+        }
+        Edit::RemoveLine { line } => (
+            Region {
+                start_line: *line,
+                start_column: 1,
+                end_line: Some(line + 1),
+                end_column: Some(1),
+            },
+            "".to_string(),
+        ),
+    };
+    Replacement {
+        deleted_region,
+        inserted_content: if text.is_empty() {
+            None
+        } else {
+            Some(Message { text })
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Log;
+    use crate::branch::Branch;
+    use crate::issue::{Edit, Issue, Position, Suggestion};
+    use crate::rule::Rule;
+    use crate::test::*;
+    use core::ops::Range;
+    use serde_json::json;
+
+    fn log_json(log: &Log) -> serde_json::Value {
+        let mut out = vec![];
+        log.write(&mut out).unwrap();
+        serde_json::from_slice(&out).unwrap()
+    }
+
+    #[test]
+    fn commit_issues() {
+        let mut commit = commit("Fixing bug", "");
+        commit.checked_rules = vec![Rule::SubjectMood, Rule::MessageTicketNumber];
+        commit.issues.push(Issue::error(
+            Rule::SubjectMood,
+            "The subject does not use the imperative grammatical mood".to_string(),
+            Position::Subject { line: 1, column: 1 },
+            vec![],
+        ));
+        commit.issues.push(Issue::hint(
+            Rule::MessageTicketNumber,
+            "The message body does not contain a ticket or issue number".to_string(),
+            Position::MessageLine { line: 3, column: 1 },
+            vec![],
+        ));
+        let log = Log::new(&[commit], &[], true);
+        let json = log_json(&log);
+
+        assert_eq!(json["version"], "2.1.0");
+        let run = &json["runs"][0];
+        assert_eq!(run["tool"]["driver"]["name"], "Lintje");
+        assert_eq!(
+            run["tool"]["driver"]["rules"],
+            json!([
+                {
+                    "id": "SubjectMood",
+                    "helpUri": "https://r.lintje.dev/r/SubjectMood"
+                },
+                {
+                    "id": "MessageTicketNumber",
+                    "helpUri": "https://r.lintje.dev/r/MessageTicketNumber"
+                }
+            ])
+        );
+        assert_eq!(
+            run["results"],
+            json!([
+                {
+                    "ruleId": "SubjectMood",
+                    "ruleIndex": 0,
+                    "level": "error",
+                    "message": {
+                        "text": "The subject does not use the imperative grammatical mood"
+                    },
+                    "locations": [{
+                        "physicalLocation": {
+                            "artifactLocation": {
+                                "description": { "text": "Commit aaaaaaa" }
+                            },
+                            "region": { "startLine": 1, "startColumn": 1 }
+                        },
+                        "logicalLocations": [{
+                            "name": "aaaaaaa",
+                            "fullyQualifiedName": "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+                            "kind": "commit"
+                        }]
+                    }]
+                },
+                {
+                    "ruleId": "MessageTicketNumber",
+                    "ruleIndex": 1,
+                    "level": "note",
+                    "message": {
+                        "text": "The message body does not contain a ticket or issue number"
+                    },
+                    "locations": [{
+                        "physicalLocation": {
+                            "artifactLocation": {
+                                "description": { "text": "Commit aaaaaaa" }
+                            },
+                            "region": { "startLine": 3, "startColumn": 1 }
+                        },
+                        "logicalLocations": [{
+                            "name": "aaaaaaa",
+                            "fullyQualifiedName": "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+                            "kind": "commit"
+                        }]
+                    }]
+                }
+            ])
+        );
+    }
+
+    #[test]
+    fn without_hints() {
+        let mut commit = commit("Fix bug", "");
+        commit.issues.push(Issue::hint(
+            Rule::MessageTicketNumber,
+            "The message body does not contain a ticket or issue number".to_string(),
+            Position::MessageLine { line: 3, column: 1 },
+            vec![],
+        ));
+        let log = Log::new(&[commit], &[], false);
+
+        assert_eq!(log_json(&log)["runs"][0]["results"], json!([]));
+    }
+
+    #[test]
+    fn branch_and_diff_issues() {
+        let mut commit = commit("Fix bug", "");
+        commit.issues.push(Issue::error(
+            Rule::DiffPresence,
+            "No file changes found".to_string(),
+            Position::Diff,
+            vec![],
+        ));
+        let mut ignored_commit = crate::test::commit("Merge pull request #1 from repo", "");
+        ignored_commit.ignored = true;
+        ignored_commit.issues.push(Issue::error(
+            Rule::SubjectLength,
+            "Ignored".to_string(),
+            Position::Subject { line: 1, column: 1 },
+            vec![],
+        ));
+        let mut branch = Branch::new("fix-123".to_string());
+        branch.issues.push(Issue::error(
+            Rule::BranchNameTicketNumber,
+            "A ticket number was detected in the branch name".to_string(),
+            Position::Branch { column: 1 },
+            vec![],
+        ));
+        let log = Log::new(&[commit, ignored_commit], &[branch], true);
+        let json = log_json(&log);
+        let results = &json["runs"][0]["results"];
+
+        assert_eq!(results.as_array().unwrap().len(), 2);
+        assert_eq!(
+            results[0]["locations"][0],
+            json!({
+                "logicalLocations": [{
+                    "name": "aaaaaaa",
+                    "fullyQualifiedName": "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+                    "kind": "commit"
+                }]
+            })
+        );
+        assert_eq!(
+            results[1]["locations"][0],
+            json!({
+                "physicalLocation": {
+                    "artifactLocation": {
+                        "description": { "text": "Branch fix-123" }
+                    },
+                    "region": { "startLine": 1, "startColumn": 1 }
+                },
+                "logicalLocations": [{
+                    "name": "fix-123",
+                    "kind": "branch"
+                }]
+            })
+        );
+        assert_eq!(results[1]["ruleId"], "BranchNameTicketNumber");
+        assert_eq!(
+            json["runs"][0]["tool"]["driver"]["rules"]
+                [results[1]["ruleIndex"].as_u64().unwrap() as usize]["id"],
+            "BranchNameTicketNumber"
+        );
+    }
+
+    #[test]
+    fn issue_with_suggestion() {
+        let mut commit = commit("Fix bug.", "\nSome message body.");
+        commit.issues.push(
+            Issue::error(
+                Rule::SubjectPunctuation,
+                "The subject ends with a punctuation character: `.`".to_string(),
+                Position::Subject { line: 1, column: 8 },
+                vec![],
+            )
+            .with_suggestion(Suggestion::safe(
+                "Remove punctuation from the end of the subject".to_string(),
+                vec![Edit::Replace {
+                    line: 1,
+                    range: Range { start: 7, end: 8 },
+                    text: "".to_string(),
+                }],
+            )),
+        );
+        commit.issues.push(
+            Issue::error(
+                Rule::SubjectBuildTag,
+                "The `[skip ci]` build tag was found in the subject".to_string(),
+                Position::Subject { line: 1, column: 1 },
+                vec![],
+            )
+            .with_suggestion(Suggestion::maybe_incorrect(
+                "Move build tag to message body".to_string(),
+                vec![
+                    Edit::InsertLine {
+                        line: 2,
+                        text: "[skip ci]".to_string(),
+                    },
+                    Edit::AppendLine {
+                        text: "[skip ci]".to_string(),
+                    },
+                ],
+            )),
+        );
+        let log = Log::new(&[commit], &[], true);
+        let json = log_json(&log);
+        let results = &json["runs"][0]["results"];
+
+        assert_eq!(
+            results[0]["fixes"],
+            json!([{
+                "description": { "text": "Remove punctuation from the end of the subject" },
+                "artifactChanges": [{
+                    "artifactLocation": {
+                        "description": { "text": "Commit aaaaaaa" }
+                    },
+                    "replacements": [{
+                        "deletedRegion": { "startLine": 1, "startColumn": 8, "endColumn": 9 }
+                    }]
+                }],
+                "properties": { "applicability": "safe" }
+            }])
+        );
+        assert_eq!(
+            results[1]["fixes"][0]["artifactChanges"][0]["replacements"],
+            json!([
+                {
+                    "deletedRegion": { "startLine": 2, "startColumn": 1, "endColumn": 1 },
+                    "insertedContent": { "text": "[skip ci]\n" }
+                },
+                {
+                    "deletedRegion": { "startLine": 3, "startColumn": 19 },
+                    "insertedContent": { "text": "\n[skip ci]" }
+                }
+            ])
+        );
+        assert_eq!(
+            results[1]["fixes"][0]["properties"]["applicability"],
+            "maybe_incorrect"
+        );
+    }
+}
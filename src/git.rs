@@ -1,16 +1,18 @@
 pub mod hooks;
+pub mod patch;
+pub mod push;
+pub mod rewrite;
 
 use regex::Regex;
+use std::io::{self, BufRead};
+use std::path::PathBuf;
 
 use crate::branch::Branch;
-use crate::command::{run_command, run_command_with_stdin};
+use crate::command::{run_command, run_command_with_reader, run_command_with_stdin};
 use crate::commit::Commit;
+use crate::rule::Rule;
 
-const SCISSORS: &str = "------------------------ >8 ------------------------";
-const COMMIT_DELIMITER: &str = "------------------------ COMMIT >! ------------------------";
-const COMMIT_BODY_DELIMITER: &str = "------------------------ BODY >! ------------------------";
-const COMMIT_TRAILERS_DELIMITER: &str =
-    "------------------------ TRAILERS >! ------------------------";
+pub const SCISSORS: &str = "------------------------ >8 ------------------------";
 
 lazy_static! {
     pub static ref SUBJECT_WITH_MERGE_REMOTE_BRANCH: Regex =
@@ -32,155 +34,292 @@ enum CleanupMode {
 }
 
 pub fn fetch_and_parse_branch() -> Result<Branch, String> {
-    let output = match run_command("git", &["rev-parse", "--abbrev-ref", "HEAD"]) {
-        Ok(o) => o,
+    Ok(Branch::new(current_branch()?))
+}
+
+/// Returns all local branches, or all branches of the remote if one is given
+pub fn fetch_and_parse_branches(remote: Option<&str>) -> Result<Vec<Branch>, String> {
+    let prefix = match remote {
+        Some(remote) => format!("refs/remotes/{}/", remote),
+        None => "refs/heads/".to_string(),
+    };
+    match run_command("git", &["for-each-ref", "--format=%(refname)", &prefix]) {
+        Ok(output) => Ok(parse_branch_refs(&output, &prefix)),
+        Err(e) => {
+            debug!("Failed to fetch Git branches: {:?}", e);
+            Err(e.message())
+        }
+    }
+}
+
+fn parse_branch_refs(output: &str, prefix: &str) -> Vec<Branch> {
+    output
+        .lines()
+        .filter_map(|line| line.strip_prefix(prefix))
+        // The symbolic ref of the remote's default branch is not a branch itself
+        .filter(|name| *name != "HEAD")
+        .map(|name| Branch::new(name.to_string()))
+        .collect()
+}
+
+/// Returns the root directory of the repository's working tree
+pub fn repository_root() -> Result<PathBuf, String> {
+    match run_command("git", &["rev-parse", "--show-toplevel"]) {
+        Ok(stdout) => Ok(PathBuf::from(stdout.trim())),
+        Err(e) => {
+            debug!("Unable to determine repository root: {:?}", e);
+            Err(e.message())
+        }
+    }
+}
+
+/// Returns the name of the current branch, or "HEAD" if no branch is checked out
+pub fn current_branch() -> Result<String, String> {
+    match run_command("git", &["rev-parse", "--abbrev-ref", "HEAD"]) {
+        Ok(output) => Ok(output.trim().to_string()),
         Err(e) => {
             debug!("Failed to fetch Git branch: {:?}", e);
-            return Err(e.message());
+            Err(e.message())
         }
-    };
-    let name = output.trim().to_string();
-    let mut branch = Branch::new(name);
-    branch.validate();
-    Ok(branch)
+    }
 }
 
-pub fn fetch_and_parse_commits(selector: &Option<String>) -> Result<Vec<Commit>, String> {
-    let mut commits = Vec::<Commit>::new();
-    // Format definition per commit:
-    // COMMIT_DELIMITER: Tell commits apart, split the string on this later
-    // Format:
-    //   - Line 1: Commit SHA in long form
-    //   - Line 2: Commit author email address
-    //   - Line 3 to end of body: Commit subject and message, including trailers
-    // COMMIT_TRAILERS_DELIMITER: Separator for commit message body and trailers
-    // %(trailers): Trailers, based on https://git-scm.com/docs/git-interpret-trailers/
-    // COMMIT_BODY_DELIMITER: Separator for end of the message body and trailers
-    // `--name-only`: Prints filenames of files changed
-    let commit_format = "%H%n%ae%n%B";
-    let mut args = vec![
-        "log".to_string(),
-        format!(
-            "--pretty=\
-             {COMMIT_DELIMITER}%n\
-             {commit_format}%n\
-             {COMMIT_TRAILERS_DELIMITER}%n\
-             %(trailers)%n\
-             {COMMIT_BODY_DELIMITER}"
-        ),
-        "--name-only".to_string(),
-    ];
+/// Returns the selected commits, with at most the given number of commits if a maximum is given.
+pub fn fetch_and_parse_commits(
+    selector: &Option<String>,
+    max_count: Option<usize>,
+) -> Result<Vec<Commit>, String> {
+    let mut commits = vec![];
+    fetch_and_handle_commits(selector, max_count, &mut |commit| commits.push(commit))?;
+    Ok(commits)
+}
+
+/// Calls `handle` with every selected commit as soon as it's read from the Git log, so the commits
+/// can be validated while Git is still writing the log of a large range of commits.
+pub fn fetch_and_handle_commits(
+    selector: &Option<String>,
+    max_count: Option<usize>,
+    handle: &mut dyn FnMut(Commit),
+) -> Result<(), String> {
+    let mut revisions = vec![];
+    if let Some(max_count) = max_count {
+        revisions.push(format!("--max-count={}", max_count));
+    }
     match selector {
         Some(selection) => {
             let selection = selection.trim().to_string();
             if !selection.contains("..") {
                 // Only select one commit if no commit range was selected
-                args.push("-n 1".to_string());
+                revisions.push("-n 1".to_string());
             }
-            args.push(selection);
+            revisions.push(selection);
         }
         None => {
-            args.push("-n 1".to_string());
-            args.push("HEAD".to_string());
+            revisions.push("-n 1".to_string());
+            revisions.push("HEAD".to_string());
         }
     };
+    fetch_and_parse_log(revisions, handle)
+}
+
+/// Returns the commits of the new commit of a ref that are not part of any other ref yet. Used in
+/// the pre-receive hook, where the refs are not updated until the hook succeeds.
+pub fn fetch_and_parse_new_commits(new_sha: &str) -> Result<Vec<Commit>, String> {
+    let mut commits = vec![];
+    fetch_and_parse_log(
+        vec![
+            new_sha.to_string(),
+            "--not".to_string(),
+            "--all".to_string(),
+        ],
+        &mut |commit| commits.push(commit),
+    )?;
+    Ok(commits)
+}
 
-    let output = match run_command("git", &args) {
-        Ok(o) => o,
+fn fetch_and_parse_log(
+    revisions: Vec<String>,
+    handle: &mut dyn FnMut(Commit),
+) -> Result<(), String> {
+    // Format definition per commit, with every field terminated by a NUL byte:
+    //   - Commit SHA in long form
+    //   - Commit author email address
+    //   - Commit subject and message, including trailers
+    //   - %(trailers): Trailers, based on https://git-scm.com/docs/git-interpret-trailers/
+    // `--name-only`: Prints filenames of files changed, with `-z` every filename is terminated by
+    // a NUL byte. Every commit is terminated by an extra NUL byte.
+    // `--encoding`: Git re-encodes messages of commits with an encoding header to UTF-8.
+    let mut args = vec![
+        "log".to_string(),
+        "-z".to_string(),
+        "--encoding=UTF-8".to_string(),
+        "--pretty=format:%H%x00%ae%x00%B%x00%(trailers)%x00".to_string(),
+        "--name-only".to_string(),
+    ];
+    args.extend(revisions);
+
+    // The output of large ranges of commits is parsed while Git writes it, so it's not kept in
+    // memory as a whole. Every commit is handled as soon as it's parsed.
+    let encoding = commit_encoding();
+    match run_command_with_reader("git", &args, |output| parse_log(output, &encoding, handle)) {
+        Ok(Ok(())) => Ok(()),
+        Ok(Err(e)) => Err(format!("Unable to read Git log output: {}\n", e)),
         Err(e) => {
             debug!("Failed to fetch Git log: {:?}", e);
-            return Err(e.message());
-        }
-    };
-    let messages = output.split(COMMIT_DELIMITER);
-    for message in messages {
-        let trimmed_message = message.trim();
-        if !trimmed_message.is_empty() {
-            match parse_commit(trimmed_message) {
-                Some(commit) => commits.push(commit),
-                None => debug!("Commit ignored: {:?}", message),
-            }
+            Err(e.message())
         }
     }
-    Ok(commits)
 }
 
-fn parse_commit(message: &str) -> Option<Commit> {
-    let mut long_sha = None;
-    let mut email = None;
-    let mut subject = None;
-    let mut message_lines = vec![];
-    let mut message_parts = message.split(COMMIT_TRAILERS_DELIMITER);
-    match message_parts.next() {
-        Some(body) => {
-            for (index, line) in body.lines().enumerate() {
-                match index {
-                    0 => long_sha = Some(line),
-                    1 => email = Some(line.to_string()),
-                    2 => subject = Some(line),
-                    _ => message_lines.push(line.to_string()),
-                }
+// Parse the NUL delimited output of the `git log` command in `fetch_and_parse_log`, and call
+// `handle` with every parsed commit. Messages that are not valid UTF-8 are decoded with the given
+// encoding.
+fn parse_log(
+    output: &mut dyn BufRead,
+    encoding: &str,
+    handle: &mut dyn FnMut(Commit),
+) -> io::Result<()> {
+    let mut fields = output.split(0);
+    while let Some(long_sha) = fields.next() {
+        let long_sha = long_sha?;
+        if long_sha.is_empty() {
+            continue;
+        }
+        let mut next_field = || -> io::Result<String> {
+            let field = fields.next().transpose()?.unwrap_or_default();
+            Ok(decode_field(&field, encoding))
+        };
+        let email = next_field()?;
+        let body = next_field()?;
+        let trailers = next_field()?;
+        let mut file_changes = vec![];
+        for file in fields.by_ref() {
+            let file = file?;
+            if file.is_empty() {
+                break;
             }
+            // The list of filenames starts on a new line
+            let file = file.strip_prefix(b"\n").unwrap_or(&file);
+            file_changes.push(String::from_utf8_lossy(file).to_string());
         }
-        None => error!("No commit body found!"),
+        handle(parse_commit(
+            &String::from_utf8_lossy(&long_sha),
+            &email,
+            &body,
+            trailers.trim(),
+            file_changes,
+        ));
     }
+    Ok(())
+}
 
-    let mut extras_str = message_parts
-        .next()
-        .unwrap_or("")
-        .split(COMMIT_BODY_DELIMITER);
-    let trailers = extras_str.next().unwrap_or("").trim().to_string();
-    let file_changes_str = extras_str.next().unwrap_or("").trim();
-    let file_changes = file_changes_str
-        .lines()
-        .map(std::string::ToString::to_string)
-        .collect::<Vec<String>>();
+fn parse_commit(
+    long_sha: &str,
+    email: &str,
+    body: &str,
+    trailers: &str,
+    file_changes: Vec<String>,
+) -> Commit {
+    let mut lines = body.lines();
+    let subject = lines.next().unwrap_or_else(|| {
+        debug!("Commit subject not present in message: {:?}", body);
+        ""
+    });
     if file_changes.is_empty() {
-        debug!("No stats found for commit '{}'", long_sha.unwrap_or(""));
+        debug!("No stats found for commit '{}'", long_sha);
     } else {
         debug!(
             "Stats line found for commit '{}': {}",
-            long_sha.unwrap_or(""),
+            long_sha,
             file_changes.join(", ")
         );
     }
 
     // Trailers are included twice, once for the body and once for the trailers. Replace the
     // trailers in the body, we already have them in the trailers string.
-    let message_body_str = message_lines.join("\n");
-    let message_body = strip_trailers_from_message(&message_body_str, &trailers);
-
-    match (long_sha, subject) {
-        (Some(long_sha), subject) => {
-            let used_subject = subject.unwrap_or_else(|| {
-                debug!("Commit subject not present in message: {:?}", message);
-                ""
-            });
-            Some(Commit::new(
-                Some(long_sha.to_string()),
-                email,
-                used_subject,
-                message_body,
-                trailers,
-                file_changes,
-            ))
+    let message_body_str = lines.collect::<Vec<&str>>().join("\n");
+    let message_body = strip_trailers_from_message(&message_body_str, trailers);
+    let email = if email.is_empty() {
+        None
+    } else {
+        Some(email.to_string())
+    };
+    Commit::new(
+        Some(long_sha.to_string()),
+        email,
+        subject,
+        message_body,
+        trailers.to_string(),
+        file_changes,
+    )
+}
+
+// Git's `i18n.commitEncoding` config, the encoding of commit messages
+fn commit_encoding() -> String {
+    match run_command("git", &["config", "i18n.commitEncoding"]) {
+        Ok(stdout) if !stdout.trim().is_empty() => stdout.trim().to_string(),
+        _ => "UTF-8".to_string(),
+    }
+}
+
+// Git assumes messages are UTF-8 when commits have no encoding header, so only fall back on the
+// configured encoding for messages that are not valid UTF-8.
+fn decode_field(bytes: &[u8], encoding: &str) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        Err(_) => decode_bytes(bytes, encoding),
+    }
+}
+
+/// Decode text in the given encoding. Only UTF-8, US-ASCII and ISO-8859-1 are supported, other
+/// encodings are decoded as UTF-8 with replacement characters.
+fn decode_bytes(bytes: &[u8], encoding: &str) -> String {
+    // Strip the RFC 2231 language, like in `UTF-8*en`
+    let encoding = encoding.split('*').next().unwrap_or("").to_lowercase();
+    match encoding.as_str() {
+        "iso-8859-1" | "iso8859-1" | "latin1" | "latin-1" => {
+            bytes.iter().map(|byte| char::from(*byte)).collect()
         }
+        "utf-8" | "utf8" | "us-ascii" => String::from_utf8_lossy(bytes).to_string(),
         _ => {
-            debug!("Commit ignored: SHA was not present: {}", message);
-            None
+            debug!("Unsupported encoding, decoding as UTF-8: {}", encoding);
+            String::from_utf8_lossy(bytes).to_string()
         }
     }
 }
 
 pub fn parse_commit_file(contents: &str) -> Commit {
-    let (subject, message) = parse_commit_hook_format(contents, &cleanup_mode(), &comment_char());
-    let trailers = parse_trailers_from_message([subject.to_owned(), message.to_owned()].join("\n"));
-    let message = strip_trailers_from_message(&message, &trailers);
     // Run the diff command to fetch the current staged changes and determine if the commit is
     // empty or not. The contents of the commit message file is too unreliable as it depends on
     // user config and how the user called the `git commit` command.
     let file_changes = current_file_changes();
-    Commit::new(None, None, &subject, message, trailers, file_changes)
+    parse_commit_message_file(contents, None, None, file_changes)
+}
+
+/// Parse a commit message given on the command line or STDIN, which is not part of a repository's
+/// staged changes. The file changes of the commit are the given files. Without files, the rules
+/// that check the file changes are skipped.
+pub fn parse_commit_message(contents: &str, file_changes: &[String]) -> Commit {
+    let mut commit = parse_commit_message_file(contents, None, None, file_changes.to_vec());
+    if file_changes.is_empty() {
+        commit
+            .ignored_rules
+            .extend([Rule::DiffPresence, Rule::DiffChangeset]);
+    }
+    commit
+}
+
+/// Parse a commit message file for an existing commit, like when rewording a commit.
+pub fn parse_commit_message_file(
+    contents: &str,
+    long_sha: Option<String>,
+    email: Option<String>,
+    file_changes: Vec<String>,
+) -> Commit {
+    let (subject, message) = parse_commit_hook_format(contents, &cleanup_mode(), &comment_char());
+    let trailers = parse_trailers_from_message([subject.to_owned(), message.to_owned()].join("\n"));
+    let message = strip_trailers_from_message(&message, &trailers);
+    Commit::new(long_sha, email, &subject, message, trailers, file_changes)
 }
 
 fn parse_commit_hook_format(
@@ -188,12 +327,36 @@ fn parse_commit_hook_format(
     cleanup_mode: &CleanupMode,
     comment_char: &str,
 ) -> (String, String) {
-    let mut subject = None;
-    let mut message_lines = vec![];
+    let mut lines = parse_commit_hook_lines(file_contents, cleanup_mode, comment_char)
+        .into_iter()
+        .map(|(_, line)| line);
+    let used_subject = lines.next().unwrap_or_else(|| {
+        debug!("Commit subject not present in message: {:?}", file_contents);
+        "".to_string()
+    });
+    let message_lines = lines.collect::<Vec<String>>();
+
+    (used_subject, message_lines.join("\n"))
+}
+
+/// Returns the lines of the commit message in the commit message file, after the cleanup of
+/// comments and whitespace, with the index of the line in the file.
+///
+/// The first line is the subject.
+pub fn commit_file_lines(contents: &str) -> Vec<(usize, String)> {
+    parse_commit_hook_lines(contents, &cleanup_mode(), &comment_char())
+}
+
+fn parse_commit_hook_lines(
+    file_contents: &str,
+    cleanup_mode: &CleanupMode,
+    comment_char: &str,
+) -> Vec<(usize, String)> {
+    let mut lines = vec![];
     let scissor_line = format!("{} {}", comment_char, SCISSORS);
     debug!("Using clean up mode: {:?}", cleanup_mode);
     debug!("Using config core.commentChar: {:?}", comment_char);
-    for line in file_contents.lines() {
+    for (index, line) in file_contents.lines().enumerate() {
         // A scissor line has been detected.
         //
         // A couple reasons why this could happen:
@@ -213,15 +376,15 @@ fn parse_commit_hook_format(
 
         // The first non-empty line is the subject line in every cleanup mode but the Verbatim
         // mode.
-        if subject.is_none() {
+        if lines.is_empty() {
             if cleanup_mode == &CleanupMode::Verbatim {
                 // Set subject, doesn't matter what the content is. Even empty lines are considered
                 // subjects in Verbatim cleanup mode.
-                subject = Some(line.to_string());
+                lines.push((index, line.to_string()));
             } else if let Some(cleaned_line) = cleanup_line(line, cleanup_mode, comment_char) {
                 if !cleaned_line.is_empty() {
                     // Skip leading empty lines in every other cleanup mode than Verbatim.
-                    subject = Some(cleaned_line);
+                    lines.push((index, cleaned_line));
                 }
             }
             // Skips this line if the cleanup mode is Strip and the line is a comment.
@@ -230,15 +393,10 @@ fn parse_commit_hook_format(
         }
 
         if let Some(cleaned_line) = cleanup_line(line, cleanup_mode, comment_char) {
-            message_lines.push(cleaned_line);
+            lines.push((index, cleaned_line));
         }
     }
-    let used_subject = subject.unwrap_or_else(|| {
-        debug!("Commit subject not present in message: {:?}", file_contents);
-        "".to_string()
-    });
-
-    (used_subject, message_lines.join("\n"))
+    lines
 }
 
 fn parse_trailers_from_message(message: String) -> String {
@@ -370,7 +528,7 @@ fn cleanup_mode() -> CleanupMode {
     }
 }
 
-fn comment_char() -> String {
+pub fn comment_char() -> String {
     match run_command("git", &["config", "core.commentChar"]) {
         Ok(stdout) => {
             let character = stdout.trim().to_string();
@@ -414,6 +572,23 @@ fn current_file_changes() -> Vec<String> {
     }
 }
 
+/// Returns true if the tree of the revision contains a changesets directory. Unlike
+/// `repo_has_changesets`, it also works in bare repositories.
+pub fn revision_has_changesets(revision: &str) -> bool {
+    match run_command("git", &["ls-tree", "-r", "-d", "--name-only", revision]) {
+        Ok(stdout) => stdout.lines().any(|path| {
+            path.rsplit('/')
+                .next()
+                .is_some_and(|name| name == ".changesets" || name == ".changeset")
+        }),
+        Err(e) => {
+            // The revision does not exist in repositories without commits
+            debug!("Unable to read files from revision {}: {:?}", revision, e);
+            false
+        }
+    }
+}
+
 pub fn repo_has_changesets() -> bool {
     // Find all changesets directories in the repo
     match run_command(
@@ -452,14 +627,15 @@ pub fn strip_trailers_from_message(message: &str, trailers: &str) -> String {
 mod tests {
     use super::Commit;
     use super::{
-        is_commit_ignored, parse_commit, parse_commit_hook_format, strip_trailers_from_message,
-        CleanupMode, COMMIT_BODY_DELIMITER, COMMIT_TRAILERS_DELIMITER,
+        is_commit_ignored, parse_branch_refs, parse_commit_hook_format, parse_commit_message,
+        parse_log, strip_trailers_from_message, CleanupMode,
     };
     use crate::config::ValidationContext;
     use crate::issue::IssueType;
+    use crate::rule::Rule;
 
     fn default_context() -> ValidationContext {
-        ValidationContext { changesets: false }
+        ValidationContext::default()
     }
 
     fn assert_commit_is_invalid(commit: &mut Commit) {
@@ -481,33 +657,103 @@ mod tests {
         }
     }
 
+    // Format the message, in the `<sha>\n<email>\n<body>` format, as the `git log` output of
+    // `fetch_and_parse_log`.
+    fn log_output(message: &str, trailers: &str, file_changes: &[&str]) -> String {
+        let mut parts = message.splitn(3, '\n');
+        let long_sha = parts.next().unwrap_or("");
+        let email = parts.next().unwrap_or("");
+        let body = parts.next().unwrap_or("");
+        let files = if file_changes.is_empty() {
+            String::new()
+        } else {
+            format!("\n{}\0", file_changes.join("\0"))
+        };
+        format!("{long_sha}\0{email}\0{body}\n\0{trailers}\0{files}\0")
+    }
+
+    fn parse_log_commits(output: &mut dyn std::io::BufRead, encoding: &str) -> Vec<Commit> {
+        let mut commits = vec![];
+        parse_log(output, encoding, &mut |commit| commits.push(commit)).unwrap();
+        commits
+    }
+
+    fn parse_commit(output: &str) -> Option<Commit> {
+        parse_log_commits(&mut output.as_bytes(), "UTF-8")
+            .into_iter()
+            .next()
+    }
+
     fn commit_with_file_changes(message: &str) -> String {
-        format!(
-            "{}\n{COMMIT_TRAILERS_DELIMITER}\n{COMMIT_BODY_DELIMITER}\n{}",
-            message, "\nsrc/main.rs\nsrc/utils.rs\n"
-        )
+        log_output(message, "", &["src/main.rs", "src/utils.rs"])
     }
 
     fn commit_without_file_changes(message: &str) -> String {
-        format!(
-            "{}\n{COMMIT_TRAILERS_DELIMITER}\n{COMMIT_BODY_DELIMITER}\n{}",
-            message, ""
-        )
+        log_output(message, "", &[])
     }
 
     fn commit_with_trailers(message: &str, trailers: &str) -> String {
-        format!(
-            "{message}\n\
-             \n\
-             {trailers}
-             {COMMIT_TRAILERS_DELIMITER}\n\
-             {trailers}\n\
-             {COMMIT_BODY_DELIMITER}\n\
-             src/main.rs\n\
-             README.md\n",
+        log_output(
+            &format!("{message}\n{trailers}"),
+            trailers,
+            &["src/main.rs", "README.md"],
         )
     }
 
+    #[test]
+    fn test_parse_log() {
+        let output = format!(
+            "{}{}{}",
+            commit_with_file_changes(
+                "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\n\
+                test@example.com\n\
+                This is a subject\n\
+                \n\
+                ------------------------ COMMIT >! ------------------------"
+            ),
+            commit_without_file_changes(
+                "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb\n\
+                test@example.com\n\
+                Merge branch 'develop'"
+            ),
+            commit_with_file_changes(
+                "cccccccccccccccccccccccccccccccccccccccc\n\
+                test@example.com\n\
+                This is a subject"
+            )
+        );
+        let commits = parse_log_commits(&mut output.as_bytes(), "UTF-8");
+        assert_eq!(commits.len(), 3);
+        assert_eq!(
+            commits[0].message,
+            "\n------------------------ COMMIT >! ------------------------"
+        );
+        assert_eq!(commits[0].file_changes, vec!["src/main.rs", "src/utils.rs"]);
+        assert_eq!(commits[1].subject, "Merge branch 'develop'");
+        assert!(commits[1].file_changes.is_empty());
+        assert_eq!(
+            commits[2].long_sha.as_deref(),
+            Some("cccccccccccccccccccccccccccccccccccccccc")
+        );
+        assert_eq!(commits[2].file_changes, vec!["src/main.rs", "src/utils.rs"]);
+    }
+
+    #[test]
+    fn test_parse_log_encoding() {
+        let mut output =
+            b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\0test@example.com\0Add caf".to_vec();
+        output.extend([0xE9]);
+        output.extend(b" menu\n\0\0\0");
+
+        let commits = parse_log_commits(&mut output.as_slice(), "ISO-8859-1");
+        assert_eq!(commits[0].subject, "Add caf\u{e9} menu");
+        let commits = parse_log_commits(&mut output.as_slice(), "UTF-8");
+        assert_eq!(commits[0].subject, "Add caf\u{fffd} menu");
+        let output = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\0test@example.com\0Add caf\u{e9} menu\n\0\0\0";
+        let commits = parse_log_commits(&mut output.as_bytes(), "ISO-8859-1");
+        assert_eq!(commits[0].subject, "Add caf\u{e9} menu");
+    }
+
     #[test]
     fn test_parse_commit() {
         let result = parse_commit(&commit_with_file_changes(
@@ -594,7 +840,9 @@ mod tests {
 
     #[test]
     fn test_parse_commit_empty() {
-        let result = parse_commit("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\n");
+        let result = parse_commit(&commit_without_file_changes(
+            "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+        ));
 
         assert_commit_is_not_ignored(&result);
         let mut commit = result.unwrap();
@@ -786,6 +1034,26 @@ mod tests {
         assert_commit_is_ignored(&result);
     }
 
+    #[test]
+    fn test_parse_commit_message() {
+        let mut commit = parse_commit_message(
+            "This is a subject\n\nThis is a message.\n# A comment\n",
+            &[],
+        );
+        assert_eq!(commit.subject, "This is a subject");
+        assert_eq!(commit.message, "\nThis is a message.");
+        commit.validate(&default_context());
+        assert!(!commit.has_changes());
+        assert!(!commit
+            .issues
+            .iter()
+            .any(|issue| issue.rule == Rule::DiffPresence));
+
+        let commit = parse_commit_message("This is a subject", &["src/main.rs".to_string()]);
+        assert_eq!(commit.file_changes, vec!["src/main.rs"]);
+        assert!(commit.ignored_rules.is_empty());
+    }
+
     #[test]
     fn test_parse_commit_hook_format() {
         let (subject, message) = parse_commit_hook_format(
@@ -1095,4 +1363,19 @@ mod tests {
             My message body"
         );
     }
+
+    #[test]
+    fn test_parse_branch_refs() {
+        let branches = parse_branch_refs(
+            "refs/remotes/origin/HEAD\n\
+            refs/remotes/origin/main\n\
+            refs/remotes/origin/feature/login-form\n",
+            "refs/remotes/origin/",
+        );
+        let names = branches
+            .iter()
+            .map(|branch| branch.name.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(names, vec!["main", "feature/login-form"]);
+    }
 }
@@ -1,60 +1,211 @@
-use std::fs::OpenOptions;
-use std::io::Write;
+use std::fs;
+use std::io::ErrorKind;
 #[cfg(target_family = "unix")]
 use std::os::unix::fs::PermissionsExt;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+use crate::git::rewrite::git_path;
+
+const SHEBANG: &str = "#!/bin/sh";
 
 #[derive(clap::ArgEnum, Clone, Debug)]
 pub enum CommitHook {
+    PrepareCommitMsg,
     CommitMsg,
     PostCommit,
+    PrePush,
+    PreReceive,
 }
 
 impl CommitHook {
+    pub fn all() -> [CommitHook; 5] {
+        [
+            CommitHook::PrepareCommitMsg,
+            CommitHook::CommitMsg,
+            CommitHook::PostCommit,
+            CommitHook::PrePush,
+            CommitHook::PreReceive,
+        ]
+    }
+
     pub fn name(&self) -> &str {
         match self {
+            CommitHook::PrepareCommitMsg => "prepare-commit-msg",
             CommitHook::CommitMsg => "commit-msg",
             CommitHook::PostCommit => "post-commit",
+            CommitHook::PrePush => "pre-push",
+            CommitHook::PreReceive => "pre-receive",
         }
     }
 
     pub fn command(&self) -> &str {
         match self {
-            CommitHook::CommitMsg => "\n\nlintje --hook-message-file=$1",
-            CommitHook::PostCommit => "\n\nlintje",
+            CommitHook::PrepareCommitMsg => {
+                "lintje --hook-prepare-message-file=\"$1\" --hook-message-source=\"$2\""
+            }
+            CommitHook::CommitMsg => "lintje --hook-message-file=$1",
+            CommitHook::PostCommit => "lintje",
+            CommitHook::PrePush => "lintje --hook-pre-push \"$1\"",
+            CommitHook::PreReceive => "lintje --hook-pre-receive",
         }
     }
 }
 
-pub fn install_hook(hook: &CommitHook) -> Result<String, String> {
-    let file_path = format!(".git/hooks/{}", hook.name());
-    let hook_file = Path::new(&file_path);
-    match OpenOptions::new().create(true).append(true).open(hook_file) {
-        Ok(mut file) => {
-            if let Err(e) = set_file_permissions(hook_file) {
-                return Err(format!(
-                    "Cannot set file permissions for: {:?}\n{:?}",
-                    hook_file, e
-                ));
-            }
-            let hook_content = hook.command();
-            match file.write_all(hook_content.as_bytes()) {
-                Ok(()) => Ok(hook_file.to_str().unwrap().to_string()),
-                Err(e) => Err(format!(
-                    "Unable to open write to Git hook file: {}\n{}",
-                    hook_file.to_str().unwrap(),
-                    e
-                )),
-            }
+#[derive(Debug, PartialEq)]
+pub enum HookStatus {
+    Installed,
+    /// Lintje is installed, but Git will not run the hook file
+    NotExecutable,
+    NotInstalled,
+}
+
+/// The result of an install or uninstall, with the path of the hook file
+#[derive(Debug, PartialEq)]
+pub enum HookChange {
+    Changed(String),
+    Unchanged(String),
+}
+
+/// Install Lintje in the hook file, in the hooks directory Git uses for the repository. Files
+/// without Lintje are created with a shebang or extended. Files that already run Lintje are left
+/// as is.
+pub fn install_hook(hook: &CommitHook) -> Result<HookChange, String> {
+    let hook_file = hook_path(hook)?;
+    let contents = read_hook_file(&hook_file)?;
+    let path = hook_file.display().to_string();
+    let new_contents = match installed_contents(contents.as_deref(), hook) {
+        Some(new_contents) => new_contents,
+        None => return Ok(HookChange::Unchanged(path)),
+    };
+    if let Some(dir) = hook_file.parent() {
+        if let Err(e) = fs::create_dir_all(dir) {
+            return Err(format!(
+                "Unable to create Git hooks directory: {}\n{}",
+                dir.display(),
+                e
+            ));
         }
+    }
+    write_hook_file(&hook_file, &new_contents)?;
+    if let Err(e) = set_file_permissions(&hook_file) {
+        return Err(format!(
+            "Cannot set file permissions for: {:?}\n{:?}",
+            hook_file, e
+        ));
+    }
+    Ok(HookChange::Changed(path))
+}
+
+/// Remove Lintje from the hook file. The file is removed if nothing but the shebang remains.
+pub fn uninstall_hook(hook: &CommitHook) -> Result<HookChange, String> {
+    let hook_file = hook_path(hook)?;
+    let path = hook_file.display().to_string();
+    let contents = match read_hook_file(&hook_file)? {
+        Some(contents) => contents,
+        None => return Ok(HookChange::Unchanged(path)),
+    };
+    match uninstalled_contents(&contents) {
+        Some(new_contents) if new_contents.is_empty() => match fs::remove_file(&hook_file) {
+            Ok(()) => Ok(HookChange::Changed(path)),
+            Err(e) => Err(format!("Unable to remove Git hook file: {}\n{}", path, e)),
+        },
+        Some(new_contents) => {
+            write_hook_file(&hook_file, &new_contents)?;
+            Ok(HookChange::Changed(path))
+        }
+        None => Ok(HookChange::Unchanged(path)),
+    }
+}
+
+/// Returns the status of every hook Lintje can be installed in, with the path of the hook file
+pub fn hook_statuses() -> Result<Vec<(CommitHook, String, HookStatus)>, String> {
+    let mut statuses = vec![];
+    for hook in CommitHook::all() {
+        let hook_file = hook_path(&hook)?;
+        let installed = read_hook_file(&hook_file)?
+            .is_some_and(|contents| contents.lines().any(is_lintje_line));
+        let status = if !installed {
+            HookStatus::NotInstalled
+        } else if is_executable(&hook_file) {
+            HookStatus::Installed
+        } else {
+            HookStatus::NotExecutable
+        };
+        statuses.push((hook, hook_file.display().to_string(), status));
+    }
+    Ok(statuses)
+}
+
+// The hooks directory is resolved by Git, so it respects the `core.hooksPath` config and works in
+// worktrees and submodules, where `.git` is a file.
+fn hook_path(hook: &CommitHook) -> Result<PathBuf, String> {
+    Ok(git_path("hooks")?.join(hook.name()))
+}
+
+fn read_hook_file(path: &Path) -> Result<Option<String>, String> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(Some(contents)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
         Err(e) => Err(format!(
             "Unable to open Git hook file: {}\n{}",
-            hook_file.to_str().unwrap(),
+            path.display(),
             e
         )),
     }
 }
 
+fn write_hook_file(path: &Path, contents: &str) -> Result<(), String> {
+    fs::write(path, contents).map_err(|e| {
+        format!(
+            "Unable to write to Git hook file: {}\n{}",
+            path.display(),
+            e
+        )
+    })
+}
+
+// Returns the hook file contents with Lintje installed, or None if Lintje is already installed.
+fn installed_contents(contents: Option<&str>, hook: &CommitHook) -> Option<String> {
+    match contents {
+        Some(contents) if contents.lines().any(is_lintje_line) => None,
+        Some(contents) => {
+            let separator = if contents.is_empty() || contents.ends_with('\n') {
+                ""
+            } else {
+                "\n"
+            };
+            Some(format!("{}{}\n{}\n", contents, separator, hook.command()))
+        }
+        None => Some(format!("{}\n\n{}\n", SHEBANG, hook.command())),
+    }
+}
+
+// Returns the hook file contents without Lintje, or None if Lintje is not installed. Returns an
+// empty string if only the shebang remains.
+fn uninstalled_contents(contents: &str) -> Option<String> {
+    if !contents.lines().any(is_lintje_line) {
+        return None;
+    }
+    let lines = contents
+        .lines()
+        .filter(|line| !is_lintje_line(line))
+        .collect::<Vec<&str>>();
+    let remaining = lines.join("\n");
+    let remaining = remaining.trim_end();
+    if remaining.is_empty() || remaining == SHEBANG {
+        Some(String::new())
+    } else {
+        Some(format!("{}\n", remaining))
+    }
+}
+
+// A line that calls Lintje, with any options
+fn is_lintje_line(line: &str) -> bool {
+    line.split_whitespace()
+        .next()
+        .is_some_and(|command| command == "lintje" || command.ends_with("/lintje"))
+}
+
 #[cfg(target_family = "unix")]
 fn set_file_permissions(path: &Path) -> std::io::Result<()> {
     std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o744))
@@ -64,3 +215,74 @@ fn set_file_permissions(path: &Path) -> std::io::Result<()> {
 fn set_file_permissions(_file: &Path) -> std::io::Result<()> {
     Ok(())
 }
+
+#[cfg(target_family = "unix")]
+fn is_executable(path: &Path) -> bool {
+    path.metadata()
+        .is_ok_and(|metadata| metadata.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(target_family = "unix"))]
+fn is_executable(_path: &Path) -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{installed_contents, uninstalled_contents, CommitHook};
+
+    #[test]
+    fn install_in_new_file() {
+        assert_eq!(
+            installed_contents(None, &CommitHook::CommitMsg),
+            Some("#!/bin/sh\n\nlintje --hook-message-file=$1\n".to_string())
+        );
+    }
+
+    #[test]
+    fn install_in_existing_file() {
+        assert_eq!(
+            installed_contents(Some("#!/bin/sh\nnpm test"), &CommitHook::PostCommit),
+            Some("#!/bin/sh\nnpm test\n\nlintje\n".to_string())
+        );
+        assert_eq!(
+            installed_contents(Some("#!/bin/sh\nnpm test\n"), &CommitHook::PostCommit),
+            Some("#!/bin/sh\nnpm test\n\nlintje\n".to_string())
+        );
+    }
+
+    #[test]
+    fn install_when_already_installed() {
+        assert_eq!(
+            installed_contents(
+                Some("#!/bin/sh\n\nlintje --hook-message-file=$1\n"),
+                &CommitHook::CommitMsg
+            ),
+            None
+        );
+        // Lintje called with other options
+        assert_eq!(
+            installed_contents(
+                Some("\n\n/usr/local/bin/lintje --no-hints"),
+                &CommitHook::PostCommit
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn uninstall() {
+        assert_eq!(
+            uninstalled_contents("#!/bin/sh\nnpm test\n\nlintje\n"),
+            Some("#!/bin/sh\nnpm test\n".to_string())
+        );
+        assert_eq!(
+            uninstalled_contents("#!/bin/sh\n\nlintje --hook-message-file=$1\n"),
+            Some(String::new())
+        );
+        assert_eq!(uninstalled_contents("\n\nlintje"), Some(String::new()));
+        assert_eq!(uninstalled_contents("#!/bin/sh\nnpm test\n"), None);
+        // Other commands that start with lintje are left as is
+        assert_eq!(uninstalled_contents("lintje-other"), None);
+    }
+}
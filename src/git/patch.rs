@@ -0,0 +1,470 @@
+use regex::Regex;
+
+use crate::commit::Commit;
+use crate::git::{decode_bytes, parse_trailers_from_message, strip_trailers_from_message};
+
+// The line that separates the mail body from the signature, like the Git version that
+// `git format-patch` adds
+const SIGNATURE_SEPARATOR: &str = "-- ";
+
+lazy_static! {
+    // Leading `[PATCH]`, `[PATCH v2 1/3]` and `[RFC PATCH]` tags of the subject. Other tags, like
+    // `[Api]`, are part of the commit subject.
+    static ref PATCH_SUBJECT_PREFIX: Regex =
+        Regex::new(r"^(\s*\[[^\]]*\bPATCH\b[^\]]*\])+\s*").unwrap();
+    // The `0/3` number of the cover letter of a patch series in the subject tags
+    static ref COVER_LETTER_NUMBER: Regex = Regex::new(r"\b0+/\d+\b").unwrap();
+    // RFC 2047 encoded word: `=?charset?encoding?text?=`
+    static ref ENCODED_WORD: Regex = Regex::new(r"=\?([^?\s]+)\?([BbQq])\?([^?\s]*)\?=").unwrap();
+}
+
+/// Parse the commits of a patch file, like the output of `git format-patch` or an mbox archive of
+/// a mailing list. Every mail with a patch in the file is a commit. Mails without a subject, cover
+/// letters and mails without a diff or diffstat, like replies, are skipped.
+pub fn parse_patch_file(contents: &str) -> Vec<Commit> {
+    split_mails(contents)
+        .iter()
+        .filter_map(|mail| parse_mail(mail))
+        .collect()
+}
+
+// Split an mbox file on the `From ` lines that start every mail. A file without these lines is
+// parsed as a single mail.
+fn split_mails(contents: &str) -> Vec<Vec<&str>> {
+    let lines = contents.lines().collect::<Vec<&str>>();
+    let mut mails = vec![];
+    let mut mail = vec![];
+    let mut previous_line_empty = true;
+    for (index, line) in lines.iter().enumerate() {
+        // Patches from `git format-patch` do not escape `From ` lines in the message body, so
+        // only lines followed by a mail header start a new mail.
+        let mail_start = line.starts_with("From ")
+            && previous_line_empty
+            && lines
+                .get(index + 1)
+                .is_some_and(|next| is_header_line(next));
+        if mail_start && !mail.is_empty() {
+            mails.push(mail);
+            mail = vec![];
+        }
+        previous_line_empty = line.is_empty();
+        mail.push(*line);
+    }
+    if !mail.is_empty() {
+        mails.push(mail);
+    }
+    mails
+}
+
+fn parse_mail(lines: &[&str]) -> Option<Commit> {
+    let mut lines = lines;
+    let mut long_sha = None;
+    if let Some(line) = lines.first().and_then(|line| line.strip_prefix("From ")) {
+        // The `git format-patch` output starts with the SHA of the commit
+        long_sha = line
+            .split_whitespace()
+            .next()
+            .filter(|sha| sha.len() == 40 && sha.chars().all(|c| c.is_ascii_hexdigit()))
+            .map(std::string::ToString::to_string);
+        lines = &lines[1..];
+    }
+    let (headers, lines) = parse_headers(lines);
+    let subject = match header(&headers, "subject") {
+        Some(subject) => decode_header(subject),
+        None => {
+            debug!("Mail ignored: no subject header found: {:?}", headers);
+            return None;
+        }
+    };
+    if is_cover_letter(&subject) {
+        debug!("Mail ignored: cover letter of patch series: {}", subject);
+        return None;
+    }
+    let subject = strip_patch_prefix(&subject);
+    let email = header(&headers, "from").and_then(|from| parse_email(&decode_header(from)));
+
+    let patch_start = lines
+        .iter()
+        .position(|line| is_patch_start(line))
+        .unwrap_or(lines.len());
+    let file_changes = patch_file_changes(&lines[patch_start..]);
+    if file_changes.is_empty() {
+        debug!("Mail ignored: no diff or diffstat found: {}", subject);
+        return None;
+    }
+    // The message body also ends at the mail signature
+    let body_end = lines[..patch_start]
+        .iter()
+        .position(|line| *line == SIGNATURE_SEPARATOR)
+        .unwrap_or(patch_start);
+    let body = lines[..body_end]
+        .iter()
+        .map(|line| unescape_from_line(line.trim_end()))
+        .collect::<Vec<&str>>()
+        .join("\n");
+    let body = body.trim();
+    let message = if body.is_empty() {
+        String::new()
+    } else {
+        format!("\n{}", body)
+    };
+    let trailers = parse_trailers_from_message([subject.to_owned(), message.to_owned()].join("\n"));
+    let message = strip_trailers_from_message(&message, &trailers);
+    Some(Commit::new(
+        long_sha,
+        email,
+        &subject,
+        message,
+        trailers,
+        file_changes,
+    ))
+}
+
+// Returns the mail headers, with folded headers unfolded, and the lines after the headers
+fn parse_headers<'a>(lines: &'a [&'a str]) -> (Vec<(String, String)>, &'a [&'a str]) {
+    let mut headers: Vec<(String, String)> = vec![];
+    for (index, line) in lines.iter().enumerate() {
+        if line.trim().is_empty() {
+            return (headers, &lines[index + 1..]);
+        }
+        if line.starts_with(' ') || line.starts_with('\t') {
+            // Continuation line of a folded header
+            if let Some((_, value)) = headers.last_mut() {
+                value.push_str(line);
+            }
+            continue;
+        }
+        match line.split_once(':') {
+            Some((name, value)) => {
+                headers.push((name.trim().to_lowercase(), value.trim().to_string()));
+            }
+            None => debug!("Invalid mail header line: {}", line),
+        }
+    }
+    (headers, &[])
+}
+
+fn is_header_line(line: &str) -> bool {
+    line.split_once(':')
+        .is_some_and(|(name, _)| !name.is_empty() && !name.contains(char::is_whitespace))
+}
+
+fn header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(header_name, _)| header_name == name)
+        .map(|(_, value)| value.as_str())
+}
+
+// The `[PATCH 0/3]` cover letter describes a patch series, but is not a commit itself
+fn is_cover_letter(subject: &str) -> bool {
+    PATCH_SUBJECT_PREFIX
+        .find(subject.trim())
+        .is_some_and(|prefix| COVER_LETTER_NUMBER.is_match(prefix.as_str()))
+}
+
+fn strip_patch_prefix(subject: &str) -> String {
+    PATCH_SUBJECT_PREFIX
+        .replace(subject.trim(), "")
+        .trim_end()
+        .to_string()
+}
+
+// The email address of a `From` header, like `Jane Doe <jane@example.com>`
+fn parse_email(from: &str) -> Option<String> {
+    let email = match (from.rfind('<'), from.rfind('>')) {
+        (Some(start), Some(end)) if start < end => &from[start + 1..end],
+        _ => from.trim(),
+    };
+    if email.is_empty() {
+        None
+    } else {
+        Some(email.to_string())
+    }
+}
+
+// The message body ends at the diffstat separator or the first diff
+fn is_patch_start(line: &str) -> bool {
+    line.trim_end() == "---" || line.starts_with("diff --git ") || line.starts_with("Index: ")
+}
+
+// Lines starting with `From ` are escaped with a `>` in mbox files
+fn unescape_from_line(line: &str) -> &str {
+    match line.strip_prefix('>') {
+        Some(unescaped) if unescaped.trim_start_matches('>').starts_with("From ") => unescaped,
+        _ => line,
+    }
+}
+
+// Returns the files changed by the patch from the diff headers, or from the diffstat if the patch
+// contains no diff.
+fn patch_file_changes(lines: &[&str]) -> Vec<String> {
+    let mut files = lines
+        .iter()
+        .filter_map(|line| line.strip_prefix("diff --git "))
+        .filter_map(|paths| paths.split_once(" b/").map(|(_, path)| path.to_string()))
+        .collect::<Vec<String>>();
+    if files.is_empty() {
+        files = lines
+            .iter()
+            .skip_while(|line| line.trim_end() != "---")
+            .skip(1)
+            .take_while(|line| !line.trim().is_empty())
+            .filter_map(|line| line.split_once(" | "))
+            .map(|(path, _)| path.trim().to_string())
+            .collect();
+    }
+    files.dedup();
+    files
+}
+
+/// Decode the RFC 2047 encoded words in a mail header value, like
+/// `=?UTF-8?q?Caf=C3=A9?=`. The whitespace between encoded words is removed.
+fn decode_header(value: &str) -> String {
+    let mut decoded = String::new();
+    let mut last_end = 0;
+    let mut previous_encoded = false;
+    for captures in ENCODED_WORD.captures_iter(value) {
+        let word = captures.get(0).unwrap();
+        let between = &value[last_end..word.start()];
+        if !(previous_encoded && between.trim().is_empty()) {
+            decoded.push_str(between);
+        }
+        let bytes = match &captures[2] {
+            "B" | "b" => decode_base64(&captures[3]),
+            _ => decode_quoted_printable(&captures[3]),
+        };
+        match bytes {
+            Some(bytes) => {
+                decoded.push_str(&decode_bytes(&bytes, &captures[1]));
+                previous_encoded = true;
+            }
+            None => {
+                debug!("Invalid encoded word in mail header: {}", word.as_str());
+                decoded.push_str(word.as_str());
+                previous_encoded = false;
+            }
+        }
+        last_end = word.end();
+    }
+    decoded.push_str(&value[last_end..]);
+    decoded
+}
+
+// The "Q" encoding of RFC 2047, where underscores are spaces
+fn decode_quoted_printable(text: &str) -> Option<Vec<u8>> {
+    let mut bytes = vec![];
+    let mut chars = text.bytes();
+    while let Some(byte) = chars.next() {
+        match byte {
+            b'_' => bytes.push(b' '),
+            b'=' => {
+                let hex = [chars.next()?, chars.next()?];
+                bytes.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+            }
+            _ => bytes.push(byte),
+        }
+    }
+    Some(bytes)
+}
+
+fn decode_base64(text: &str) -> Option<Vec<u8>> {
+    let mut bytes = vec![];
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for byte in text.bytes().filter(|byte| *byte != b'=') {
+        let value = match byte {
+            b'A'..=b'Z' => byte - b'A',
+            b'a'..=b'z' => byte - b'a' + 26,
+            b'0'..=b'9' => byte - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return None,
+        };
+        buffer = (buffer << 6) | u32::from(value);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_header, parse_patch_file, strip_patch_prefix};
+
+    #[test]
+    fn format_patch_series() {
+        let commits = parse_patch_file(
+            "From 0000000000000000000000000000000000000000 Mon Sep 17 00:00:00 2001\n\
+            From: Jane Doe <jane@example.com>\n\
+            Date: Sat, 17 Oct 2026 12:00:00 +0200\n\
+            Subject: [PATCH 0/2] Login form\n\
+            \n\
+            Add a login form and fix a bug.\n\
+            \n\
+            Jane Doe (2):\n  \
+            Add login form\n  \
+            fixed bug\n\
+            \n \
+            README.md    | 1 +\n \
+            src/login.rs | 3 +++\n \
+            2 files changed, 4 insertions(+)\n\
+            \n\
+            -- \n\
+            2.40.0\n\
+            \n\
+            From 0123456789abcdef0123456789abcdef01234567 Mon Sep 17 00:00:00 2001\n\
+            From: Jane Doe <jane@example.com>\n\
+            Date: Sat, 17 Oct 2026 12:00:00 +0200\n\
+            Subject: [PATCH 1/2] Add login form\n\
+            \n\
+            Add a form to log in with an email address.\n\
+            \n\
+            Signed-off-by: Jane Doe <jane@example.com>\n\
+            ---\n \
+            src/login.rs | 3 +++\n \
+            1 file changed, 3 insertions(+)\n\
+            \n\
+            diff --git a/src/login.rs b/src/login.rs\n\
+            new file mode 100644\n\
+            --- /dev/null\n\
+            +++ b/src/login.rs\n\
+            @@ -0,0 +1,3 @@\n\
+            +fn login() {}\n\
+            -- \n\
+            2.40.0\n\
+            \n\
+            From fedcba9876543210fedcba9876543210fedcba98 Mon Sep 17 00:00:00 2001\n\
+            From: Jane Doe <jane@example.com>\n\
+            Subject: [PATCH 2/2] fixed bug\n\
+            \n\
+            ---\n\
+            diff --git a/README.md b/README.md\n\
+            diff --git a/src/main.rs b/src/main.rs\n",
+        );
+        assert_eq!(commits.len(), 2);
+        let first = &commits[0];
+        assert_eq!(
+            first.long_sha.as_deref(),
+            Some("0123456789abcdef0123456789abcdef01234567")
+        );
+        assert_eq!(first.short_sha.as_deref(), Some("0123456"));
+        assert_eq!(first.email.as_deref(), Some("jane@example.com"));
+        assert_eq!(first.subject, "Add login form");
+        assert_eq!(
+            first.message,
+            "\nAdd a form to log in with an email address."
+        );
+        assert_eq!(first.trailers, "Signed-off-by: Jane Doe <jane@example.com>");
+        assert_eq!(first.file_changes, vec!["src/login.rs"]);
+
+        let second = &commits[1];
+        assert_eq!(second.subject, "fixed bug");
+        assert_eq!(second.message, "");
+        assert_eq!(second.file_changes, vec!["README.md", "src/main.rs"]);
+    }
+
+    #[test]
+    fn mbox_with_encoded_and_folded_subject() {
+        let commits = parse_patch_file(
+            "From MAILER-DAEMON Sat Oct 17 12:00:00 2026\n\
+            From: =?UTF-8?q?Jos=C3=A9?= <jose@example.com>\n\
+            Subject: [RFC PATCH v2 3/5] Add =?UTF-8?q?caf=C3=A9?=\n \
+            =?UTF-8?B?IG1lbnU=?= page\n\
+            \n\
+            Add the page with the menu.\n\
+            >From now on the menu is shown.\n\
+            \n\
+            From the start page it's one click away.\n\
+            \n\
+            ---\n \
+            src/menu.rs  | 10 ++++++++++\n \
+            README.md    |  2 +-\n\
+            \n\
+            From MAILER-DAEMON Sat Oct 17 12:00:00 2026\n\
+            From: Jane Doe <jane@example.com>\n\
+            Subject: Re: [PATCH] Add login form\n\
+            \n\
+            Looks good to me.\n",
+        );
+        // Replies without a patch are skipped
+        assert_eq!(commits.len(), 1);
+        let commit = &commits[0];
+        assert_eq!(commit.long_sha, None);
+        assert_eq!(commit.email.as_deref(), Some("jose@example.com"));
+        assert_eq!(commit.subject, "Add café menu page");
+        assert_eq!(
+            commit.message,
+            "\nAdd the page with the menu.\nFrom now on the menu is shown.\n\n\
+            From the start page it's one click away."
+        );
+        assert_eq!(commit.file_changes, vec!["src/menu.rs", "README.md"]);
+    }
+
+    #[test]
+    fn mails_without_patch() {
+        let commits = parse_patch_file(
+            "From MAILER-DAEMON Sat Oct 17 12:00:00 2026\n\
+            From: Jane Doe <jane@example.com>\n\
+            Subject: Re: [PATCH] Add login form\n\
+            \n\
+            Looks good to me.\n\
+            \n\
+            From MAILER-DAEMON Sat Oct 17 12:00:00 2026\n\
+            From: Jane Doe <jane@example.com>\n\
+            Subject: [PATCH v2 00/12] Login form\n\
+            \n\
+            ---\n \
+            src/login.rs | 3 +++\n",
+        );
+        assert!(commits.is_empty());
+    }
+
+    #[test]
+    fn mail_signature() {
+        let commits = parse_patch_file(
+            "From: Jane Doe <jane@example.com>\n\
+            Subject: [PATCH] Add logout button\n\
+            \n\
+            Add a button to log out.\n\
+            \n\
+            -- \n\
+            2.39.5\n\
+            \n\
+            diff --git a/src/logout.rs b/src/logout.rs\n",
+        );
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].message, "\nAdd a button to log out.");
+        assert_eq!(commits[0].file_changes, vec!["src/logout.rs"]);
+    }
+
+    #[test]
+    fn patch_prefix() {
+        assert_eq!(strip_patch_prefix("[PATCH] Add login"), "Add login");
+        assert_eq!(
+            strip_patch_prefix("[PATCH v3 02/10] [Api] Add login"),
+            "[Api] Add login"
+        );
+        assert_eq!(strip_patch_prefix("[Api] Add login"), "[Api] Add login");
+        assert_eq!(strip_patch_prefix("[PATCH]"), "");
+    }
+
+    #[test]
+    fn encoded_headers() {
+        assert_eq!(decode_header("Plain subject"), "Plain subject");
+        assert_eq!(
+            decode_header("=?iso-8859-1?q?Caf=E9_menu?= page"),
+            "Café menu page"
+        );
+        assert_eq!(
+            decode_header("=?utf-8?b?Q2Fmw6k=?= =?utf-8?b?IG1lbnU=?="),
+            "Café menu"
+        );
+        // Invalid encoded words are left as is
+        assert_eq!(decode_header("=?utf-8?b?Q2$w6k=?="), "=?utf-8?b?Q2$w6k=?=");
+    }
+}
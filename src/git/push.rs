@@ -0,0 +1,251 @@
+use crate::branch::Branch;
+use crate::command::run_command;
+use crate::commit::Commit;
+use crate::git::{fetch_and_parse_commits, fetch_and_parse_new_commits};
+
+/// A ref that is updated by a push, from the old commit to the new commit. Git uses a SHA of only
+/// zeros for the old commit of a new ref and for the new commit of a deleted ref.
+#[derive(Debug, PartialEq)]
+pub struct RefUpdate {
+    pub old_sha: String,
+    pub new_sha: String,
+    pub ref_name: String,
+}
+
+impl RefUpdate {
+    pub fn is_new_ref(&self) -> bool {
+        is_null_sha(&self.old_sha)
+    }
+
+    pub fn is_deleted_ref(&self) -> bool {
+        is_null_sha(&self.new_sha)
+    }
+
+    /// Returns the name of the branch if the updated ref is a branch
+    pub fn branch_name(&self) -> Option<&str> {
+        self.ref_name.strip_prefix("refs/heads/")
+    }
+}
+
+/// The commits of a push that are new for the pushed branch
+#[derive(Debug)]
+pub struct PushedCommits {
+    pub branch: String,
+    pub commits: Vec<Commit>,
+}
+
+fn is_null_sha(sha: &str) -> bool {
+    sha.chars().all(|c| c == '0')
+}
+
+/// Parse the ref update lines Git writes to the STDIN of the pre-push hook, in the
+/// `<local ref> <local sha> <remote ref> <remote sha>` format.
+pub fn parse_pre_push_input(input: &str) -> Result<Vec<RefUpdate>, String> {
+    let mut updates = vec![];
+    for line in input.lines().filter(|line| !line.trim().is_empty()) {
+        match line.split_whitespace().collect::<Vec<&str>>().as_slice() {
+            [_local_ref, local_sha, remote_ref, remote_sha] => updates.push(RefUpdate {
+                old_sha: remote_sha.to_string(),
+                new_sha: local_sha.to_string(),
+                ref_name: remote_ref.to_string(),
+            }),
+            _ => return Err(format!("Invalid pre-push hook input line: {}", line)),
+        }
+    }
+    Ok(updates)
+}
+
+/// Parse the ref update lines Git writes to the STDIN of the pre-receive hook, in the
+/// `<old sha> <new sha> <ref name>` format.
+pub fn parse_pre_receive_input(input: &str) -> Result<Vec<RefUpdate>, String> {
+    let mut updates = vec![];
+    for line in input.lines().filter(|line| !line.trim().is_empty()) {
+        match line.split_whitespace().collect::<Vec<&str>>().as_slice() {
+            [old_sha, new_sha, ref_name] => updates.push(RefUpdate {
+                old_sha: old_sha.to_string(),
+                new_sha: new_sha.to_string(),
+                ref_name: ref_name.to_string(),
+            }),
+            _ => return Err(format!("Invalid pre-receive hook input line: {}", line)),
+        }
+    }
+    Ok(updates)
+}
+
+/// Returns the commits per branch that are not part of any branch or tag in the receiving
+/// repository yet, and the branches that are created by the push. Branches that already exist were
+/// accepted before, so their names are not linted again.
+pub fn fetch_received_commits_and_branches(
+    updates: &[RefUpdate],
+) -> Result<(Vec<PushedCommits>, Vec<Branch>), String> {
+    let mut commits = vec![];
+    let mut branches = vec![];
+    for update in updates {
+        if update.is_deleted_ref() {
+            debug!("Skipping deleted ref: {}", update.ref_name);
+            continue;
+        }
+        let branch_name = match update.branch_name() {
+            Some(name) => name,
+            None => {
+                debug!("Skipping ref that is not a branch: {}", update.ref_name);
+                continue;
+            }
+        };
+        commits.push(PushedCommits {
+            branch: branch_name.to_string(),
+            commits: fetch_and_parse_new_commits(&update.new_sha)?,
+        });
+        if update.is_new_ref() {
+            branches.push(Branch::new(branch_name.to_string()));
+        }
+    }
+    Ok((commits, branches))
+}
+
+/// Returns the commits per branch that are new on the remote and the branches that are pushed. The
+/// commits of new branches are the commits that are not part of the remote's default branch.
+pub fn fetch_pushed_commits_and_branches(
+    remote: &str,
+    updates: &[RefUpdate],
+) -> Result<(Vec<PushedCommits>, Vec<Branch>), String> {
+    let default_branch = remote_default_branch(remote);
+    let mut commits = vec![];
+    let mut branches = vec![];
+    for update in updates {
+        if update.is_deleted_ref() {
+            debug!("Skipping deleted ref: {}", update.ref_name);
+            continue;
+        }
+        let branch_name = match update.branch_name() {
+            Some(name) => name,
+            None => {
+                debug!("Skipping ref that is not a branch: {}", update.ref_name);
+                continue;
+            }
+        };
+        let range = commit_range(update, default_branch.as_deref());
+        commits.push(PushedCommits {
+            branch: branch_name.to_string(),
+            commits: fetch_and_parse_commits(&Some(range), None)?,
+        });
+        branches.push(Branch::new(branch_name.to_string()));
+    }
+    Ok((commits, branches))
+}
+
+// The range of commits that is new on the remote for the ref update. When the old commit of the
+// ref is unknown, because it's a new branch or the local repository has not fetched it, the
+// commits are compared to the remote's default branch. Without a known default branch, only the
+// pushed commit itself is linted.
+fn commit_range(update: &RefUpdate, default_branch: Option<&str>) -> String {
+    if !update.is_new_ref() && commit_exists(&update.old_sha) {
+        return format!("{}..{}", update.old_sha, update.new_sha);
+    }
+    match default_branch {
+        Some(base) => format!("{}..{}", base, update.new_sha),
+        None => update.new_sha.to_string(),
+    }
+}
+
+// The commit the remote's default branch points to, as last fetched from the remote
+fn remote_default_branch(remote: &str) -> Option<String> {
+    let head = format!("refs/remotes/{}/HEAD", remote);
+    match run_command("git", &["rev-parse", "--verify", "--quiet", &head]) {
+        Ok(stdout) => Some(stdout.trim().to_string()),
+        Err(e) => {
+            debug!("No default branch found for remote {}: {:?}", remote, e);
+            None
+        }
+    }
+}
+
+fn commit_exists(sha: &str) -> bool {
+    run_command("git", &["cat-file", "-e", &format!("{}^{{commit}}", sha)]).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{commit_range, parse_pre_push_input, parse_pre_receive_input, RefUpdate};
+
+    const NULL_SHA: &str = "0000000000000000000000000000000000000000";
+
+    #[test]
+    fn parse_pre_push_lines() {
+        let updates = parse_pre_push_input(&format!(
+            "refs/heads/feature-login aaa refs/heads/feature-login bbb\n\
+            (delete) {NULL_SHA} refs/heads/old-branch ccc\n"
+        ))
+        .unwrap();
+        assert_eq!(
+            updates,
+            vec![
+                RefUpdate {
+                    old_sha: "bbb".to_string(),
+                    new_sha: "aaa".to_string(),
+                    ref_name: "refs/heads/feature-login".to_string(),
+                },
+                RefUpdate {
+                    old_sha: "ccc".to_string(),
+                    new_sha: NULL_SHA.to_string(),
+                    ref_name: "refs/heads/old-branch".to_string(),
+                }
+            ]
+        );
+        assert_eq!(updates[0].branch_name(), Some("feature-login"));
+        assert!(!updates[0].is_deleted_ref());
+        assert!(updates[1].is_deleted_ref());
+    }
+
+    #[test]
+    fn parse_invalid_pre_push_lines() {
+        assert_eq!(
+            parse_pre_push_input("refs/heads/main aaa"),
+            Err("Invalid pre-push hook input line: refs/heads/main aaa".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_pre_receive_lines() {
+        let updates = parse_pre_receive_input(&format!(
+            "{NULL_SHA} aaa refs/heads/feature-login\n\
+            bbb ccc refs/tags/v1.0.0\n"
+        ))
+        .unwrap();
+        assert_eq!(
+            updates,
+            vec![
+                RefUpdate {
+                    old_sha: NULL_SHA.to_string(),
+                    new_sha: "aaa".to_string(),
+                    ref_name: "refs/heads/feature-login".to_string(),
+                },
+                RefUpdate {
+                    old_sha: "bbb".to_string(),
+                    new_sha: "ccc".to_string(),
+                    ref_name: "refs/tags/v1.0.0".to_string(),
+                }
+            ]
+        );
+        assert!(updates[0].is_new_ref());
+        assert!(!updates[1].is_new_ref());
+
+        assert_eq!(
+            parse_pre_receive_input("aaa bbb"),
+            Err("Invalid pre-receive hook input line: aaa bbb".to_string())
+        );
+    }
+
+    #[test]
+    fn new_branch_commit_range() {
+        let update = RefUpdate {
+            old_sha: NULL_SHA.to_string(),
+            new_sha: "aaa".to_string(),
+            ref_name: "refs/tags/v1.0.0".to_string(),
+        };
+        assert!(update.is_new_ref());
+        assert_eq!(update.branch_name(), None);
+        assert_eq!(commit_range(&update, Some("bbb")), "bbb..aaa");
+        assert_eq!(commit_range(&update, None), "aaa");
+    }
+}
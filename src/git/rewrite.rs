@@ -0,0 +1,272 @@
+use std::path::PathBuf;
+
+use crate::command::{run_command, run_command_with_stdin};
+
+// Commit object headers that are not copied to the rewritten commit. Parents are replaced by the
+// rewritten parents and signatures are no longer valid once the commit changes.
+const DROPPED_HEADERS: [&str; 4] = ["parent", "gpgsig", "gpgsig-sha256", "mergetag"];
+
+/// A commit object as stored by Git, split into its headers and message.
+#[derive(Debug, PartialEq)]
+pub struct CommitObject {
+    pub sha: String,
+    pub parents: Vec<String>,
+    headers: Vec<String>,
+    pub message: String,
+}
+
+impl CommitObject {
+    fn parse(sha: &str, contents: &str) -> Self {
+        let (header_block, message) = contents.split_once("\n\n").unwrap_or((contents, ""));
+        let mut parents = vec![];
+        let mut headers = Vec::<String>::new();
+        for line in header_block.lines() {
+            // Lines starting with a space continue the value of the previous header
+            if line.starts_with(' ') {
+                if let Some(header) = headers.last_mut() {
+                    header.push('\n');
+                    header.push_str(line);
+                }
+                continue;
+            }
+            headers.push(line.to_string());
+        }
+        headers.retain(|header| {
+            let name = header.split_once(' ').map_or(header.as_str(), |(n, _)| n);
+            if name == "parent" {
+                if let Some((_, parent)) = header.split_once(' ') {
+                    parents.push(parent.to_string());
+                }
+            }
+            !DROPPED_HEADERS.contains(&name)
+        });
+        Self {
+            sha: sha.to_string(),
+            parents,
+            headers,
+            message: message.to_string(),
+        }
+    }
+
+    /// Returns true if the commit message uses another encoding than UTF-8
+    pub fn has_custom_encoding(&self) -> bool {
+        self.headers.iter().any(|header| {
+            header
+                .strip_prefix("encoding ")
+                .is_some_and(|encoding| !encoding.eq_ignore_ascii_case("utf-8"))
+        })
+    }
+
+    // The commit object contents with the given parents, committer and message. All other
+    // headers, such as the tree and author, stay the same.
+    fn contents(&self, parents: &[String], committer: &str, message: &str) -> String {
+        let mut lines = vec![];
+        for header in &self.headers {
+            if header.starts_with("committer ") {
+                lines.push(format!("committer {}", committer));
+                continue;
+            }
+            lines.push(header.to_string());
+            if header.starts_with("tree ") {
+                for parent in parents {
+                    lines.push(format!("parent {}", parent));
+                }
+            }
+        }
+        format!("{}\n\n{}", lines.join("\n"), message)
+    }
+}
+
+pub fn read_commit(sha: &str) -> Result<CommitObject, String> {
+    match run_command("git", &["cat-file", "commit", sha]) {
+        Ok(contents) => Ok(CommitObject::parse(sha, &contents)),
+        Err(e) => {
+            debug!("Failed to read commit object: {:?}", e);
+            Err(e.message())
+        }
+    }
+}
+
+/// Write a copy of the commit with the given parents, committer and message to the object
+/// database, without changing the tree. Returns the SHA of the new commit.
+pub fn write_commit(
+    commit: &CommitObject,
+    parents: &[String],
+    committer: &str,
+    message: &str,
+) -> Result<String, String> {
+    match run_command_with_stdin(
+        "git",
+        &["hash-object", "-t", "commit", "-w", "--stdin"],
+        commit.contents(parents, committer, message),
+    ) {
+        Ok(stdout) => Ok(stdout.trim().to_string()),
+        Err(e) => {
+            debug!("Failed to write commit object: {:?}", e);
+            Err(e.message())
+        }
+    }
+}
+
+/// Returns the commits from the given commit up to and including HEAD, oldest commit first.
+pub fn commits_since(sha: &str, has_parent: bool) -> Result<Vec<String>, String> {
+    let range = format!("{}^..HEAD", sha);
+    let args = if has_parent {
+        vec!["rev-list", "--reverse", "--topo-order", &range]
+    } else {
+        vec!["rev-list", "--reverse", "--topo-order", "HEAD"]
+    };
+    match run_command("git", &args) {
+        Ok(stdout) => Ok(stdout.lines().map(|line| line.to_string()).collect()),
+        Err(e) => {
+            debug!("Failed to list commits: {:?}", e);
+            Err(e.message())
+        }
+    }
+}
+
+/// Point HEAD, or the branch it refers to, to the new commit. Fails if HEAD no longer points to
+/// the old commit.
+pub fn update_head(new_sha: &str, old_sha: &str) -> Result<(), String> {
+    match run_command(
+        "git",
+        &[
+            "update-ref",
+            "-m",
+            "lintje: reword",
+            "HEAD",
+            new_sha,
+            old_sha,
+        ],
+    ) {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            debug!("Failed to update HEAD: {:?}", e);
+            Err(e.message())
+        }
+    }
+}
+
+pub fn head_sha() -> Result<String, String> {
+    match run_command("git", &["rev-parse", "HEAD"]) {
+        Ok(stdout) => Ok(stdout.trim().to_string()),
+        Err(e) => {
+            debug!("Failed to fetch HEAD commit: {:?}", e);
+            Err(e.message())
+        }
+    }
+}
+
+/// Returns the name of the upstream branch of the current branch, if any
+pub fn upstream_branch() -> Option<String> {
+    match run_command(
+        "git",
+        &[
+            "rev-parse",
+            "--abbrev-ref",
+            "--symbolic-full-name",
+            "@{upstream}",
+        ],
+    ) {
+        Ok(stdout) => Some(stdout.trim().to_string()),
+        Err(e) => {
+            // Git fails if the branch has no upstream branch configured
+            debug!("No upstream branch found: {:?}", e);
+            None
+        }
+    }
+}
+
+/// Returns true if the commit is part of the history of the given revision
+pub fn is_ancestor(sha: &str, revision: &str) -> bool {
+    run_command("git", &["merge-base", "--is-ancestor", sha, revision]).is_ok()
+}
+
+/// Returns the path of a file in the Git directory
+pub fn git_path(name: &str) -> Result<PathBuf, String> {
+    match run_command("git", &["rev-parse", "--git-path", name]) {
+        Ok(stdout) => Ok(PathBuf::from(stdout.trim())),
+        Err(e) => {
+            debug!("Failed to determine Git directory: {:?}", e);
+            Err(e.message())
+        }
+    }
+}
+
+/// Returns the committer name, email and the current time, as Git writes them in new commits
+pub fn committer_ident() -> Result<String, String> {
+    match run_command("git", &["var", "GIT_COMMITTER_IDENT"]) {
+        Ok(stdout) => Ok(stdout.trim().to_string()),
+        Err(e) => {
+            debug!("Failed to determine Git committer: {:?}", e);
+            Err(e.message())
+        }
+    }
+}
+
+/// Returns the editor command configured for Git
+pub fn editor() -> Result<String, String> {
+    match run_command("git", &["var", "GIT_EDITOR"]) {
+        Ok(stdout) => Ok(stdout.trim().to_string()),
+        Err(e) => {
+            debug!("Failed to determine Git editor: {:?}", e);
+            Err(e.message())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CommitObject;
+
+    const COMMIT: &str = "tree 1111111111111111111111111111111111111111\n\
+        parent 2222222222222222222222222222222222222222\n\
+        author Person A <a@example.com> 1650000000 +0200\n\
+        committer Person A <a@example.com> 1650000000 +0200\n\
+        gpgsig -----BEGIN PGP SIGNATURE-----\n \n abc\n -----END PGP SIGNATURE-----\n\
+        \n\
+        Fixing bug\n\
+        \n\
+        Message body.\n";
+
+    #[test]
+    fn parse_commit_object() {
+        let commit = CommitObject::parse("aaa", COMMIT);
+        assert_eq!(
+            commit.parents,
+            vec!["2222222222222222222222222222222222222222".to_string()]
+        );
+        assert_eq!(commit.message, "Fixing bug\n\nMessage body.\n");
+        assert!(!commit.has_custom_encoding());
+    }
+
+    #[test]
+    fn rewritten_commit_contents() {
+        let commit = CommitObject::parse("aaa", COMMIT);
+        assert_eq!(
+            commit.contents(
+                &["3333333333333333333333333333333333333333".to_string()],
+                "Person B <b@example.com> 1660000000 +0100",
+                "Fix bug\n"
+            ),
+            "tree 1111111111111111111111111111111111111111\n\
+            parent 3333333333333333333333333333333333333333\n\
+            author Person A <a@example.com> 1650000000 +0200\n\
+            committer Person B <b@example.com> 1660000000 +0100\n\
+            \n\
+            Fix bug\n"
+        );
+    }
+
+    #[test]
+    fn commit_with_encoding() {
+        let commit = CommitObject::parse(
+            "aaa",
+            "tree 1111111111111111111111111111111111111111\n\
+            encoding ISO-8859-1\n\
+            \n\
+            Fix bug\n",
+        );
+        assert!(commit.has_custom_encoding());
+    }
+}
@@ -1,8 +1,10 @@
 use crate::rule::Rule;
 use core::ops::Range;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum IssueType {
     Error,
     Hint,
@@ -18,13 +20,22 @@ impl fmt::Display for IssueType {
     }
 }
 
-#[derive(Debug, PartialEq)]
+pub fn issue_type_by_name(name: &str) -> Option<IssueType> {
+    match name {
+        "error" | "Error" => Some(IssueType::Error),
+        "hint" | "Hint" => Some(IssueType::Hint),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
 pub struct Issue {
     pub r#type: IssueType,
     pub rule: Rule,
     pub message: String,
     pub position: Position,
     pub context: Vec<Context>,
+    pub suggestions: Vec<Suggestion>,
 }
 
 impl Issue {
@@ -35,6 +46,7 @@ impl Issue {
             message,
             position,
             context,
+            suggestions: vec![],
         }
     }
 
@@ -45,11 +57,120 @@ impl Issue {
             message,
             position,
             context,
+            suggestions: vec![],
+        }
+    }
+
+    /// Add a suggested fix for the issue
+    pub fn with_suggestion(mut self, suggestion: Suggestion) -> Self {
+        self.suggestions.push(suggestion);
+        self
+    }
+
+    /// Returns true if the issue has a suggestion that is safe to apply automatically
+    pub fn is_fixable(&self) -> bool {
+        self.suggestions
+            .iter()
+            .any(|suggestion| suggestion.applicability == Applicability::Safe)
+    }
+}
+
+/// How likely it is that applying a suggestion results in the message the author intended.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Applicability {
+    /// The suggestion only fixes the issue and does not change the meaning of the text. It can be
+    /// applied automatically.
+    Safe,
+    /// The suggestion fixes the issue, but may need further changes by the author.
+    MaybeIncorrect,
+}
+
+/// A change to the commit message or branch name that fixes an issue.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Suggestion {
+    pub message: String,
+    pub applicability: Applicability,
+    pub edits: Vec<Edit>,
+}
+
+impl Suggestion {
+    pub fn safe(message: String, edits: Vec<Edit>) -> Self {
+        Self {
+            message,
+            applicability: Applicability::Safe,
+            edits,
+        }
+    }
+
+    pub fn maybe_incorrect(message: String, edits: Vec<Edit>) -> Self {
+        Self {
+            message,
+            applicability: Applicability::MaybeIncorrect,
+            edits,
+        }
+    }
+}
+
+/// A text edit of a suggestion. Lines of the commit message are numbered like the issue
+/// positions: line 1 is the subject and line 2 the first line of the message body, including the
+/// trailers at the end of the message. Ranges are byte ranges within a line.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Edit {
+    /// Replace the range of a line of the commit message with the text
+    Replace {
+        line: usize,
+        range: Range<usize>,
+        text: String,
+    },
+    /// Insert a line before the given line of the commit message. If the commit message has
+    /// fewer lines, it is added at the end of the commit message instead.
+    InsertLine { line: usize, text: String },
+    /// Remove the line of the commit message
+    RemoveLine { line: usize },
+    /// Add a line at the end of the commit message, after the trailers
+    AppendLine { text: String },
+    /// Replace the range of the branch name with the text
+    ReplaceBranch { range: Range<usize>, text: String },
+}
+
+impl Edit {
+    /// Remove the range from the subject.
+    ///
+    /// When the removed text is a separate word, the whitespace around it is removed as well, so
+    /// the subject does not start or end with whitespace or contain a double space.
+    pub fn subject_removal(subject: &str, range: Range<usize>) -> Self {
+        Self::Replace {
+            line: 1,
+            range: removal_range_with_whitespace(subject, range),
+            text: "".to_string(),
+        }
+    }
+}
+
+fn removal_range_with_whitespace(text: &str, range: Range<usize>) -> Range<usize> {
+    let before = text.get(..range.start).unwrap_or("");
+    let after = text.get(range.end..).unwrap_or("");
+    if after.trim().is_empty() {
+        // At the end of the text, remove the whitespace before it
+        Range {
+            start: before.trim_end().len(),
+            end: text.len(),
         }
+    } else if before.is_empty() || before.ends_with(char::is_whitespace) {
+        // At the start of a word, remove the whitespace after it
+        Range {
+            start: range.start,
+            end: text.len() - after.trim_start().len(),
+        }
+    } else {
+        range
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
 pub enum Position {
     Subject { line: usize, column: usize },
     MessageLine { line: usize, column: usize },
@@ -57,7 +178,8 @@ pub enum Position {
     Branch { column: usize },
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ContextType {
     Plain,
     Gap,
@@ -66,7 +188,7 @@ pub enum ContextType {
     Removal,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
 pub struct Context {
     pub r#type: ContextType,
     pub line: Option<usize>,
@@ -243,6 +365,20 @@ impl Context {
         }
     }
 
+    pub fn branch_addition_suggestion(
+        content: String,
+        range: Range<usize>,
+        message: String,
+    ) -> Self {
+        Self {
+            r#type: ContextType::Addition,
+            line: None,
+            content,
+            range: Some(range),
+            message: Some(message),
+        }
+    }
+
     pub fn branch_removal_suggestion(
         content: String,
         range: Range<usize>,
@@ -257,3 +393,30 @@ impl Context {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Edit;
+    use core::ops::Range;
+
+    fn removal_range(subject: &str, range: Range<usize>) -> Range<usize> {
+        match Edit::subject_removal(subject, range) {
+            Edit::Replace { range, .. } => range,
+            edit => panic!("Unexpected edit: {:?}", edit),
+        }
+    }
+
+    #[test]
+    fn subject_removal() {
+        // Punctuation at the start of the subject
+        assert_eq!(removal_range("- Fix bug", 0..1), 0..2);
+        // Word in the middle of the subject
+        assert_eq!(removal_range("Fix #123 bug", 4..8), 4..9);
+        // Word at the end of the subject
+        assert_eq!(removal_range("Fix bug #123", 8..12), 7..12);
+        // Punctuation at the end of the subject
+        assert_eq!(removal_range("Fix bug.", 7..8), 7..8);
+        // Part of a word
+        assert_eq!(removal_range("Fix bug!ok", 7..8), 7..8);
+    }
+}
@@ -16,34 +16,50 @@ extern crate lazy_static;
 
 use log::LevelFilter;
 use std::fs::File;
-use std::io::{self, Read, Write};
-use std::path::Path;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Mutex};
 
 use termcolor::{ColorChoice, StandardStream, WriteColor};
 
+mod baseline;
 mod branch;
+mod cache;
 mod command;
 mod commit;
 mod config;
+mod fix;
 mod formatter;
 mod git;
 mod issue;
 mod logger;
+mod reword;
 mod rule;
 mod rules;
+mod template;
 #[cfg(test)]
 mod test;
 mod utils;
 
+use baseline::Baseline;
 use branch::Branch;
+use cache::ResultCache;
 use commit::Commit;
-use config::{fetch_options, Lint, ValidationContext};
-use formatter::{formatted_branch_issue, formatted_commit_issue};
+use config::{fetch_options, Lint, LintCommand, ValidationContext};
+use fix::fix_commit_message_file;
+use formatter::{formatted_branch_issue, formatted_commit_issue, OutputFormat, Summary};
+use git::hooks::{HookChange, HookStatus};
 use git::{
-    fetch_and_parse_branch, fetch_and_parse_commits, is_commit_ignored, parse_commit_file,
-    repo_has_changesets,
+    comment_char, current_branch, fetch_and_handle_commits, fetch_and_parse_branch,
+    fetch_and_parse_branches, is_commit_ignored, parse_commit_file, parse_commit_message,
+    patch::parse_patch_file,
+    push::{
+        fetch_pushed_commits_and_branches, fetch_received_commits_and_branches,
+        parse_pre_push_input, parse_pre_receive_input, PushedCommits,
+    },
+    repo_has_changesets, repository_root, revision_has_changesets,
 };
-use issue::IssueType;
+use issue::{Issue, IssueType};
 use logger::Logger;
 use utils::pluralize;
 
@@ -62,30 +78,133 @@ fn main() {
 
 fn handle_command(options: &Lint) -> Result<(), String> {
     if let Some(hook) = &options.install_hook {
-        return match git::hooks::install_hook(hook) {
-            Ok(file) => {
+        match git::hooks::install_hook(hook)? {
+            HookChange::Changed(file) => {
                 println!("Succesfully installed Lintje in hook: '{}'", file);
-                Ok(())
             }
-            Err(e) => Err(e),
-        };
+            HookChange::Unchanged(file) => {
+                println!("Lintje is already installed in hook: '{}'", file);
+            }
+        }
+        return Ok(());
+    }
+    if let Some(hook) = &options.uninstall_hook {
+        match git::hooks::uninstall_hook(hook)? {
+            HookChange::Changed(file) => {
+                println!("Succesfully uninstalled Lintje from hook: '{}'", file);
+            }
+            HookChange::Unchanged(file) => {
+                println!("Lintje is not installed in hook: '{}'", file);
+            }
+        }
+        return Ok(());
+    }
+    if options.clear_cache {
+        match cache::clear_cache()? {
+            Some(file) => println!("Succesfully cleared the Lintje cache: '{}'", file.display()),
+            None => println!("No Lintje cache found"),
+        }
+        return Ok(());
+    }
+    if options.hook_status {
+        for (hook, file, status) in git::hooks::hook_statuses()? {
+            let status = match status {
+                HookStatus::Installed => "installed",
+                HookStatus::NotExecutable => "installed, but the hook file is not executable",
+                HookStatus::NotInstalled => "not installed",
+            };
+            println!("{}: {} ('{}')", hook.name(), status, file);
+        }
+        return Ok(());
     }
 
-    let validate_changesets = repo_has_changesets();
+    if let Some(message_file) = &options.hook_prepare_message_file {
+        let branch = current_branch().ok();
+        template::prepare_commit_message_file(
+            message_file,
+            options.hook_message_source.as_deref(),
+            &options.config,
+            branch.as_deref(),
+            &comment_char(),
+        )?;
+        return Ok(());
+    }
+
+    let validate_changesets = if options.hook_pre_receive {
+        // Server repositories are bare, so look for changesets in the default branch
+        revision_has_changesets("HEAD")
+    } else if options.message_input() {
+        // Without the changed files there are no changesets to check. Messages can also be linted
+        // outside a Git repository, which has no changesets either.
+        !options.files.is_empty() && repository_root().is_ok() && repo_has_changesets()
+    } else {
+        repo_has_changesets()
+    };
+    // The branch name is only needed to check the branch's ticket reference in commits. The
+    // checked out branch is only used when the commits are on that branch.
+    let branch_name = if options.config.message_ticket_number.required {
+        match &options.branch_name {
+            Some(name) => Some(name.to_string()),
+            None if options.selects_current_branch() => current_branch().ok(),
+            None => None,
+        }
+    } else {
+        None
+    };
     let context = ValidationContext {
         changesets: validate_changesets,
+        config: options.config.clone(),
+        branch: branch_name,
     };
     debug!("Validating with context: {context:?}");
-    let commits = match &options.hook_message_file {
-        Some(hook_message_file) => lint_commit_hook(hook_message_file)?,
-        None => lint_commit(&options.selection)?,
-    };
-    let branch = if options.branch_validation {
-        Some(lint_branch()?)
+    if let Some(LintCommand::Reword { selection }) = &options.command {
+        return reword::reword_commits(selection, &context, options.hints);
+    }
+    if let (true, Some(hook_message_file)) = (options.fix, &options.hook_message_file) {
+        let fixed_issues = fix_commit_message_file(hook_message_file, &context)?;
+        print_fixed_issues(&fixed_issues, options);
+    }
+    let (groups, mut branches) = if let Some(remote) = &options.hook_pre_push {
+        let (pushed, branches) = lint_pre_push(remote)?;
+        (
+            pushed_commit_groups(pushed, &context),
+            pushed_branches(branches, options),
+        )
+    } else if options.hook_pre_receive {
+        let (pushed, branches) = lint_pre_receive()?;
+        (
+            pushed_commit_groups(pushed, &context),
+            pushed_branches(branches, options),
+        )
     } else {
-        None
+        let (commits, branches) = lint_selection(options)?;
+        (vec![(context.clone(), commits)], branches)
     };
-    match print_lint_result(commits, branch, options, &context) {
+    let mut commits = validate(groups, &mut branches, options, &context)?;
+    if let Some(path) = &options.write_baseline {
+        let path = baseline::baseline_path(path.as_deref().map(Path::new))?;
+        let baseline = Baseline::from_commits(&commits);
+        baseline.write(&path)?;
+        let count = baseline.issue_count();
+        println!(
+            "Succesfully wrote {} {} to the baseline: '{}'",
+            count,
+            pluralize("issue", count),
+            path.display()
+        );
+        return Ok(());
+    }
+    if let Some(path) = &options.baseline {
+        let baseline = Baseline::load(&baseline::baseline_path(path.as_deref().map(Path::new))?)?;
+        // Printed to STDERR so the output remains parsable
+        for entry in baseline.apply(&mut commits) {
+            eprintln!(
+                "Baseline entry does not match an issue anymore: {} {}",
+                entry.sha, entry.rule
+            );
+        }
+    }
+    match print_lint_result(&commits, &branches, options) {
         Ok(_) => Ok(()),
         Err(error) => Err(format!(
             "Error encountered while printing output: {}",
@@ -94,12 +213,196 @@ fn handle_command(options: &Lint) -> Result<(), String> {
     }
 }
 
-fn lint_branch() -> Result<Branch, String> {
-    fetch_and_parse_branch()
+// The commits and branches selected with the options, other than the pushed commits of the hooks
+fn lint_selection(options: &Lint) -> Result<(CommitSource, Vec<Branch>), String> {
+    if options.message_input() {
+        let commits = vec![parse_commit_message(
+            &read_message_input(options)?,
+            &options.files,
+        )];
+        Ok((CommitSource::Commits(commits), given_branch(options)))
+    } else if !options.patch.is_empty() {
+        Ok((
+            CommitSource::Commits(lint_patches(&options.patch)?),
+            given_branch(options),
+        ))
+    } else if options.lint_branches_only() {
+        Ok((
+            CommitSource::Commits(vec![]),
+            fetch_and_parse_branches(options.remote_branches.as_deref())?,
+        ))
+    } else {
+        let commits = match &options.hook_message_file {
+            Some(hook_message_file) => CommitSource::Commits(lint_commit_hook(hook_message_file)?),
+            None => CommitSource::Selection {
+                selection: options.selection.clone(),
+                max_commits: options.max_commits,
+            },
+        };
+        let branches = if options.branch_validation {
+            vec![lint_branch(&options.branch_name)?]
+        } else {
+            vec![]
+        };
+        Ok((commits, branches))
+    }
+}
+
+fn lint_branch(name: &Option<String>) -> Result<Branch, String> {
+    match name {
+        Some(name) => Ok(Branch::new(name.to_string())),
+        None => fetch_and_parse_branch(),
+    }
+}
+
+// The commits of the `--stdin`, `--message` and `--patch` options may not belong to the current
+// branch, so only a given branch name is linted.
+fn given_branch(options: &Lint) -> Vec<Branch> {
+    match (&options.branch_name, options.branch_validation) {
+        (Some(name), true) => vec![Branch::new(name.to_string())],
+        _ => vec![],
+    }
+}
+
+fn lint_patches(files: &[PathBuf]) -> Result<Vec<Commit>, String> {
+    let mut commits = vec![];
+    for file in files {
+        let contents = match std::fs::read_to_string(file) {
+            Ok(contents) => contents,
+            Err(e) => {
+                return Err(format!(
+                    "Unable to read patch file: {}\n{}",
+                    file.display(),
+                    e
+                ));
+            }
+        };
+        commits.append(&mut parse_patch_file(&contents));
+    }
+    // Files with only a cover letter are allowed, as long as the other files contain patches
+    if commits.is_empty() {
+        let names = files
+            .iter()
+            .map(|file| file.display().to_string())
+            .collect::<Vec<String>>();
+        return Err(format!(
+            "No patches found in {}: {}\n",
+            pluralize("file", names.len()),
+            names.join(", ")
+        ));
+    }
+    Ok(commits)
+}
+
+fn lint_pre_push(remote: &str) -> Result<(Vec<PushedCommits>, Vec<Branch>), String> {
+    let updates = parse_pre_push_input(&read_ref_updates()?)?;
+    fetch_pushed_commits_and_branches(remote, &updates)
+}
+
+fn lint_pre_receive() -> Result<(Vec<PushedCommits>, Vec<Branch>), String> {
+    let updates = parse_pre_receive_input(&read_ref_updates()?)?;
+    fetch_received_commits_and_branches(&updates)
+}
+
+fn read_ref_updates() -> Result<String, String> {
+    let mut input = String::new();
+    match io::stdin().read_to_string(&mut input) {
+        Ok(_) => Ok(input),
+        Err(e) => Err(format!("Unable to read pushed refs from STDIN: {}\n", e)),
+    }
+}
+
+// The commits of every pushed branch, with the validation context of that branch. When the
+// branch's ticket is required, the commits are checked against the ticket of the branch they are
+// pushed to, rather than the checked out branch. Commits pushed to multiple branches at once are
+// linted once per branch.
+fn pushed_commit_groups(
+    pushed: Vec<PushedCommits>,
+    context: &ValidationContext,
+) -> Vec<(ValidationContext, CommitSource)> {
+    let mut groups: Vec<(ValidationContext, Vec<Commit>)> = vec![];
+    for PushedCommits { branch, commits } in pushed {
+        let branch = context
+            .config
+            .message_ticket_number
+            .required
+            .then_some(branch);
+        let index = match groups.iter().position(|(c, _)| c.branch == branch) {
+            Some(index) => index,
+            None => {
+                let group_context = ValidationContext {
+                    branch,
+                    ..context.clone()
+                };
+                groups.push((group_context, vec![]));
+                groups.len() - 1
+            }
+        };
+        let group_commits = &mut groups[index].1;
+        for commit in commits {
+            if !group_commits.iter().any(|c| c.long_sha == commit.long_sha) {
+                group_commits.push(commit);
+            }
+        }
+    }
+    groups
+        .into_iter()
+        .map(|(context, commits)| (context, CommitSource::Commits(commits)))
+        .collect()
+}
+
+fn pushed_branches(branches: Vec<Branch>, options: &Lint) -> Vec<Branch> {
+    if options.branch_validation {
+        branches
+    } else {
+        vec![]
+    }
+}
+
+// The commit message of the `--stdin` or `--message` option. Messages given with `--message` are
+// paragraphs of the commit message, like Git does for `git commit --message`.
+fn read_message_input(options: &Lint) -> Result<String, String> {
+    if !options.stdin {
+        return Ok(options.message.join("\n\n"));
+    }
+    let mut input = String::new();
+    match io::stdin().read_to_string(&mut input) {
+        Ok(_) => Ok(input),
+        Err(e) => Err(format!("Unable to read commit message from STDIN: {}\n", e)),
+    }
+}
+
+// The commits to validate. The commits of a selection are validated while they are read from the
+// Git log.
+enum CommitSource {
+    Commits(Vec<Commit>),
+    Selection {
+        selection: Option<String>,
+        max_commits: Option<usize>,
+    },
 }
 
-fn lint_commit(selection: &Option<String>) -> Result<Vec<Commit>, String> {
-    fetch_and_parse_commits(selection)
+fn validate_selection(
+    selection: &Option<String>,
+    max_commits: Option<usize>,
+    context: &ValidationContext,
+    cache: Option<&mut ResultCache>,
+) -> Result<Vec<Commit>, String> {
+    // Fetch one more commit than the maximum to tell if the selection contains more commits
+    let mut commits = validate_commits(context, cache, |handle| {
+        fetch_and_handle_commits(selection, max_commits.map(|max| max + 1), handle)
+    })?;
+    if let Some(max) = max_commits {
+        if commits.len() > max {
+            commits.truncate(max);
+            eprintln!(
+                "Only the latest {} of the selected commits are linted, as set by the \
+                --max-commits option.",
+                max
+            );
+        }
+    }
+    Ok(commits)
 }
 
 fn lint_commit_hook(filename: &Path) -> Result<Vec<Commit>, String> {
@@ -131,46 +434,197 @@ fn lint_commit_hook(filename: &Path) -> Result<Vec<Commit>, String> {
     Ok(commits)
 }
 
-fn print_lint_result(
-    mut commits: Vec<Commit>,
-    branch: Option<Branch>,
+// Print the fixed issues to STDOUT for the text format. For other formats, print them to STDERR
+// so the output remains parsable.
+fn print_fixed_issues(issues: &[Issue], options: &Lint) {
+    for issue in issues {
+        let line = format!("Fixed[{}]: {}", issue.rule, issue.message);
+        match options.format() {
+            OutputFormat::Text => println!("{}", line),
+            _ => eprintln!("{}", line),
+        }
+    }
+}
+
+// Validate the commits in parallel on the available CPU cores, while `read` passes the commits to
+// validate one by one. The validated commits are returned in the order they were read. Commits
+// with a cached result are not validated again.
+fn validate_commits<F>(
+    context: &ValidationContext,
+    mut cache: Option<&mut ResultCache>,
+    read: F,
+) -> Result<Vec<Commit>, String>
+where
+    F: FnOnce(&mut dyn FnMut(Commit)) -> Result<(), String>,
+{
+    let threads = std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get);
+    let (pending_sender, pending_receiver) = mpsc::channel::<(usize, Commit)>();
+    let pending_receiver = Mutex::new(pending_receiver);
+    let (validated_sender, validated_receiver) = mpsc::channel::<(usize, Commit)>();
+    let result = std::thread::scope(|scope| {
+        for _ in 0..threads {
+            let pending_receiver = &pending_receiver;
+            let validated_sender = validated_sender.clone();
+            scope.spawn(move || loop {
+                // The lock is only held while waiting for the next commit
+                let next = pending_receiver
+                    .lock()
+                    .expect("Lintje failed to receive the next commit")
+                    .recv();
+                match next {
+                    Ok((index, mut commit)) => {
+                        commit.validate(context);
+                        let _ = validated_sender.send((index, commit));
+                    }
+                    // All commits are read
+                    Err(_) => break,
+                }
+            });
+        }
+        let mut index = 0;
+        let result = read(&mut |mut commit| {
+            commit.ignored = is_commit_ignored(&commit);
+            if commit.ignored
+                || cache
+                    .as_mut()
+                    .is_some_and(|cache| cache.restore(&mut commit))
+            {
+                let _ = validated_sender.send((index, commit));
+            } else {
+                let _ = pending_sender.send((index, commit));
+            }
+            index += 1;
+        });
+        // Stop the workers once they have validated the pending commits
+        drop(pending_sender);
+        result
+    });
+    drop(validated_sender);
+    result?;
+    let mut commits = validated_receiver
+        .into_iter()
+        .collect::<Vec<(usize, Commit)>>();
+    commits.sort_by_key(|(index, _)| *index);
+    Ok(commits.into_iter().map(|(_, commit)| commit).collect())
+}
+
+// The cache of commit results, if enabled. The cache is an optimization, so errors are not
+// reported as failures.
+fn load_cache(options: &Lint, context: &ValidationContext) -> Option<ResultCache> {
+    if !options.cache_results() {
+        return None;
+    }
+    match ResultCache::load(context) {
+        Ok(cache) => Some(cache),
+        Err(e) => {
+            debug!("Unable to load the cache: {}", e);
+            None
+        }
+    }
+}
+
+// Validate the commits with the context of their group and the branches. Returns the validated
+// commits of all groups.
+fn validate(
+    groups: Vec<(ValidationContext, CommitSource)>,
+    branches: &mut [Branch],
     options: &Lint,
     context: &ValidationContext,
+) -> Result<Vec<Commit>, String> {
+    let mut cache = load_cache(options, context);
+    let mut commits = vec![];
+    for (group_context, source) in groups {
+        // The cache only holds the results of the context it was loaded for
+        let use_cache = group_context.branch == context.branch;
+        let group_cache = cache.as_mut().filter(|_| use_cache);
+        let mut group_commits = match source {
+            CommitSource::Commits(group_commits) => {
+                validate_commits(&group_context, group_cache, |handle| {
+                    group_commits.into_iter().for_each(handle);
+                    Ok(())
+                })?
+            }
+            CommitSource::Selection {
+                selection,
+                max_commits,
+            } => validate_selection(&selection, max_commits, &group_context, group_cache)?,
+        };
+        if let (true, Some(cache)) = (use_cache, cache.as_mut()) {
+            cache.update(&group_commits);
+        }
+        commits.append(&mut group_commits);
+    }
+    if let Some(cache) = cache {
+        if let Err(e) = cache.save() {
+            debug!("Unable to save the cache: {}", e);
+        }
+    }
+    for branch in branches.iter_mut() {
+        branch.validate(context);
+    }
+    Ok(commits)
+}
+
+fn print_lint_result(commits: &[Commit], branches: &[Branch], options: &Lint) -> io::Result<()> {
+    let summary = Summary::new(commits, branches);
+
+    match options.format() {
+        OutputFormat::Text => print_text_result(commits, branches, options, &summary)?,
+        OutputFormat::Json => {
+            let report = formatter::json::Report::new(commits, branches, &summary, options.hints);
+            report.write(&mut io::stdout())?;
+        }
+        OutputFormat::Sarif => {
+            let log = formatter::sarif::Log::new(commits, branches, options.hints);
+            log.write(&mut io::stdout())?;
+        }
+        OutputFormat::Github => {
+            formatter::github::write_commands(&mut io::stdout(), commits, branches, options.hints)?;
+            let mut out = buffer_writer(options.color());
+            print_summary(&mut out, &summary, options)?;
+        }
+        OutputFormat::Gitlab => {
+            let report = formatter::gitlab::Report::new(commits, branches, options.hints);
+            report.write(&mut io::stdout())?;
+        }
+        OutputFormat::Compact => {
+            formatter::compact::write_issues(&mut io::stdout(), commits, branches, options.hints)?;
+            let mut out = buffer_writer(options.color());
+            print_summary(&mut out, &summary, options)?;
+        }
+        OutputFormat::Junit => {
+            formatter::junit::write_report(&mut io::stdout(), commits, branches, options.hints)?;
+        }
+    }
+    if summary.error_count > 0 {
+        std::process::exit(1)
+    }
+    Ok(())
+}
+
+fn print_text_result(
+    commits: &[Commit],
+    branches: &[Branch],
+    options: &Lint,
+    summary: &Summary,
 ) -> io::Result<()> {
     let mut out = buffer_writer(options.color());
-    let mut error_count = 0;
-    let mut hint_count = 0;
-    let mut commit_count = 0;
-    let mut ignored_commit_count = 0;
-    let mut branch_message = "";
-
-    for commit in commits.iter_mut() {
-        let ignored_commit = is_commit_ignored(commit);
-        if !ignored_commit {
-            commit.validate(context);
-        }
+
+    for commit in commits {
         if options.verbose {
             println!("{}", commit);
         }
-        if ignored_commit {
-            ignored_commit_count += 1;
+        if commit.ignored {
             if options.verbose {
                 println!("No issues: Commit ignored.");
             }
             continue;
         }
-        commit_count += 1;
         if !commit.is_valid() {
             for issue in &commit.issues {
                 let show = match issue.r#type {
-                    IssueType::Error => {
-                        error_count += 1;
-                        true
-                    }
-                    IssueType::Hint => {
-                        hint_count += 1;
-                        options.hints
-                    }
+                    IssueType::Error => true,
+                    IssueType::Hint => options.hints,
                 };
                 if show {
                     formatted_commit_issue(&mut out, commit, issue)?;
@@ -179,41 +633,52 @@ fn print_lint_result(
         }
     }
 
-    if let Some(branch) = branch {
+    // Print the issues grouped per branch
+    for branch in branches {
         if options.verbose {
             println!("{}", branch);
         }
-        branch_message = " and branch";
         if !branch.is_valid() {
             for issue in &branch.issues {
-                match issue.r#type {
-                    IssueType::Error => error_count += 1,
-                    IssueType::Hint => hint_count += 1,
-                }
-                formatted_branch_issue(&mut out, &branch, issue)?;
+                formatted_branch_issue(&mut out, branch, issue)?;
             }
         }
     }
 
-    let commit_label = pluralize("commit", commit_count);
-    write!(
-        out,
-        "{} {}{} inspected, ",
-        commit_count, commit_label, branch_message
-    )?;
-    print_issue_counts(&mut out, error_count, hint_count, options.hints)?;
-    if ignored_commit_count > 0 || options.debug {
-        let ignored_commit_label = pluralize("commit", ignored_commit_count);
+    print_summary(&mut out, summary, options)
+}
+
+fn print_summary(out: &mut impl WriteColor, summary: &Summary, options: &Lint) -> io::Result<()> {
+    if options.lint_branches_only() {
+        let branch_label = if summary.branch_count == 1 {
+            "branch"
+        } else {
+            "branches"
+        };
+        write!(out, "{} {} inspected, ", summary.branch_count, branch_label)?;
+    } else {
+        let branch_message = match summary.branch_count {
+            0 => "".to_string(),
+            1 => " and branch".to_string(),
+            count => format!(" and {} branches", count),
+        };
+        let commit_label = pluralize("commit", summary.commit_count);
+        write!(
+            out,
+            "{} {}{} inspected, ",
+            summary.commit_count, commit_label, branch_message
+        )?;
+    }
+    print_issue_counts(out, summary.error_count, summary.hint_count, options.hints)?;
+    if summary.ignored_commit_count > 0 || options.debug {
+        let ignored_commit_label = pluralize("commit", summary.ignored_commit_count);
         write!(
             out,
             " ({} {} ignored)",
-            ignored_commit_count, ignored_commit_label
+            summary.ignored_commit_count, ignored_commit_label
         )?;
     }
     writeln!(out)?;
-    if error_count > 0 {
-        std::process::exit(1)
-    }
     Ok(())
 }
 
@@ -600,7 +1065,7 @@ mod tests {
             \x20\x200000000:1:1: Fixing tests\n\
             \x20\x20  | \n\
             \x20\x201 | Fixing tests\n\
-            \x20\x20  | ^^^^^^ Use the imperative mood for the subject\n\
+            \x20\x20  | ^^^^^^ Use `Fix` instead of `Fixing`\n\
             \x20\x20  | \n\
             \x20\x20  = help: https://r.lintje.dev/r/SubjectMood\n\
             \n\
@@ -648,7 +1113,7 @@ mod tests {
             \x20\x20\u{1b}[0m\u{1b}[38;5;12m0000000:1:1:\u{1b}[0m Fixing tests\n\
             \u{1b}[0m\u{1b}[38;5;12m    | \u{1b}[0m\n\
             \u{1b}[0m\u{1b}[38;5;12m  1 | \u{1b}[0mFixing tests\n\
-            \u{1b}[0m\u{1b}[38;5;12m    | \u{1b}[0m\u{1b}[0m\u{1b}[38;5;9m^^^^^^ Use the imperative mood for the subject\u{1b}[0m\n\
+            \u{1b}[0m\u{1b}[38;5;12m    | \u{1b}[0m\u{1b}[0m\u{1b}[38;5;9m^^^^^^ Use `Fix` instead of `Fixing`\u{1b}[0m\n\
             \u{1b}[0m\u{1b}[38;5;12m    | \u{1b}[0m\n\
             \u{1b}[0m\u{1b}[38;5;12m    = \u{1b}[0mhelp: https://r.lintje.dev/r/SubjectMood\n\
             \n\
@@ -864,6 +1329,44 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_file_option_with_fix() {
+        compile_bin();
+        let dir = test_dir("commit_file_option_with_fix");
+        create_test_repo(&dir);
+        create_dummy_file(&dir.join("file name"));
+        stage_files(&dir);
+        let filename = "commit_message_file";
+        let commit_file = dir.join(filename);
+        let mut file = File::create(&commit_file).unwrap();
+        file.write_all(b"fix the bug.\nValid message body.\n# Comment\n")
+            .unwrap();
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args([
+                "--no-color",
+                "--fix",
+                &format!("--hook-message-file={}", filename),
+            ])
+            .current_dir(dir)
+            .assert()
+            .success();
+        assert
+            .stdout(predicate::str::contains(
+                "Fixed[SubjectCapitalization]: The subject does not start with a capital letter",
+            ))
+            .stdout(predicate::str::contains("Fixed[SubjectPunctuation]: "))
+            .stdout(predicate::str::contains("Fixed[MessageEmptyFirstLine]: "))
+            .stdout(predicate::str::contains(
+                "1 commit and branch inspected, 0 errors detected",
+            ));
+        assert_eq!(
+            std::fs::read_to_string(commit_file).unwrap(),
+            "Fix the bug\n\nValid message body.\n# Comment\n"
+        );
+    }
+
     #[test]
     fn test_file_option_with_scissors_cleanup() {
         compile_bin();
@@ -1044,105 +1547,1167 @@ mod tests {
     }
 
     #[test]
-    fn options_file_present() {
+    fn test_given_branch_name() {
         compile_bin();
-        let dir = test_dir("options_config_file_present");
+        let dir = test_dir("branch_name_given");
         create_test_repo(&dir);
         create_commit_with_file(&dir, "Test commit", "I am a test commit.", "file");
-        let config_filename = Path::new("options.txt");
-        let config_file = dir.join(config_filename);
-        create_file(&config_file, b"--debug\n--no-branch");
+        checkout_branch(&dir, "my-branch");
 
         let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
         let assert = cmd
-            .arg("--no-color")
-            .env("LINTJE_OPTIONS_PATH", config_filename)
+            .args(["--no-color", "--no-hints", "--branch", "fix-123"])
             .current_dir(dir)
             .assert()
-            .success();
-
-        // Prints a debug statement (options file)
-        // Does not validate branch (options file)
-        // But does not print the output with color (CLI flag)
+            .failure()
+            .code(1);
         assert
-            .stderr("") // Does not print any warnings to STDERR
-            .stdout(predicate::str::contains("[DEBUG]"))
             .stdout(predicate::str::contains(
-                "1 commit inspected, 0 errors detected",
+                "Error[BranchNameTicketNumber]: A ticket number was detected in the branch name\n\
+                \x20\x20Branch:1: fix-123\n",
+            ))
+            .stdout(predicate::str::contains(
+                "1 commit and branch inspected, 2 errors detected",
             ));
     }
 
     #[test]
-    fn options_file_missing() {
+    fn test_all_branches() {
         compile_bin();
-        let dir = test_dir("options_config_file_missing");
+        let dir = test_dir("branch_all_local");
         create_test_repo(&dir);
-        create_commit_with_file(&dir, "Test commit", "I am a test commit.", "file");
-        let config_filename = Path::new("options.txt");
+        checkout_branch(&dir, "fix-123");
+        checkout_branch(&dir, "feature-login-form");
+        // An invalid commit that is not linted when only linting branches
+        create_commit_with_file(&dir, "fixing bug", "", "file");
 
         let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
         let assert = cmd
-            .env("LINTJE_OPTIONS_PATH", config_filename)
+            .args(["--no-color", "--no-hints", "--all-branches"])
             .current_dir(dir)
             .assert()
-            .success();
-
-        assert.stderr(predicate::str::contains(
-            "ERROR: Configured LINTJE_OPTIONS_PATH does not exist or is not a file. Path: \'options.txt\'",
-        ));
+            .failure()
+            .code(1);
+        assert
+            .stdout(predicate::str::contains("Branch:1: fix-123\n"))
+            .stdout(predicate::str::contains("feature-login-form").not())
+            .stdout(predicate::str::contains("Error[Subject").not())
+            .stdout(predicate::str::contains(
+                "3 branches inspected, 2 errors detected",
+            ));
     }
 
     #[test]
-    fn git_hook_install_commit_msg() {
+    fn test_remote_branches() {
         compile_bin();
-        let dir = test_dir("git_hook_install_commit_msg");
+        let dir = test_dir("branch_all_remote");
         create_test_repo(&dir);
+        checkout_branch(&dir, "feature-login-form");
+        for args in [
+            vec!["update-ref", "refs/remotes/origin/fix-123", "HEAD"],
+            vec![
+                "update-ref",
+                "refs/remotes/origin/feature-login-form",
+                "HEAD",
+            ],
+            vec![
+                "symbolic-ref",
+                "refs/remotes/origin/HEAD",
+                "refs/remotes/origin/feature-login-form",
+            ],
+            vec!["update-ref", "refs/remotes/upstream/fix-456", "HEAD"],
+        ] {
+            Command::new("git")
+                .args(args)
+                .current_dir(&dir)
+                .output()
+                .unwrap();
+        }
 
         let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
         let assert = cmd
-            .current_dir(&dir)
-            .arg("--install-hook=commit-msg")
+            .args(["--no-color", "--no-hints", "--remote-branches", "origin"])
+            .current_dir(dir)
             .assert()
-            .success();
-
-        assert.stdout(predicate::str::contains(
-            "Succesfully installed Lintje in hook: '.git/hooks/commit-msg'",
-        ));
+            .failure()
+            .code(1);
+        assert
+            .stdout(predicate::str::contains("Branch:1: fix-123\n"))
+            .stdout(predicate::str::contains("fix-456").not())
+            .stdout(predicate::str::contains(
+                "2 branches inspected, 2 errors detected",
+            ));
+    }
 
-        let hook_file = dir.join(".git/hooks/commit-msg");
-        let contents = std::fs::read_to_string(&hook_file).expect("Can't read hook file");
-        assert_eq!(contents, "\n\nlintje --hook-message-file=$1");
+    fn git_output(dir: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .expect("Could not run Git command");
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
 
-        let permissions = hook_file.metadata().unwrap().permissions();
-        assert_eq!(format!("{:#o}", permissions.mode()), "0o100744");
+    fn create_remote_default_branch(dir: &Path, remote: &str) {
+        let default_ref = format!("refs/remotes/{}/main", remote);
+        git_output(dir, &["update-ref", &default_ref, "HEAD"]);
+        git_output(
+            dir,
+            &[
+                "symbolic-ref",
+                &format!("refs/remotes/{}/HEAD", remote),
+                &default_ref,
+            ],
+        );
     }
 
     #[test]
-    fn git_hook_install_post_commit() {
+    fn cached_commit_results() {
         compile_bin();
-        let dir = test_dir("git_hook_install_post_commit");
+        let dir = test_dir("cached_commit_results");
         create_test_repo(&dir);
+        create_commit_with_file(&dir, "fixing bug", "Some message.", "file1");
+        let cache_file = dir.join(".git/lintje/cache.json");
 
         let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
-        let assert = cmd
+        cmd.args(["--no-color", "--no-branch"])
             .current_dir(&dir)
-            .arg("--install-hook=post-commit")
             .assert()
-            .success();
-
-        assert.stdout(predicate::str::contains(
-            "Succesfully installed Lintje in hook: '.git/hooks/post-commit'",
-        ));
+            .failure()
+            .stdout(predicate::str::contains(
+                "The subject does not use the imperative grammatical mood",
+            ));
+        // Change the cached result, to tell it apart from a new validation
+        let contents = std::fs::read_to_string(&cache_file).unwrap();
+        let changed = contents.replace(
+            "The subject does not use the imperative grammatical mood",
+            "Cached issue message",
+        );
+        create_file(&cache_file, changed.as_bytes());
 
-        let hook_file = dir.join(".git/hooks/post-commit");
-        let contents = std::fs::read_to_string(hook_file).expect("Can't read hook file");
-        assert_eq!(contents, "\n\nlintje");
-    }
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.args(["--no-color", "--no-branch"])
+            .current_dir(&dir)
+            .assert()
+            .failure()
+            .stdout(predicate::str::contains("Cached issue message"));
 
-    #[test]
-    fn git_hook_install_with_file_exists() {
-        compile_bin();
-        let dir = test_dir("git_hook_install_with_file_exists");
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.args(["--no-color", "--no-branch", "--no-cache"])
+            .current_dir(&dir)
+            .assert()
+            .failure()
+            .stdout(predicate::str::contains(
+                "The subject does not use the imperative grammatical mood",
+            ));
+
+        // A different configuration does not use the cached results
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.args(["--no-color", "--no-branch", "--disable", "SubjectLength"])
+            .current_dir(&dir)
+            .assert()
+            .failure()
+            .stdout(predicate::str::contains("Cached issue message").not());
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.args(["--clear-cache"])
+            .current_dir(&dir)
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(
+                "Succesfully cleared the Lintje cache: '.git/lintje/cache.json'",
+            ));
+        assert!(!cache_file.exists());
+    }
+
+    #[test]
+    fn lint_with_baseline() {
+        compile_bin();
+        let dir = test_dir("lint_with_baseline");
+        create_test_repo(&dir);
+        create_commit_with_file(&dir, "Fixing bug one", "Some message.", "file1");
+        create_commit_with_file(&dir, "Fixing bug two", "Some message.", "file2");
+        let baseline_file = dir.join("lintje-baseline.toml");
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.args([
+            "--no-color",
+            "--no-branch",
+            "--write-baseline",
+            "HEAD~2..HEAD",
+        ])
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Succesfully wrote 4 issues to the baseline:",
+        ));
+        let contents = std::fs::read_to_string(&baseline_file).unwrap();
+        let sha = git_output(&dir, &["rev-parse", "HEAD"]);
+        assert!(contents.contains(&format!(
+            "{} = [\"SubjectMood\", \"MessageTicketNumber\"]",
+            sha
+        )));
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.args(["--no-color", "--no-branch", "--baseline", "HEAD~2..HEAD"])
+            .current_dir(&dir)
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(
+                "2 commits inspected, 0 errors detected",
+            ));
+
+        // New issues are reported, and entries without an issue are flagged
+        create_commit_with_file(&dir, "Fixing bug three", "Some message.", "file3");
+        let short_sha = git_output(&dir, &["rev-parse", "--short", "HEAD~1"]);
+        create_file(
+            &baseline_file,
+            contents
+                .replace(
+                    "\"MessageTicketNumber\"]",
+                    "\"MessageTicketNumber\", \"SubjectLength\"]",
+                )
+                .as_bytes(),
+        );
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.args(["--no-color", "--no-branch", "--baseline", "HEAD~3..HEAD"])
+            .current_dir(&dir)
+            .assert()
+            .failure()
+            .stdout(predicate::str::contains("Fixing bug three"))
+            .stdout(predicate::str::contains(
+                "3 commits inspected, 1 error detected",
+            ))
+            .stderr(predicate::str::contains(format!(
+                "Baseline entry does not match an issue anymore: {} SubjectLength",
+                short_sha
+            )));
+    }
+
+    #[test]
+    fn validate_commits_in_read_order() {
+        let context = crate::config::ValidationContext::default();
+        let commits = super::validate_commits(&context, None, |handle| {
+            for number in 0..50 {
+                let subject = format!("Fix bug {}", number);
+                handle(commit(subject.as_str(), "Some message."));
+            }
+            handle(commit("Merge tag 'v1.0.0'", ""));
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(commits.len(), 51);
+        for (number, commit) in commits.iter().take(50).enumerate() {
+            assert_eq!(commit.subject, format!("Fix bug {}", number));
+            assert!(!commit.checked_rules.is_empty());
+        }
+        assert!(commits[50].ignored);
+
+        let result = super::validate_commits(&context, None, |handle| {
+            handle(commit("Fix bug", "Some message."));
+            Err("Unable to read Git log output".to_string())
+        });
+        assert_eq!(result.unwrap_err(), "Unable to read Git log output");
+    }
+
+    #[test]
+    fn max_commits_option() {
+        compile_bin();
+        let dir = test_dir("max_commits_option");
+        create_test_repo(&dir);
+        create_commit_with_file(&dir, "fixing bug one", "Some message.", "file1");
+        create_commit_with_file(&dir, "fixing bug two", "Some message.", "file2");
+        create_commit_with_file(&dir, "fixing bug three", "Some message.", "file3");
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "--no-hints", "--no-branch", "HEAD~3..HEAD"])
+            .current_dir(&dir)
+            .assert()
+            .failure()
+            .code(1);
+        // The commits are printed in the order of the selection
+        let stdout = String::from_utf8_lossy(&assert.get_output().stdout).to_string();
+        let positions = ["three", "two", "one"]
+            .map(|word| stdout.find(&format!("fixing bug {}", word)).unwrap());
+        assert!(positions[0] < positions[1] && positions[1] < positions[2]);
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.args([
+            "--no-color",
+            "--no-hints",
+            "--no-branch",
+            "--max-commits",
+            "2",
+            "HEAD~3..HEAD",
+        ])
+        .current_dir(&dir)
+        .assert()
+        .failure()
+        .code(1)
+        .stdout(predicate::str::contains("fixing bug one").not())
+        .stdout(predicate::str::contains("2 commits inspected"))
+        .stderr(predicate::str::contains(
+            "Only the latest 2 of the selected commits are linted",
+        ));
+    }
+
+    #[test]
+    fn lint_commit_messages_with_encoding() {
+        compile_bin();
+        let dir = test_dir("commit_messages_with_encoding");
+        create_test_repo(&dir);
+        create_commit_with_file(
+            &dir,
+            "Add login form",
+            "Add a form to log in.\n\
+            ------------------------ COMMIT >! ------------------------\n\
+            The line above is part of the message.",
+            "file1",
+        );
+        git_output(&dir, &["config", "i18n.commitEncoding", "ISO-8859-1"]);
+        let message_file = dir.join("message.txt");
+        create_file(
+            &message_file,
+            b"Added caf\xE9 menu\n\nAdd a menu to the caf\xE9.\n",
+        );
+        git_output(&dir, &["add", "message.txt"]);
+        git_output(&dir, &["commit", "-F", "message.txt"]);
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.args(["--no-color", "--no-hints", "--no-branch", "HEAD~2..HEAD"])
+            .current_dir(&dir)
+            .assert()
+            .failure()
+            .code(1)
+            .stdout(predicate::str::contains("Added caf\u{e9} menu"))
+            .stdout(predicate::str::contains(
+                "2 commits inspected, 1 error detected",
+            ));
+    }
+
+    #[test]
+    fn lint_message_from_stdin() {
+        compile_bin();
+        let dir = test_dir("lint_message_from_stdin");
+        create_test_repo(&dir);
+        // Staged changes are not part of the given message
+        create_dummy_file(&dir.join("file1"));
+        stage_files(&dir);
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.args(["--no-color", "--stdin"])
+            .write_stdin(
+                "Add login form\n\nAdd a form to log in with an email address.\n\
+                # Please enter the commit message for your changes.\n",
+            )
+            .current_dir(&dir)
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("DiffPresence").not())
+            .stdout(predicate::str::contains(
+                "1 commit inspected, 0 errors detected",
+            ));
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.args(["--no-color", "--stdin", "--branch", "fix-123"])
+            .write_stdin("fixing bug")
+            .current_dir(&dir)
+            .assert()
+            .failure()
+            .code(1)
+            .stdout(predicate::str::contains("Error[SubjectMood]"))
+            .stdout(predicate::str::contains("Branch:1: fix-123\n"))
+            .stdout(predicate::str::contains("1 commit and branch inspected"));
+    }
+
+    #[test]
+    fn lint_message_argument() {
+        compile_bin();
+        let dir = test_dir("lint_message_argument");
+        create_test_repo(&dir);
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.args([
+            "--no-color",
+            "--message",
+            "Add login form",
+            "--message",
+            "Add a form to log in with an email address.",
+        ])
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "1 commit inspected, 0 errors detected",
+        ));
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.args([
+            "--no-color",
+            "--message",
+            "Add login form\n\nAdd a form to log in with an email address.",
+            "--files",
+        ])
+        .current_dir(&dir)
+        .assert()
+        .failure()
+        .code(2);
+
+        let changeset_dir = dir.join(".changesets");
+        std::fs::create_dir_all(&changeset_dir).expect("Could not create changeset dir");
+        create_dummy_file(&changeset_dir.join("some-changeset.md"));
+        stage_files(&changeset_dir);
+        create_commit(&changeset_dir, "Add changeset", "Add a changeset file");
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.args([
+            "--no-color",
+            "--message",
+            "Add login form\n\nAdd a form to log in with an email address.",
+            "--files",
+            "src/login.rs",
+        ])
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Hint[DiffChangeset]"));
+
+        // Outside a Git repository there are no changesets to look up
+        let outside_dir = test_dir("lint_message_input_outside_repository");
+        prepare_test_dir(&outside_dir);
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.args([
+            "--no-color",
+            "--message",
+            "Add login form\n\nAdd a form to log in with an email address.",
+            "--files",
+            "src/login.rs",
+        ])
+        .env(
+            "GIT_CEILING_DIRECTORIES",
+            std::fs::canonicalize(outside_dir.parent().unwrap()).unwrap(),
+        )
+        .current_dir(&outside_dir)
+        .assert()
+        .success()
+        .stderr("")
+        .stdout(predicate::str::contains(
+            "1 commit inspected, 0 errors detected",
+        ));
+    }
+
+    #[test]
+    fn lint_patch_files() {
+        compile_bin();
+        let dir = test_dir("lint_patch_files");
+        create_test_repo(&dir);
+        create_commit_with_file(
+            &dir,
+            "Add login form",
+            "Add a form to log in with an email address.",
+            "file1",
+        );
+        create_commit_with_file(&dir, "fixing bug", "Some message.", "file2");
+        git_output(&dir, &["format-patch", "--cover-letter", "-2"]);
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.args([
+            "--no-color",
+            "--no-hints",
+            "--patch",
+            "0000-cover-letter.patch",
+            "0001-Add-login-form.patch",
+            "0002-fixing-bug.patch",
+        ])
+        .current_dir(&dir)
+        .assert()
+        .failure()
+        .code(1)
+        .stdout(predicate::str::contains("Error[SubjectCapitalization]"))
+        .stdout(predicate::str::contains("[PATCH").not())
+        .stdout(predicate::str::contains("DiffPresence").not())
+        .stdout(predicate::str::contains("2 commits inspected"));
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.args(["--no-color", "--patch", "missing.patch"])
+            .current_dir(&dir)
+            .assert()
+            .failure()
+            .code(2)
+            .stderr(predicate::str::contains(
+                "Lintje encountered an error: Unable to read patch file: missing.patch",
+            ));
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.args(["--no-color", "--patch", "0000-cover-letter.patch"])
+            .current_dir(&dir)
+            .assert()
+            .failure()
+            .code(2)
+            .stderr(predicate::str::contains(
+                "Lintje encountered an error: No patches found in file: 0000-cover-letter.patch",
+            ));
+    }
+
+    #[test]
+    fn pre_push_new_branch() {
+        compile_bin();
+        let dir = test_dir("pre_push_new_branch");
+        create_test_repo(&dir);
+        create_remote_default_branch(&dir, "origin");
+        checkout_branch(&dir, "fix-123");
+        create_commit_with_file(&dir, "Add login form", "Some message.", "file1");
+        create_commit_with_file(&dir, "fixing bug", "Some message.", "file2");
+        let sha = git_output(&dir, &["rev-parse", "HEAD"]);
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "--no-hints", "--hook-pre-push", "origin"])
+            .write_stdin(format!(
+                "refs/heads/fix-123 {} refs/heads/fix-123 {}\n",
+                sha, "0000000000000000000000000000000000000000"
+            ))
+            .current_dir(&dir)
+            .assert()
+            .failure()
+            .code(1);
+        assert
+            .stdout(predicate::str::contains("Error[SubjectCapitalization]"))
+            .stdout(predicate::str::contains("Branch:1: fix-123\n"))
+            .stdout(predicate::str::contains("Initial commit").not())
+            .stdout(predicate::str::contains("2 commits and branch inspected"));
+    }
+
+    #[test]
+    fn pre_push_updated_branches() {
+        compile_bin();
+        let dir = test_dir("pre_push_updated_branches");
+        create_test_repo(&dir);
+        create_remote_default_branch(&dir, "origin");
+        checkout_branch(&dir, "feature-login-form");
+        create_commit_with_file(&dir, "fixing bug", "Some message.", "file1");
+        let remote_sha = git_output(&dir, &["rev-parse", "HEAD"]);
+        create_commit_with_file(&dir, "Add login form", "Some message.", "file2");
+        let sha = git_output(&dir, &["rev-parse", "HEAD"]);
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.args(["--no-color", "--no-hints", "--hook-pre-push", "origin"])
+            .write_stdin(format!(
+                "refs/heads/feature-login-form {sha} refs/heads/feature-login-form {remote_sha}\n\
+                refs/heads/feature-login-form {sha} refs/heads/feature-signup-form {remote_sha}\n\
+                (delete) {null_sha} refs/heads/fix-123 {remote_sha}\n",
+                null_sha = "0000000000000000000000000000000000000000"
+            ))
+            .current_dir(&dir)
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(
+                "1 commit and 2 branches inspected, 0 errors detected",
+            ));
+    }
+
+    #[test]
+    fn pre_push_ticket_number_per_branch() {
+        compile_bin();
+        let dir = test_dir("pre_push_ticket_number_per_branch");
+        create_test_repo(&dir);
+        create_file(
+            &dir.join("lintje.toml"),
+            b"[rules.MessageTicketNumber]\n\
+            required = true",
+        );
+        stage_files(&dir);
+        create_commit(&dir, "Add Lintje config", "");
+        create_remote_default_branch(&dir, "origin");
+        checkout_branch(&dir, "feature/ENG-123-login");
+        create_commit_with_file(&dir, "Add login form", "Part of ENG-123", "file1");
+        let login_sha = git_output(&dir, &["rev-parse", "HEAD"]);
+        git_output(
+            &dir,
+            &["checkout", "-b", "feature/ENG-124-signup", "HEAD~1"],
+        );
+        create_commit_with_file(&dir, "Add signup form", "Part of ENG-124", "file2");
+        let signup_sha = git_output(&dir, &["rev-parse", "HEAD"]);
+        let null_sha = "0000000000000000000000000000000000000000";
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.args(["--no-color", "--no-hints", "--hook-pre-push", "origin"])
+            .write_stdin(format!(
+                "refs/heads/feature/ENG-123-login {login_sha} refs/heads/feature/ENG-123-login {null_sha}\n\
+                refs/heads/feature/ENG-124-signup {signup_sha} refs/heads/feature/ENG-124-signup {null_sha}\n"
+            ))
+            .current_dir(&dir)
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(
+                "2 commits and 2 branches inspected, 0 errors detected",
+            ));
+
+        // The commits of the login branch pushed to another ticket's branch
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.args(["--no-color", "--no-hints", "--hook-pre-push", "origin"])
+            .write_stdin(format!(
+                "refs/heads/feature/ENG-123-login {login_sha} refs/heads/feature/ENG-123-login {null_sha}\n\
+                refs/heads/feature/ENG-123-login {login_sha} refs/heads/feature/ENG-125-profile {null_sha}\n"
+            ))
+            .current_dir(&dir)
+            .assert()
+            .failure()
+            .stdout(predicate::str::contains(
+                "The message body references ticket ENG-123 instead of the branch's ticket ENG-125",
+            ));
+    }
+
+    #[test]
+    fn pre_receive_in_bare_repository() {
+        compile_bin();
+        let dir = test_dir("pre_receive_in_bare_repository");
+        prepare_test_dir(&dir);
+        let work_dir = dir.join("work");
+        create_test_repo(&work_dir);
+        git_output(&dir, &["clone", "--bare", "work", "server.git"]);
+        let server_dir = dir.join("server.git");
+        let hook_file = server_dir.join("hooks/pre-receive");
+        let lintje = assert_cmd::cargo::cargo_bin("lintje");
+        create_file(
+            &hook_file,
+            format!(
+                "#!/bin/sh\n{} --no-color --no-hints --hook-pre-receive",
+                lintje.display()
+            )
+            .as_bytes(),
+        );
+        std::fs::set_permissions(&hook_file, std::fs::Permissions::from_mode(0o744)).unwrap();
+
+        checkout_branch(&work_dir, "fix-123");
+        create_commit_with_file(&work_dir, "fixing bug", "Some message.", "file1");
+        let output = Command::new("git")
+            .args(["push", "../server.git", "fix-123"])
+            .current_dir(&work_dir)
+            .output()
+            .unwrap();
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(
+            stderr.contains("remote: Branch fix-123:1: Error[BranchNameTicketNumber]"),
+            "{}",
+            stderr
+        );
+        assert!(
+            stderr.contains(": Error[SubjectCapitalization]"),
+            "{}",
+            stderr
+        );
+        assert!(
+            stderr.contains("remote: 1 commit and branch inspected"),
+            "{}",
+            stderr
+        );
+
+        checkout_branch(&work_dir, "feature-login-form");
+        git_output(
+            &work_dir,
+            &[
+                "commit",
+                "--amend",
+                "-m",
+                "Add login form validation",
+                "-m",
+                "Some message.",
+            ],
+        );
+        let output = Command::new("git")
+            .args(["push", "../server.git", "feature-login-form"])
+            .current_dir(&work_dir)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    #[test]
+    fn options_file_present() {
+        compile_bin();
+        let dir = test_dir("options_config_file_present");
+        create_test_repo(&dir);
+        create_commit_with_file(&dir, "Test commit", "I am a test commit.", "file");
+        let config_filename = Path::new("options.txt");
+        let config_file = dir.join(config_filename);
+        create_file(&config_file, b"--debug\n--no-branch");
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .arg("--no-color")
+            .env("LINTJE_OPTIONS_PATH", config_filename)
+            .current_dir(dir)
+            .assert()
+            .success();
+
+        // Prints a debug statement (options file)
+        // Does not validate branch (options file)
+        // But does not print the output with color (CLI flag)
+        assert
+            .stderr("") // Does not print any warnings to STDERR
+            .stdout(predicate::str::contains("[DEBUG]"))
+            .stdout(predicate::str::contains(
+                "1 commit inspected, 0 errors detected",
+            ));
+    }
+
+    #[test]
+    fn options_file_missing() {
+        compile_bin();
+        let dir = test_dir("options_config_file_missing");
+        create_test_repo(&dir);
+        create_commit_with_file(&dir, "Test commit", "I am a test commit.", "file");
+        let config_filename = Path::new("options.txt");
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .env("LINTJE_OPTIONS_PATH", config_filename)
+            .current_dir(dir)
+            .assert()
+            .success();
+
+        assert.stderr(predicate::str::contains(
+            "ERROR: Configured LINTJE_OPTIONS_PATH does not exist or is not a file. Path: \'options.txt\'",
+        ));
+    }
+
+    #[test]
+    fn config_file_present() {
+        compile_bin();
+        let dir = test_dir("config_file_present");
+        create_test_repo(&dir);
+        create_commit_with_file(&dir, "Fix a bug with a long subject line", "", "file");
+        create_file(
+            &dir.join("lintje.toml"),
+            b"branch = false\n\
+            \n\
+            [rules.SubjectLength]\n\
+            max_width = 20\n\
+            \n\
+            [rules.MessagePresence]\n\
+            enabled = false",
+        );
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "--no-hints"])
+            .current_dir(dir)
+            .assert()
+            .failure()
+            .code(1);
+        assert
+            .stdout(predicate::str::contains(
+                "Error[SubjectLength]: The subject of `34` characters wide is too long",
+            ))
+            .stdout(predicate::str::contains(
+                "Shorten the subject to a maximum width of 20 characters",
+            ))
+            .stdout(predicate::str::contains("Error[MessagePresence]").not())
+            .stdout(predicate::str::contains(
+                "1 commit inspected, 1 error detected",
+            ));
+    }
+
+    #[test]
+    fn config_file_invalid() {
+        compile_bin();
+        let dir = test_dir("config_file_invalid");
+        create_test_repo(&dir);
+        create_file(
+            &dir.join("lintje.toml"),
+            b"[rules.UnknownRule]\nenabled = false",
+        );
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd.current_dir(dir).assert().failure().code(2);
+        assert
+            .stderr(predicate::str::contains(
+                "ERROR: Error parsing config file: \"",
+            ))
+            .stderr(predicate::str::contains(
+                "Unknown rule in config file: UnknownRule",
+            ));
+    }
+
+    #[test]
+    fn pre_receive_ticket_number_per_branch() {
+        compile_bin();
+        let dir = test_dir("pre_receive_ticket_number_per_branch");
+        prepare_test_dir(&dir);
+        let work_dir = dir.join("work");
+        create_test_repo(&work_dir);
+        git_output(&dir, &["clone", "--bare", "work", "server.git"]);
+        let server_dir = dir.join("server.git");
+        create_file(
+            &server_dir.join("lintje.toml"),
+            b"[rules.MessageTicketNumber]\n\
+            required = true",
+        );
+        let hook_file = server_dir.join("hooks/pre-receive");
+        let lintje = assert_cmd::cargo::cargo_bin("lintje");
+        create_file(
+            &hook_file,
+            format!(
+                "#!/bin/sh\n{} --no-color --no-hints --hook-pre-receive",
+                lintje.display()
+            )
+            .as_bytes(),
+        );
+        std::fs::set_permissions(&hook_file, std::fs::Permissions::from_mode(0o744)).unwrap();
+
+        checkout_branch(&work_dir, "feature/ENG-123-login");
+        create_commit_with_file(&work_dir, "Add login form", "Part of ENG-123", "file1");
+        git_output(
+            &work_dir,
+            &["checkout", "-b", "feature/ENG-124-signup", "HEAD~1"],
+        );
+        create_commit_with_file(&work_dir, "Add signup form", "Part of ENG-124", "file2");
+        let output = Command::new("git")
+            .args([
+                "push",
+                "../server.git",
+                "feature/ENG-123-login",
+                "feature/ENG-124-signup",
+            ])
+            .current_dir(&work_dir)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        checkout_branch(&work_dir, "feature/ENG-125-profile");
+        create_commit_with_file(&work_dir, "Add profile page", "Part of ENG-123", "file3");
+        let output = Command::new("git")
+            .args(["push", "../server.git", "feature/ENG-125-profile"])
+            .current_dir(&work_dir)
+            .output()
+            .unwrap();
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(
+            stderr.contains(
+                "The message body references ticket ENG-123 instead of the branch's ticket ENG-125"
+            ),
+            "{}",
+            stderr
+        );
+    }
+
+    #[test]
+    fn required_ticket_number_of_branch() {
+        compile_bin();
+        let dir = test_dir("required_ticket_number_of_branch");
+        create_test_repo(&dir);
+        checkout_branch(&dir, "feature/ENG-123-login");
+        create_file(
+            &dir.join("lintje.toml"),
+            b"[rules.MessageTicketNumber]\n\
+            required = true",
+        );
+        create_commit_with_file(
+            &dir,
+            "Fix the login form",
+            "The form did not submit.\n\nFixes ENG-124",
+            "file",
+        );
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.args(["--no-color"])
+            .current_dir(&dir)
+            .assert()
+            .failure()
+            .code(1)
+            .stdout(predicate::str::contains(
+                "Error[MessageTicketNumber]: The message body references ticket ENG-124 \
+                instead of the branch's ticket ENG-123",
+            ));
+
+        create_dummy_file(&dir.join("other file"));
+        stage_files(&dir);
+        let commit_file = dir.join("commit_message_file");
+        create_file(
+            &commit_file,
+            b"Fix the login form\n\nThe form did not submit.\n",
+        );
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.args([
+            "--no-color",
+            "--fix",
+            "--hook-message-file=commit_message_file",
+        ])
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Fixed[MessageTicketNumber]: The message body does not contain a reference to the \
+            branch's ticket ENG-123",
+        ));
+        assert_eq!(
+            std::fs::read_to_string(commit_file).unwrap(),
+            "Fix the login form\n\nThe form did not submit.\n\nPart of ENG-123\n"
+        );
+
+        // The checked out branch is not used for commits that are not on that branch
+        checkout_branch(&dir, "other-branch");
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.args(["--no-color", "--no-branch", "feature/ENG-123-login"])
+            .current_dir(&dir)
+            .assert()
+            .success();
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.args([
+            "--no-color",
+            "--branch=feature/ENG-123-login",
+            "feature/ENG-123-login",
+        ])
+        .current_dir(&dir)
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "The message body references ticket ENG-124 instead of the branch's ticket ENG-123",
+        ));
+    }
+
+    #[test]
+    fn rule_selection_options() {
+        compile_bin();
+        let dir = test_dir("rule_selection_options");
+        create_test_repo(&dir);
+        create_commit_with_file(&dir, "fix: some bug", "", "file");
+        checkout_branch(&dir, "fix-123");
+        create_file(
+            &dir.join("lintje.toml"),
+            b"[rules.SubjectPrefix]\n\
+            severity = \"hint\"",
+        );
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args([
+                "--no-color",
+                "--disable",
+                "MessagePresence",
+                "--disable",
+                "BranchNameTicketNumber",
+                "--severity",
+                "BranchNameCliche=hint",
+            ])
+            .current_dir(dir)
+            .assert()
+            .success();
+        assert
+            .stdout(predicate::str::contains("Hint[SubjectPrefix]"))
+            .stdout(predicate::str::contains("Hint[BranchNameCliche]"))
+            .stdout(predicate::str::contains("Error[MessagePresence]").not())
+            .stdout(predicate::str::contains("BranchNameTicketNumber").not())
+            .stdout(predicate::str::contains(
+                "1 commit and branch inspected, 0 errors detected, 3 hints",
+            ));
+    }
+
+    #[test]
+    fn json_format() {
+        compile_bin();
+        let dir = test_dir("json_format");
+        create_test_repo(&dir);
+        create_commit_with_file(&dir, "Valid commit subject", "", "file");
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--format", "json", "--no-branch", "--no-hints"])
+            .current_dir(dir)
+            .assert()
+            .failure()
+            .code(1);
+        let output = &assert.get_output().stdout;
+        let json: serde_json::Value = serde_json::from_slice(output).unwrap();
+        let commit = &json["commits"][0];
+        assert_eq!(commit["subject"], "Valid commit subject");
+        assert_eq!(commit["issues"][0]["rule"], "MessagePresence");
+        assert_eq!(commit["issues"][0]["type"], "error");
+        assert_eq!(commit["issues"].as_array().unwrap().len(), 1);
+        assert_eq!(json["branches"], serde_json::json!([]));
+        assert_eq!(json["summary"]["commit_count"], 1);
+        assert_eq!(json["summary"]["error_count"], 1);
+    }
+
+    #[test]
+    fn reword_commits() {
+        compile_bin();
+        let dir = test_dir("reword_commits");
+        create_test_repo(&dir);
+        create_commit_with_file(&dir, "fixing bug", "Some message.", "file2");
+        create_commit_with_file(&dir, "Add other file", "Some message.", "file3");
+        create_file(&dir.join("file2"), b"Uncommitted change");
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.args(["--no-color", "reword", "HEAD~2..HEAD"])
+            .env("GIT_EDITOR", "sed -i -e '1s/.*/Fix the bug/'")
+            .env("GIT_COMMITTER_NAME", "Person B")
+            .env("GIT_COMMITTER_EMAIL", "b@example.com")
+            .current_dir(&dir)
+            .assert()
+            .success()
+            .stdout(
+                predicate::str::is_match("Reworded [a-z0-9]{7} -> [a-z0-9]{7}: Fix the bug\n$")
+                    .unwrap(),
+            );
+
+        let output = Command::new("git")
+            .args(["log", "--format=%s"])
+            .current_dir(&dir)
+            .output()
+            .unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout),
+            "Add other file\nFix the bug\nInitial commit\n"
+        );
+        // The rewritten commits are committed by the current committer, by the same author
+        let output = Command::new("git")
+            .args(["log", "--format=%an <%ae>|%cn <%ce>"])
+            .current_dir(&dir)
+            .output()
+            .unwrap();
+        let idents = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(|line| line.split_once('|').unwrap())
+            .map(|(author, committer)| (author.to_string(), committer.to_string()))
+            .collect::<Vec<(String, String)>>();
+        let (initial_author, initial_committer) = &idents[2];
+        for (author, committer) in &idents[..2] {
+            assert_eq!(author, initial_author);
+            assert_eq!(committer, "Person B <b@example.com>");
+        }
+        assert_ne!(initial_committer, "Person B <b@example.com>");
+        let output = Command::new("git")
+            .args(["status", "--short"])
+            .current_dir(&dir)
+            .output()
+            .unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stdout), " M file2\n");
+    }
+
+    #[test]
+    fn reword_commits_on_upstream_branch() {
+        compile_bin();
+        let dir = test_dir("reword_commits_on_upstream_branch");
+        create_test_repo(&dir);
+        create_commit_with_file(&dir, "fixing bug", "Some message.", "file2");
+        let output = Command::new("git")
+            .args(["branch", "--show-current"])
+            .current_dir(&dir)
+            .output()
+            .unwrap();
+        let branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        for (key, value) in [
+            (format!("branch.{}.remote", branch), ".".to_string()),
+            (
+                format!("branch.{}.merge", branch),
+                format!("refs/heads/{}", branch),
+            ),
+        ] {
+            Command::new("git")
+                .args(["config", &key, &value])
+                .current_dir(&dir)
+                .output()
+                .unwrap();
+        }
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.args(["--no-color", "reword", "HEAD~1..HEAD"])
+            .env("GIT_EDITOR", "sed -i -e '1s/.*/Fix the bug/'")
+            .current_dir(&dir)
+            .assert()
+            .failure()
+            .code(2)
+            .stderr(predicate::str::contains(
+                "it is already part of the upstream branch",
+            ));
+    }
+
+    #[test]
+    fn git_hook_install_commit_msg() {
+        compile_bin();
+        let dir = test_dir("git_hook_install_commit_msg");
+        create_test_repo(&dir);
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .current_dir(&dir)
+            .arg("--install-hook=commit-msg")
+            .assert()
+            .success();
+
+        assert.stdout(predicate::str::contains(
+            "Succesfully installed Lintje in hook: '.git/hooks/commit-msg'",
+        ));
+
+        let hook_file = dir.join(".git/hooks/commit-msg");
+        let contents = std::fs::read_to_string(&hook_file).expect("Can't read hook file");
+        assert_eq!(contents, "#!/bin/sh\n\nlintje --hook-message-file=$1\n");
+
+        let permissions = hook_file.metadata().unwrap().permissions();
+        assert_eq!(format!("{:#o}", permissions.mode()), "0o100744");
+    }
+
+    #[test]
+    fn git_hook_install_post_commit() {
+        compile_bin();
+        let dir = test_dir("git_hook_install_post_commit");
+        create_test_repo(&dir);
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .current_dir(&dir)
+            .arg("--install-hook=post-commit")
+            .assert()
+            .success();
+
+        assert.stdout(predicate::str::contains(
+            "Succesfully installed Lintje in hook: '.git/hooks/post-commit'",
+        ));
+
+        let hook_file = dir.join(".git/hooks/post-commit");
+        let contents = std::fs::read_to_string(hook_file).expect("Can't read hook file");
+        assert_eq!(contents, "#!/bin/sh\n\nlintje\n");
+    }
+
+    #[test]
+    fn git_hook_install_pre_push() {
+        compile_bin();
+        let dir = test_dir("git_hook_install_pre_push");
+        create_test_repo(&dir);
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .current_dir(&dir)
+            .arg("--install-hook=pre-push")
+            .assert()
+            .success();
+
+        assert.stdout(predicate::str::contains(
+            "Succesfully installed Lintje in hook: '.git/hooks/pre-push'",
+        ));
+
+        let hook_file = dir.join(".git/hooks/pre-push");
+        let contents = std::fs::read_to_string(hook_file).expect("Can't read hook file");
+        assert_eq!(contents, "#!/bin/sh\n\nlintje --hook-pre-push \"$1\"\n");
+    }
+
+    #[test]
+    fn git_hook_install_with_file_exists() {
+        compile_bin();
+        let dir = test_dir("git_hook_install_with_file_exists");
         create_test_repo(&dir);
 
         // Write some content to the file beforehand
@@ -1168,7 +2733,241 @@ mod tests {
         ));
 
         let contents = std::fs::read_to_string(hook_file).expect("Can't read hook file");
-        assert_eq!(contents, "Other content\n\nlintje");
+        assert_eq!(contents, "Other content\n\nlintje\n");
+    }
+
+    #[test]
+    fn git_hook_install_twice() {
+        compile_bin();
+        let dir = test_dir("git_hook_install_twice");
+        create_test_repo(&dir);
+
+        for _ in 0..2 {
+            let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+            cmd.current_dir(&dir)
+                .arg("--install-hook=commit-msg")
+                .assert()
+                .success();
+        }
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.current_dir(&dir)
+            .arg("--install-hook=commit-msg")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(
+                "Lintje is already installed in hook: '.git/hooks/commit-msg'",
+            ));
+
+        let contents = std::fs::read_to_string(dir.join(".git/hooks/commit-msg"))
+            .expect("Can't read hook file");
+        assert_eq!(contents, "#!/bin/sh\n\nlintje --hook-message-file=$1\n");
+    }
+
+    #[test]
+    fn git_hook_install_with_hooks_path() {
+        compile_bin();
+        let dir = test_dir("git_hook_install_with_hooks_path");
+        create_test_repo(&dir);
+        git_output(&dir, &["config", "core.hooksPath", ".githooks"]);
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.current_dir(&dir)
+            .arg("--install-hook=commit-msg")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(
+                "Succesfully installed Lintje in hook: '.githooks/commit-msg'",
+            ));
+        assert!(dir.join(".githooks/commit-msg").exists());
+        assert!(!dir.join(".git/hooks/commit-msg").exists());
+    }
+
+    #[test]
+    fn git_hook_install_in_worktree() {
+        compile_bin();
+        let dir = test_dir("git_hook_install_in_worktree");
+        prepare_test_dir(&dir);
+        let repo_dir = dir.join("repo");
+        create_test_repo(&repo_dir);
+        git_output(
+            &repo_dir,
+            &["worktree", "add", "../worktree", "-b", "feature"],
+        );
+        let worktree_dir = dir.join("worktree");
+        assert!(worktree_dir.join(".git").is_file());
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.current_dir(&worktree_dir)
+            .arg("--install-hook=commit-msg")
+            .assert()
+            .success();
+        // Worktrees use the hooks of the main repository
+        let contents = std::fs::read_to_string(repo_dir.join(".git/hooks/commit-msg"))
+            .expect("Can't read hook file");
+        assert_eq!(contents, "#!/bin/sh\n\nlintje --hook-message-file=$1\n");
+    }
+
+    #[test]
+    fn git_hook_uninstall() {
+        compile_bin();
+        let dir = test_dir("git_hook_uninstall");
+        create_test_repo(&dir);
+        let hook_file = dir.join(".git/hooks/post-commit");
+        create_file(&hook_file, b"#!/bin/sh\nnpm test\n\nlintje\n");
+        let commit_msg_file = dir.join(".git/hooks/commit-msg");
+        create_file(
+            &commit_msg_file,
+            b"#!/bin/sh\n\nlintje --hook-message-file=$1\n",
+        );
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.current_dir(&dir)
+            .arg("--uninstall-hook=post-commit")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(
+                "Succesfully uninstalled Lintje from hook: '.git/hooks/post-commit'",
+            ));
+        let contents = std::fs::read_to_string(&hook_file).expect("Can't read hook file");
+        assert_eq!(contents, "#!/bin/sh\nnpm test\n");
+
+        // The hook file is removed when only the shebang remains
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.current_dir(&dir)
+            .arg("--uninstall-hook=commit-msg")
+            .assert()
+            .success();
+        assert!(!commit_msg_file.exists());
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.current_dir(&dir)
+            .arg("--uninstall-hook=commit-msg")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(
+                "Lintje is not installed in hook: '.git/hooks/commit-msg'",
+            ));
+    }
+
+    #[test]
+    fn git_hook_status() {
+        compile_bin();
+        let dir = test_dir("git_hook_status");
+        create_test_repo(&dir);
+        create_file(
+            &dir.join(".git/hooks/post-commit"),
+            b"#!/bin/sh\n\nlintje\n",
+        );
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.current_dir(&dir)
+            .arg("--install-hook=commit-msg")
+            .assert()
+            .success();
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.current_dir(&dir)
+            .arg("--hook-status")
+            .assert()
+            .success()
+            .stdout(
+                "prepare-commit-msg: not installed ('.git/hooks/prepare-commit-msg')\n\
+                commit-msg: installed ('.git/hooks/commit-msg')\n\
+                post-commit: installed, but the hook file is not executable ('.git/hooks/post-commit')\n\
+                pre-push: not installed ('.git/hooks/pre-push')\n\
+                pre-receive: not installed ('.git/hooks/pre-receive')\n",
+            );
+    }
+
+    #[test]
+    fn prepare_commit_message_template() {
+        compile_bin();
+        let dir = test_dir("prepare_commit_message_template");
+        create_test_repo(&dir);
+        checkout_branch(&dir, "feature/ENG-123-login-form");
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.current_dir(&dir)
+            .arg("--install-hook=prepare-commit-msg")
+            .assert()
+            .success();
+
+        // Commit with the template as the message, like saving it with a subject added and the
+        // ticket reference uncommented
+        create_dummy_file(&dir.join("file"));
+        stage_files(&dir);
+        let output = Command::new("git")
+            .args(["commit", "--cleanup=strip"])
+            .env(
+                "GIT_EDITOR",
+                "sed -i -e '1s/.*/Add login form/' -e 's/^# Part of/Part of/'",
+            )
+            .env("PATH", path_with_lintje())
+            .current_dir(&dir)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let message = git_output(&dir, &["log", "-1", "--format=%B"]);
+        assert_eq!(message, "Add login form\n\nPart of ENG-123");
+
+        // Messages given with `-m` are left as is
+        create_dummy_file(&dir.join("file2"));
+        stage_files(&dir);
+        Command::new("git")
+            .args(["commit", "-m", "Add signup form"])
+            .env("PATH", path_with_lintje())
+            .current_dir(&dir)
+            .output()
+            .unwrap();
+        let message = git_output(&dir, &["log", "-1", "--format=%B"]);
+        assert_eq!(message, "Add signup form");
+    }
+
+    #[test]
+    fn prepare_commit_message_template_unedited() {
+        compile_bin();
+        let dir = test_dir("prepare_commit_message_template_unedited");
+        create_test_repo(&dir);
+        checkout_branch(&dir, "ENG-123-login");
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.current_dir(&dir)
+            .arg("--install-hook=prepare-commit-msg")
+            .assert()
+            .success();
+        create_file(
+            &dir.join("lintje.toml"),
+            b"[template]\ntrailers = [\"Reviewed-by\"]",
+        );
+
+        // Saving the template without changes aborts the commit
+        let commit_count = git_output(&dir, &["rev-list", "--all", "--count"]);
+        create_dummy_file(&dir.join("file"));
+        stage_files(&dir);
+        let output = Command::new("git")
+            .args(["commit"])
+            .env("GIT_EDITOR", "true")
+            .env("PATH", path_with_lintje())
+            .current_dir(&dir)
+            .output()
+            .unwrap();
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains("empty commit message"));
+        assert_eq!(
+            git_output(&dir, &["rev-list", "--all", "--count"]),
+            commit_count
+        );
+    }
+
+    // The PATH with the directory of the compiled Lintje executable, so Git hooks can call it
+    fn path_with_lintje() -> String {
+        let lintje = assert_cmd::cargo::cargo_bin("lintje");
+        format!(
+            "{}:{}",
+            lintje.parent().unwrap().display(),
+            std::env::var("PATH").unwrap_or_default()
+        )
     }
 
     #[test]
@@ -0,0 +1,339 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use termcolor::Buffer;
+
+use crate::commit::Commit;
+use crate::config::ValidationContext;
+use crate::formatter::formatted_commit_issue;
+use crate::git::rewrite::{
+    commits_since, committer_ident, editor, git_path, head_sha, is_ancestor, read_commit,
+    update_head, upstream_branch, write_commit,
+};
+use crate::git::{
+    comment_char, commit_file_lines, fetch_and_parse_commits, is_commit_ignored,
+    parse_commit_message_file, SCISSORS,
+};
+use crate::issue::IssueType;
+
+const REWORD_FILE: &str = "LINTJE_REWORD_EDITMSG";
+
+/// Open the commit message of every commit with errors in the selection in the editor, and
+/// rewrite the history of the current branch with the new commit messages.
+///
+/// Only the commit messages change. The tree of every commit, the index and the working tree stay
+/// the same.
+pub fn reword_commits(
+    selection: &str,
+    context: &ValidationContext,
+    hints: bool,
+) -> Result<(), String> {
+    let mut commits = fetch_and_parse_commits(&Some(selection.to_string()), None)?;
+    for commit in commits.iter_mut() {
+        commit.ignored = is_commit_ignored(commit);
+        if !commit.ignored {
+            commit.validate(context);
+        }
+    }
+    // Reword the oldest commit first, like an interactive rebase
+    commits.reverse();
+    let invalid_commits = commits
+        .iter()
+        .filter(|commit| !commit.ignored && has_errors(commit))
+        .collect::<Vec<&Commit>>();
+    let oldest_commit = match invalid_commits.first() {
+        Some(commit) => commit,
+        None => {
+            println!("No commits with errors found. There is nothing to reword.");
+            return Ok(());
+        }
+    };
+    check_commits(&commits)?;
+
+    let oldest_sha = commit_sha(oldest_commit);
+    let oldest_object = read_commit(oldest_sha)?;
+    let history = commits_since(oldest_sha, !oldest_object.parents.is_empty())?;
+    let mut objects = vec![];
+    for sha in &history {
+        let object = read_commit(sha)?;
+        if object.parents.len() > 1 {
+            return Err(format!(
+                "Unable to reword the selected commits: merge commit {} is part of the history \
+                since the oldest commit to reword. Rebase the branch manually instead.\n",
+                sha
+            ));
+        }
+        objects.push(object);
+    }
+
+    let file = git_path(REWORD_FILE)?;
+    let mut messages = HashMap::new();
+    for commit in invalid_commits {
+        let sha = commit_sha(commit);
+        let object = objects
+            .iter()
+            .find(|object| object.sha == sha)
+            .ok_or_else(|| {
+                format!(
+                    "Unable to reword commit {}: it is not part of the current branch.\n",
+                    sha
+                )
+            })?;
+        if object.has_custom_encoding() {
+            return Err(format!(
+                "Unable to reword commit {}: its commit message is not encoded in UTF-8.\n",
+                sha
+            ));
+        }
+        let message = edit_message(&file, commit, &object.message, context, hints)?;
+        messages.insert(sha.to_string(), message);
+    }
+    let _ = fs::remove_file(&file);
+
+    // Write every commit since the oldest reworded commit again, so they point to the new parent
+    let old_head = head_sha()?;
+    let committer = committer_ident()?;
+    let mut rewritten = HashMap::<String, String>::new();
+    let mut new_head = old_head.to_string();
+    let mut reworded = vec![];
+    for object in &objects {
+        let parents = object
+            .parents
+            .iter()
+            .map(|parent| rewritten.get(parent).unwrap_or(parent).to_string())
+            .collect::<Vec<String>>();
+        let message = messages.get(&object.sha).unwrap_or(&object.message);
+        let sha = write_commit(object, &parents, &committer, message)?;
+        if let Some(subject) = messages.get(&object.sha).and_then(|m| m.lines().next()) {
+            reworded.push(format!(
+                "Reworded {} -> {}: {}",
+                &object.sha[..7],
+                &sha[..7],
+                subject
+            ));
+        }
+        new_head = sha.to_string();
+        rewritten.insert(object.sha.to_string(), sha);
+    }
+    update_head(&new_head, &old_head)?;
+    for line in reworded {
+        println!("{}", line);
+    }
+    Ok(())
+}
+
+fn has_errors(commit: &Commit) -> bool {
+    commit
+        .issues
+        .iter()
+        .any(|issue| issue.r#type == IssueType::Error)
+}
+
+fn commit_sha(commit: &Commit) -> &str {
+    commit.long_sha.as_deref().unwrap_or_default()
+}
+
+// Only commits in the history of the current branch, that have not been pushed to its upstream
+// branch yet, can be reworded.
+fn check_commits(commits: &[Commit]) -> Result<(), String> {
+    let upstream = upstream_branch();
+    for commit in commits {
+        let sha = commit_sha(commit);
+        if !is_ancestor(sha, "HEAD") {
+            return Err(format!(
+                "Unable to reword commit {}: it is not part of the current branch.\n",
+                sha
+            ));
+        }
+        if let Some(upstream) = &upstream {
+            if is_ancestor(sha, upstream) {
+                return Err(format!(
+                    "Unable to reword commit {}: it is already part of the upstream branch `{}`. \
+                    Select only commits that have not been pushed yet.\n",
+                    sha, upstream
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+// Open the commit message in the editor until the errors are fixed, or the commit message is
+// saved without changes. Returns the new commit message.
+fn edit_message(
+    file: &Path,
+    commit: &Commit,
+    message: &str,
+    context: &ValidationContext,
+    hints: bool,
+) -> Result<String, String> {
+    let comment_char = comment_char();
+    let mut message = message.trim_end().to_string();
+    let mut linted_commit = None;
+    loop {
+        let commit = linted_commit.as_ref().unwrap_or(commit);
+        let contents = format!(
+            "{}\n\n{}",
+            message,
+            issues_comment(commit, &comment_char, hints)
+        );
+        write_file(file, &contents)?;
+        open_editor(file)?;
+        let contents = fs::read_to_string(file).map_err(|e| {
+            format!(
+                "Unable to read commit message file contents: {}\n{}",
+                file.to_string_lossy(),
+                e
+            )
+        })?;
+        let new_message = cleaned_message(&contents);
+        if new_message.is_empty() {
+            let _ = fs::remove_file(file);
+            return Err(format!(
+                "Aborting reword due to empty commit message for commit {}. \
+                No commits were changed.\n",
+                commit_sha(commit)
+            ));
+        }
+        let mut new_commit = parse_commit_message_file(
+            &contents,
+            commit.long_sha.clone(),
+            commit.email.clone(),
+            commit.file_changes.clone(),
+        );
+        new_commit.validate(context);
+        // Keep the message as is when it was saved without changes, even if it has errors
+        if new_message.trim_end() == message || !has_errors(&new_commit) {
+            return Ok(format!("{}\n", new_message));
+        }
+        message = new_message;
+        linted_commit = Some(new_commit);
+    }
+}
+
+// The commit message without comments and trailing whitespace
+fn cleaned_message(contents: &str) -> String {
+    commit_file_lines(contents)
+        .into_iter()
+        .map(|(_, line)| line)
+        .collect::<Vec<String>>()
+        .join("\n")
+        .trim_end()
+        .to_string()
+}
+
+fn issues_comment(commit: &Commit, comment_char: &str, hints: bool) -> String {
+    let mut out = Buffer::no_color();
+    for issue in &commit.issues {
+        if issue.r#type == IssueType::Hint && !hints {
+            continue;
+        }
+        if let Err(e) = formatted_commit_issue(&mut out, commit, issue) {
+            error!("Unable to format issue: {:?}", e);
+        }
+    }
+    let issues = String::from_utf8_lossy(out.as_slice()).to_string();
+    let mut lines = vec![
+        SCISSORS.to_string(),
+        "Do not modify or remove the line above.".to_string(),
+        "Everything below it will be ignored.".to_string(),
+        "".to_string(),
+        format!(
+            "Lintje found the following issues in commit {}:",
+            commit.short_sha.as_deref().unwrap_or_default()
+        ),
+        "".to_string(),
+    ];
+    lines.extend(issues.trim_end().lines().map(|line| line.to_string()));
+    lines.push("".to_string());
+    lines.push(
+        "Save the message without changes to keep it as is, or remove the message to abort \
+        rewording."
+            .to_string(),
+    );
+    lines
+        .iter()
+        .map(|line| {
+            if line.is_empty() {
+                comment_char.to_string()
+            } else {
+                format!("{} {}", comment_char, line)
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+        + "\n"
+}
+
+fn write_file(file: &Path, contents: &str) -> Result<(), String> {
+    fs::write(file, contents).map_err(|e| {
+        format!(
+            "Unable to write commit message file: {}\n{}",
+            file.to_string_lossy(),
+            e
+        )
+    })
+}
+
+// Open the file in the editor configured for Git, the same way Git starts the editor.
+fn open_editor(file: &Path) -> Result<(), String> {
+    let editor = editor()?;
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(format!("{} \"$@\"", editor))
+        .arg(&editor)
+        .arg(file)
+        .status()
+        .map_err(|e| format!("Unable to start the editor: {}\n{}", editor, e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "The editor exited with an error: {}\nNo commits were changed.\n",
+            editor
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{cleaned_message, issues_comment};
+    use crate::config::ValidationContext;
+    use crate::test::*;
+
+    #[test]
+    fn cleaned_commit_message() {
+        assert_eq!(
+            cleaned_message(
+                "Fix bug  \n\nMessage body.\n\n# ------------------------ >8 ------------------------\n\
+                Not a comment\n"
+            ),
+            "Fix bug\n\nMessage body."
+        );
+    }
+
+    #[test]
+    fn issues_as_comment() {
+        let mut commit = commit("Fixing the login form", "\nMessage body.");
+        commit.validate(&ValidationContext::default());
+        let comment = issues_comment(&commit, "#", false);
+
+        assert!(comment.starts_with(
+            "# ------------------------ >8 ------------------------\n\
+            # Do not modify or remove the line above.\n\
+            # Everything below it will be ignored.\n\
+            #\n\
+            # Lintje found the following issues in commit aaaaaaa:\n\
+            #\n\
+            # Error[SubjectMood]: The subject does not use the imperative grammatical mood\n\
+            #   aaaaaaa:1:1: Fixing the login form\n"
+        ));
+        assert!(!comment.contains("MessageTicketNumber"));
+        assert!(comment.ends_with(
+            "#\n\
+            # Save the message without changes to keep it as is, or remove the message to abort \
+            rewording.\n"
+        ));
+    }
+}
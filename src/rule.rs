@@ -1,13 +1,16 @@
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 use crate::branch::Branch;
 use crate::commit::Commit;
+use crate::config::file::Config;
+use crate::config::ValidationContext;
 use crate::issue::Issue;
 use crate::rules::*;
 
 const REDIRECTOR_DOMAIN: &str = "https://r.lintje.dev/";
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
 pub enum Rule {
     MergeCommit,
     RebaseCommit,
@@ -32,6 +35,8 @@ pub enum Rule {
     BranchNameLength,
     BranchNamePunctuation,
     BranchNameCliche,
+    BranchNameFormat,
+    ConventionalCommit,
 }
 
 impl fmt::Display for Rule {
@@ -60,44 +65,64 @@ impl fmt::Display for Rule {
             Rule::BranchNameLength => "BranchNameLength",
             Rule::BranchNamePunctuation => "BranchNamePunctuation",
             Rule::BranchNameCliche => "BranchNameCliche",
+            Rule::BranchNameFormat => "BranchNameFormat",
+            Rule::ConventionalCommit => "ConventionalCommit",
         };
         write!(f, "{}", label)
     }
 }
 
 impl Rule {
-    pub fn validate_commit(&self, commit: &Commit) -> Option<Vec<Issue>> {
+    pub fn validate_commit(
+        &self,
+        commit: &Commit,
+        context: &ValidationContext,
+    ) -> Option<Vec<Issue>> {
+        let config = &context.config;
         let rule_validator: Box<dyn RuleValidator<Commit>> = match self {
             Rule::MergeCommit => Box::new(MergeCommit::new()),
             Rule::RebaseCommit => Box::new(RebaseCommit::new()),
-            Rule::SubjectLength => Box::new(SubjectLength::new()),
-            Rule::SubjectMood => Box::new(SubjectMood::new()),
+            Rule::SubjectLength => Box::new(SubjectLength::new(&config.subject_length)),
+            Rule::SubjectMood => Box::new(SubjectMood::new(&config.subject_mood)),
             Rule::SubjectWhitespace => Box::new(SubjectWhitespace::new()),
             Rule::SubjectCapitalization => Box::new(SubjectCapitalization::new()),
             Rule::SubjectPunctuation => Box::new(SubjectPunctuation::new()),
-            Rule::SubjectTicketNumber => Box::new(SubjectTicketNumber::new()),
+            Rule::SubjectTicketNumber => Box::new(SubjectTicketNumber::new(&config.tickets)),
             Rule::SubjectPrefix => Box::new(SubjectPrefix::new()),
             Rule::SubjectBuildTag => Box::new(SubjectBuildTag::new()),
             Rule::SubjectCliche => Box::new(SubjectCliche::new()),
-            Rule::MessagePresence => Box::new(MessagePresence::new()),
+            Rule::MessagePresence => Box::new(MessagePresence::new(
+                &config.message_presence,
+                &config.tickets,
+            )),
             Rule::MessageEmptyFirstLine => Box::new(MessageEmptyFirstLine::new()),
-            Rule::MessageLineLength => Box::new(MessageLineLength::new()),
+            Rule::MessageLineLength => {
+                Box::new(MessageLineLength::new(&config.message_line_length))
+            }
             Rule::MessageSkipBuildTag => Box::new(MessageSkipBuildTag::new()),
-            Rule::MessageTicketNumber => Box::new(MessageTicketNumber::new()),
+            Rule::MessageTicketNumber => Box::new(MessageTicketNumber::new(
+                &config.message_ticket_number,
+                &config.tickets,
+                context.branch.as_deref(),
+            )),
             Rule::MessageTrailerLine => Box::new(MessageTrailerLine::new()),
             Rule::DiffChangeset => Box::new(DiffChangeset::new()),
             Rule::DiffPresence => Box::new(DiffPresence::new()),
+            Rule::ConventionalCommit => {
+                Box::new(ConventionalCommit::new(&config.conventional_commit))
+            }
             Rule::BranchNameTicketNumber
             | Rule::BranchNameLength
             | Rule::BranchNamePunctuation
-            | Rule::BranchNameCliche => {
+            | Rule::BranchNameCliche
+            | Rule::BranchNameFormat => {
                 panic!("Unknown rule for commit validation: {}", self)
             }
         };
         rule_validator.validate(commit)
     }
 
-    pub fn validate_branch(&self, branch: &Branch) -> Option<Vec<Issue>> {
+    pub fn validate_branch(&self, branch: &Branch, config: &Config) -> Option<Vec<Issue>> {
         let rule_validator: Box<dyn RuleValidator<Branch>> = match self {
             Rule::MergeCommit
             | Rule::RebaseCommit
@@ -117,11 +142,16 @@ impl Rule {
             | Rule::MessageTicketNumber
             | Rule::MessageTrailerLine
             | Rule::DiffChangeset
-            | Rule::DiffPresence => panic!("Unknown rule for branch validation: {}", self),
+            | Rule::DiffPresence
+            | Rule::ConventionalCommit => panic!("Unknown rule for branch validation: {}", self),
             Rule::BranchNameLength => Box::new(BranchNameLength::new()),
-            Rule::BranchNameTicketNumber => Box::new(BranchNameTicketNumber::new()),
+            Rule::BranchNameTicketNumber => Box::new(BranchNameTicketNumber::new(&config.tickets)),
             Rule::BranchNamePunctuation => Box::new(BranchNamePunctuation::new()),
             Rule::BranchNameCliche => Box::new(BranchNameCliche::new()),
+            Rule::BranchNameFormat => Box::new(BranchNameFormat::new(
+                &config.branch_name_format,
+                &config.tickets,
+            )),
         };
         rule_validator.validate(branch)
     }
@@ -156,6 +186,12 @@ pub fn rule_by_name(name: &str) -> Option<Rule> {
         "MessageTrailerLine" => Some(Rule::MessageTrailerLine),
         "DiffChangeset" => Some(Rule::DiffChangeset),
         "DiffPresence" => Some(Rule::DiffPresence),
+        "BranchNameTicketNumber" => Some(Rule::BranchNameTicketNumber),
+        "BranchNameLength" => Some(Rule::BranchNameLength),
+        "BranchNamePunctuation" => Some(Rule::BranchNamePunctuation),
+        "BranchNameCliche" => Some(Rule::BranchNameCliche),
+        "BranchNameFormat" => Some(Rule::BranchNameFormat),
+        "ConventionalCommit" => Some(Rule::ConventionalCommit),
         _ => None,
     }
 }
@@ -1,9 +1,15 @@
-use regex::Regex;
+use regex::{Captures, Match, Regex};
+
+use crate::commit::Commit;
+use crate::config::file::TicketSyntax;
+use crate::issue::Edit;
 
 pub mod branch_name_cliche;
+pub mod branch_name_format;
 pub mod branch_name_length;
 pub mod branch_name_punctuation;
 pub mod branch_name_ticket_number;
+pub mod conventional_commit;
 pub mod diff_changeset;
 pub mod diff_presence;
 pub mod merge_commit;
@@ -25,9 +31,11 @@ pub mod subject_ticket_number;
 pub mod subject_whitespace;
 
 pub use branch_name_cliche::BranchNameCliche;
+pub use branch_name_format::BranchNameFormat;
 pub use branch_name_length::BranchNameLength;
 pub use branch_name_punctuation::BranchNamePunctuation;
 pub use branch_name_ticket_number::BranchNameTicketNumber;
+pub use conventional_commit::ConventionalCommit;
 pub use diff_changeset::DiffChangeset;
 pub use diff_presence::DiffPresence;
 pub use merge_commit::MergeCommit;
@@ -48,15 +56,18 @@ pub use subject_punctuation::SubjectPunctuation;
 pub use subject_ticket_number::SubjectTicketNumber;
 pub use subject_whitespace::SubjectWhitespace;
 
-pub static ISSUE_NUMBER_REFERENCE: &str = r"
-    (
-        https?://[^\s]+/| # Match entire URL
-        [\w\-_\.]+/[\w\-_\.]+[\#!]| # Repo shorthand format: org/repo#123 or org/repo!123
-        [\#!]| # Only an issue or PR symbol
-        (?-i:[A-Z]{2,}-) # Jira project keys, at least 2 uppercase character, e.g. AB-123
-    )
-    \d+ # Ends in an issue/PR number
-";
+/// The built-in ticket reference syntaxes, in the order they are matched. Every syntax ends in a
+/// ticket, issue or PR number.
+pub const BUILT_IN_TICKET_SYNTAXES: [(&str, &str); 4] = [
+    // Entire URL ending in a number: https://example.com/org/repo/issues/123
+    ("url", r"(?i:https?://[^\s]+/)\d+"),
+    // Repo shorthand format: org/repo#123 or org/repo!123
+    ("repository", r"[\w\-_\.]+/[\w\-_\.]+[#!]\d+"),
+    // Only an issue or PR symbol: #123 or !123
+    ("number", r"[#!]\d+"),
+    // Jira project keys, at least 2 uppercase characters: AB-123
+    ("jira", r"(?P<project>[A-Z]{2,})-\d+"),
+];
 
 // Match all GitHub and GitLab keywords
 pub static FIX_KEY_WORD_REFERENCE: &str = r"
@@ -64,18 +75,148 @@ pub static FIX_KEY_WORD_REFERENCE: &str = r"
     :? # Optional colon
 ";
 
+// Match "Part of #123" and friends
+pub static LINK_KEY_WORD_REFERENCE: &str = r"
+    (part\sof|part\sof\s(issue|epic|project)|related) # Keywords
+    :? # Optional colon
+";
+
 lazy_static! {
-    pub static ref CONTAINS_FIX_TICKET: Regex = Regex::new(&format!(
-        r"(?xi)
-        {FIX_KEY_WORD_REFERENCE}\s+{ISSUE_NUMBER_REFERENCE}
-    "
-    ))
-    .unwrap();
-    pub static ref CONTAINS_FIX_TICKET_OR_TICKET_REFERENCE: Regex = Regex::new(&format!(
-        r"(?xi)
-        (?<match>(?<keyword>{FIX_KEY_WORD_REFERENCE}\s+)?{ISSUE_NUMBER_REFERENCE})"
-    ))
-    .unwrap();
     pub static ref CO_AUTHOR_REFERENCE: Regex =
         Regex::new(r"(?im)^co-authored-by: [\w\s\-]+\s+<[^\s]+[@]+[^\s]+>").unwrap();
 }
+
+/// Finds ticket references in commit messages and branch names, using the configured ticket
+/// syntaxes. Used by all rules that check for ticket references.
+#[derive(Clone, Debug)]
+pub struct TicketReferences {
+    // The allowed project keys per syntax, by the name of the syntax's project capture group
+    projects: Vec<(String, Vec<String>)>,
+    reference: Regex,
+    fix_ticket: Regex,
+    fix_ticket_or_reference: Regex,
+    link_ticket: Regex,
+}
+
+impl TicketReferences {
+    pub fn new(syntaxes: &[TicketSyntax]) -> Result<Self, String> {
+        let mut projects = vec![];
+        let mut patterns = vec![];
+        for (index, syntax) in syntaxes.iter().enumerate() {
+            // Every syntax can have a project group. Give them unique names so the patterns can be
+            // combined into one expression.
+            let group = format!("project_{}", index);
+            let pattern = syntax
+                .pattern
+                .as_str()
+                .replace("(?P<project>", &format!("(?P<{}>", group))
+                .replace("(?<project>", &format!("(?P<{}>", group));
+            if !syntax.projects.is_empty() {
+                projects.push((group, syntax.projects.clone()));
+            }
+            // Configured patterns are matched case sensitive and without verbose mode, even
+            // though they are combined with the verbose and case insensitive expressions below.
+            patterns.push(format!("(?-ix:{})", pattern));
+        }
+        let reference = if patterns.is_empty() {
+            // Match nothing when no syntaxes are configured
+            r"[^\s\S]".to_string()
+        } else {
+            format!("(?:{})", patterns.join("|"))
+        };
+        let build = |pattern: String| {
+            Regex::new(&pattern).map_err(|e| format!("Invalid ticket syntax: {}", e))
+        };
+        Ok(Self {
+            projects,
+            reference: build(format!("(?x){reference}"))?,
+            fix_ticket: build(format!(
+                r"(?xi)
+                {FIX_KEY_WORD_REFERENCE}\s+(?P<ticket>{reference})"
+            ))?,
+            fix_ticket_or_reference: build(format!(
+                r"(?xi)
+                (?P<match>(?P<keyword>{FIX_KEY_WORD_REFERENCE}\s+)?{reference})"
+            ))?,
+            link_ticket: build(format!(
+                r"(?xi)
+                {LINK_KEY_WORD_REFERENCE}\s+(?P<ticket>{reference})"
+            ))?,
+        })
+    }
+
+    /// Returns the first ticket reference in the text.
+    pub fn find_reference<'t>(&self, text: &'t str) -> Option<Match<'t>> {
+        self.find(&self.reference, text)
+    }
+
+    /// Returns the first ticket reference with a fix keyword, like "Fixes #123", in the text.
+    pub fn find_fix_ticket<'t>(&self, text: &'t str) -> Option<Match<'t>> {
+        self.find(&self.fix_ticket, text)
+    }
+
+    /// Returns the tickets referenced with a fix or link keyword in the text, in the order they
+    /// appear in the text. The matches only contain the ticket reference, without the keyword.
+    pub fn referenced_tickets<'t>(&self, text: &'t str) -> Vec<Match<'t>> {
+        let mut tickets = self
+            .fix_ticket
+            .captures_iter(text)
+            .chain(self.link_ticket.captures_iter(text))
+            .filter(|captures| self.is_allowed_project(captures))
+            .filter_map(|captures| captures.name("ticket"))
+            .collect::<Vec<Match>>();
+        tickets.sort_by_key(|ticket| ticket.start());
+        tickets
+    }
+
+    /// Returns all ticket references in the text, with or without a fix keyword.
+    ///
+    /// The captures contain a `match` group with the entire reference and a `keyword` group with
+    /// the fix keyword, if present.
+    pub fn fix_tickets_or_references<'t>(&self, text: &'t str) -> Vec<Captures<'t>> {
+        self.fix_ticket_or_reference
+            .captures_iter(text)
+            .filter(|captures| self.is_allowed_project(captures))
+            .collect()
+    }
+
+    fn find<'t>(&self, regex: &Regex, text: &'t str) -> Option<Match<'t>> {
+        regex
+            .captures_iter(text)
+            .filter(|captures| self.is_allowed_project(captures))
+            .find_map(|captures| captures.get(0))
+    }
+
+    // Only syntaxes with a project allow-list are checked. The project captured by the syntax
+    // needs to be one of the allowed projects.
+    fn is_allowed_project(&self, captures: &Captures) -> bool {
+        self.projects
+            .iter()
+            .all(|(group, projects)| match captures.name(group) {
+                Some(project) => projects.iter().any(|p| p == project.as_str()),
+                None => true,
+            })
+    }
+}
+
+// Edits that add a line at the end of the message body, before the trailers. If the commit has no
+// message body, an empty line is added below the subject first.
+pub fn message_body_addition(commit: &Commit, text: String) -> Vec<Edit> {
+    let line_count = commit.message.trim_end().lines().count();
+    if line_count == 0 {
+        vec![
+            Edit::InsertLine {
+                line: 2,
+                text: "".to_string(),
+            },
+            Edit::InsertLine { line: 2, text },
+        ]
+    } else {
+        // +1 for the subject line
+        // +1 for the line below the last line
+        vec![Edit::InsertLine {
+            line: line_count + 2,
+            text,
+        }]
+    }
+}
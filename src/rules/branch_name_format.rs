@@ -0,0 +1,468 @@
+use core::ops::Range;
+use regex::Regex;
+use serde::Deserialize;
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::branch::Branch;
+use crate::config::file::{BranchNameFormatConfig, TicketConfig};
+use crate::issue::{Context, Edit, Issue, Position, Suggestion};
+use crate::rule::Rule;
+use crate::rule::RuleValidator;
+use crate::rules::TicketReferences;
+use crate::utils::character_count_for_bytes_index;
+
+lazy_static! {
+    static ref SLUG: Regex = Regex::new(r"^[a-z0-9]+(-[a-z0-9]+)*$").unwrap();
+    static ref PLACEHOLDER: Regex = Regex::new(r"<([^<>]*)>").unwrap();
+}
+
+/// A part of the branch name format.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Segment {
+    /// Text that needs to be present as is, like the `/` separator
+    Literal(String),
+    /// One of the configured branch types, like `feature`
+    Type,
+    /// A ticket reference in one of the configured ticket syntaxes
+    Ticket,
+    /// Lowercase words separated by hyphens, like `fix-login-form`
+    Slug,
+}
+
+/// The format branch names need to match, like `<type>/<ticket>-<slug>`.
+///
+/// The format is parsed when the config file is parsed, so unknown placeholders are reported as
+/// config file errors.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(try_from = "String")]
+pub struct BranchFormat {
+    format: String,
+    segments: Vec<Segment>,
+}
+
+impl BranchFormat {
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+}
+
+impl TryFrom<String> for BranchFormat {
+    type Error = String;
+
+    fn try_from(format: String) -> Result<Self, Self::Error> {
+        let mut segments = vec![];
+        let mut last_end = 0;
+        for placeholder in PLACEHOLDER.captures_iter(&format) {
+            let (all, name) = match (placeholder.get(0), placeholder.get(1)) {
+                (Some(all), Some(name)) => (all, name),
+                _ => continue,
+            };
+            if all.start() > last_end {
+                segments.push(Segment::Literal(format[last_end..all.start()].to_string()));
+            }
+            let segment = match name.as_str() {
+                "type" => Segment::Type,
+                "ticket" => Segment::Ticket,
+                "slug" => Segment::Slug,
+                name => {
+                    return Err(format!(
+                        "Unknown placeholder in branch name format {:?}: <{}>",
+                        format, name
+                    ))
+                }
+            };
+            segments.push(segment);
+            last_end = all.end();
+        }
+        if last_end < format.len() {
+            segments.push(Segment::Literal(format[last_end..].to_string()));
+        }
+        Ok(Self { format, segments })
+    }
+}
+
+impl fmt::Display for BranchFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.format)
+    }
+}
+
+pub struct BranchNameFormat {
+    format: BranchFormat,
+    types: Vec<String>,
+    tickets: TicketReferences,
+}
+
+// The segment of the branch name that does not match the format
+struct SegmentError {
+    message: String,
+    hint: String,
+    range: Range<usize>,
+}
+
+impl BranchNameFormat {
+    pub fn new(config: &BranchNameFormatConfig, tickets: &TicketConfig) -> Self {
+        Self {
+            format: config.format.clone(),
+            types: config.types.clone(),
+            tickets: tickets.references().clone(),
+        }
+    }
+
+    // Match the branch name against the format one segment at a time, so the issue can point to
+    // the segment that does not match.
+    fn check_segments(&self, name: &str) -> Result<(), SegmentError> {
+        let segments = self.format.segments();
+        let mut position = 0;
+        for (index, segment) in segments.iter().enumerate() {
+            let rest = &name[position..];
+            // The text up to the next literal in the format, like the `/` after the type
+            let text_end = match segments.get(index + 1) {
+                Some(Segment::Literal(literal)) => rest.find(literal.as_str()),
+                _ => None,
+            }
+            .unwrap_or(rest.len());
+            let text = &rest[..text_end];
+            let text_range = segment_range(name, position, text_end);
+            match segment {
+                Segment::Literal(literal) => {
+                    if !rest.starts_with(literal.as_str()) {
+                        return Err(SegmentError {
+                            message: format!(
+                                "The branch name does not contain `{}` at this position",
+                                literal
+                            ),
+                            hint: format!("Add `{}` here", literal),
+                            range: segment_range(name, position, 0),
+                        });
+                    }
+                    position += literal.len();
+                }
+                Segment::Type => {
+                    if !self.types.iter().any(|t| t == text) {
+                        let message = if text.is_empty() {
+                            "The branch name does not contain a type".to_string()
+                        } else {
+                            format!("The branch name type `{}` is not allowed", text)
+                        };
+                        return Err(SegmentError {
+                            message,
+                            hint: format!("Use one of the types: {}", self.types.join(", ")),
+                            range: text_range,
+                        });
+                    }
+                    position += text.len();
+                }
+                Segment::Ticket => match self.tickets.find_reference(rest) {
+                    Some(ticket) if ticket.start() == 0 => position += ticket.end(),
+                    _ => {
+                        return Err(SegmentError {
+                            message: "The branch name does not contain a ticket reference"
+                                .to_string(),
+                            hint: "Add a ticket reference here".to_string(),
+                            range: segment_range(name, position, 0),
+                        })
+                    }
+                },
+                Segment::Slug => {
+                    if !SLUG.is_match(text) {
+                        let message = if text.is_empty() {
+                            "The branch name does not contain a description".to_string()
+                        } else {
+                            "The branch name description is not written in lowercase words \
+                            separated by hyphens"
+                                .to_string()
+                        };
+                        return Err(SegmentError {
+                            message,
+                            hint: "Use lowercase words separated by hyphens".to_string(),
+                            range: text_range,
+                        });
+                    }
+                    position += text.len();
+                }
+            }
+        }
+        if position < name.len() {
+            return Err(SegmentError {
+                message: format!(
+                    "The branch name contains more text than the `{}` format",
+                    self.format
+                ),
+                hint: "Remove this text".to_string(),
+                range: position..name.len(),
+            });
+        }
+        Ok(())
+    }
+
+    // A branch name in the format, based on the words and ticket reference in the current name.
+    // Returns `None` if no name can be derived, like when there's no ticket reference in the name.
+    fn suggested_name(&self, name: &str) -> Option<String> {
+        let ticket = self.tickets.find_reference(name);
+        let without_ticket = match &ticket {
+            Some(ticket) => format!("{}-{}", &name[..ticket.start()], &name[ticket.end()..]),
+            None => name.to_string(),
+        };
+        let mut words = without_ticket
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(|word| word.to_lowercase())
+            .collect::<Vec<String>>();
+        // Use the first word that is a type, or an abbreviation of a type, like `feat`
+        let type_index = words
+            .iter()
+            .position(|word| self.types.iter().any(|t| t.starts_with(word.as_str())));
+        let branch_type = match type_index {
+            Some(index) => {
+                let word = words.remove(index);
+                self.types
+                    .iter()
+                    .find(|t| t.starts_with(&word))?
+                    .to_string()
+            }
+            None => self.types.first()?.to_string(),
+        };
+        let slug = words.join("-");
+
+        let mut suggestion = String::new();
+        for segment in self.format.segments() {
+            match segment {
+                Segment::Literal(literal) => suggestion.push_str(literal),
+                Segment::Type => suggestion.push_str(&branch_type),
+                Segment::Ticket => suggestion.push_str(ticket.as_ref()?.as_str()),
+                Segment::Slug if slug.is_empty() => return None,
+                Segment::Slug => suggestion.push_str(&slug),
+            }
+        }
+        if suggestion == name {
+            None
+        } else {
+            Some(suggestion)
+        }
+    }
+}
+
+// The byte range of the segment. Empty segments are shown as the character at the position, or
+// at the end of the name.
+fn segment_range(name: &str, start: usize, length: usize) -> Range<usize> {
+    let length = if length == 0 {
+        name[start..].chars().next().map_or(0, |c| c.len_utf8())
+    } else {
+        length
+    };
+    Range {
+        start,
+        end: start + length,
+    }
+}
+
+impl RuleValidator<Branch> for BranchNameFormat {
+    fn validate(&self, branch: &Branch) -> Option<Vec<Issue>> {
+        let name = &branch.name;
+        let error = self.check_segments(name).err()?;
+        let mut context = vec![Context::branch_error(
+            name.to_string(),
+            error.range.clone(),
+            error.hint,
+        )];
+        let suggestion = self.suggested_name(name).map(|suggested_name| {
+            context.push(Context::branch_addition_suggestion(
+                suggested_name.to_string(),
+                Range {
+                    start: 0,
+                    end: suggested_name.len(),
+                },
+                format!("Rename the branch to match the `{}` format", self.format),
+            ));
+            Suggestion::maybe_incorrect(
+                format!("Rename the branch to `{}`", suggested_name),
+                vec![Edit::ReplaceBranch {
+                    range: Range {
+                        start: 0,
+                        end: name.len(),
+                    },
+                    text: suggested_name,
+                }],
+            )
+        });
+        let issue = Issue::error(
+            Rule::BranchNameFormat,
+            error.message,
+            Position::Branch {
+                column: character_count_for_bytes_index(name, error.range.start),
+            },
+            context,
+        );
+        Some(vec![match suggestion {
+            Some(suggestion) => issue.with_suggestion(suggestion),
+            None => issue,
+        }])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::file::Config;
+    use crate::test::*;
+
+    fn validator(format: &str) -> BranchNameFormat {
+        let config = Config::parse(&format!(
+            "{}\n\
+            [rules.BranchNameFormat]\n\
+            format = {:?}\n\
+            types = [\"feature\", \"fix\", \"chore\"]",
+            TICKET_SYNTAXES, format
+        ))
+        .unwrap();
+        BranchNameFormat::new(&config.branch_name_format, &config.tickets)
+    }
+
+    fn validate(name: &str) -> Option<Vec<Issue>> {
+        validator("<type>/<ticket>-<slug>").validate(&branch(name))
+    }
+
+    #[test]
+    fn parse_format() {
+        let format = BranchFormat::try_from("<type>/<ticket>-<slug>".to_string()).unwrap();
+        assert_eq!(
+            format.segments(),
+            vec![
+                Segment::Type,
+                Segment::Literal("/".to_string()),
+                Segment::Ticket,
+                Segment::Literal("-".to_string()),
+                Segment::Slug,
+            ]
+        );
+        assert_eq!(
+            BranchFormat::try_from("<type>/<name>".to_string()),
+            Err("Unknown placeholder in branch name format \"<type>/<name>\": <name>".to_string())
+        );
+    }
+
+    #[test]
+    fn valid_names() {
+        for name in [
+            "feature/ENG-123-login-form",
+            "fix/OPS-1-crash",
+            "chore/AB#1234-update-dependencies",
+        ] {
+            assert_eq!(validate(name), None, "Branch name is invalid: {}", name);
+        }
+        assert_eq!(
+            validator("<type>/<slug>").validate(&branch("fix/login-form")),
+            None
+        );
+    }
+
+    #[test]
+    fn invalid_type() {
+        let issue = first_issue(validate("feat/ENG-123-login-form"));
+        assert_eq!(issue.message, "The branch name type `feat` is not allowed");
+        assert_eq!(issue.position, Position::Branch { column: 1 });
+        assert_contains_issue_output(
+            &issue,
+            "| feat/ENG-123-login-form\n\
+             | ^^^^ Use one of the types: feature, fix, chore\n\
+             | feature/ENG-123-login-form\n\
+             | ++++++++++++++++++++++++++ Rename the branch to match the `<type>/<ticket>-<slug>` format",
+        );
+        assert_eq!(
+            issue.suggestions,
+            vec![Suggestion::maybe_incorrect(
+                "Rename the branch to `feature/ENG-123-login-form`".to_string(),
+                vec![Edit::ReplaceBranch {
+                    range: Range { start: 0, end: 23 },
+                    text: "feature/ENG-123-login-form".to_string(),
+                }],
+            )]
+        );
+    }
+
+    #[test]
+    fn missing_separator() {
+        let issue = first_issue(validate("feature-ENG-123-login-form"));
+        assert_eq!(
+            issue.message,
+            "The branch name type `feature-ENG-123-login-form` is not allowed"
+        );
+        let issue = first_issue(validator("<type>/<slug>").validate(&branch("fix")));
+        assert_eq!(
+            issue.message,
+            "The branch name does not contain `/` at this position"
+        );
+        assert_eq!(issue.position, Position::Branch { column: 4 });
+    }
+
+    #[test]
+    fn missing_ticket() {
+        let issue = first_issue(validate("feature/login-form"));
+        assert_eq!(
+            issue.message,
+            "The branch name does not contain a ticket reference"
+        );
+        assert_eq!(issue.position, Position::Branch { column: 9 });
+        // No name can be suggested without a ticket reference
+        assert!(issue.suggestions.is_empty());
+        assert_contains_issue_output(
+            &issue,
+            "| feature/login-form\n\
+             |         ^ Add a ticket reference here",
+        );
+    }
+
+    #[test]
+    fn invalid_slug() {
+        let issue = first_issue(validate("fix/ENG-123-Login_Form"));
+        assert_eq!(
+            issue.message,
+            "The branch name description is not written in lowercase words separated by hyphens"
+        );
+        assert_eq!(issue.position, Position::Branch { column: 13 });
+        assert_contains_issue_output(
+            &issue,
+            "| fix/ENG-123-Login_Form\n\
+             |             ^^^^^^^^^^ Use lowercase words separated by hyphens\n\
+             | fix/ENG-123-login-form\n",
+        );
+
+        let issue = first_issue(validate("fix/ENG-123-"));
+        assert_eq!(
+            issue.message,
+            "The branch name does not contain a description"
+        );
+        assert!(issue.suggestions.is_empty());
+    }
+
+    #[test]
+    fn extra_text() {
+        let issue =
+            first_issue(validator("<type>/<ticket>").validate(&branch("fix/ENG-123-login")));
+        assert_eq!(
+            issue.message,
+            "The branch name contains more text than the `<type>/<ticket>` format"
+        );
+        assert_eq!(issue.position, Position::Branch { column: 12 });
+    }
+
+    #[test]
+    fn suggested_names() {
+        let validator = validator("<type>/<ticket>-<slug>");
+        assert_eq!(
+            validator.suggested_name("ENG-123-login-form"),
+            Some("feature/ENG-123-login-form".to_string())
+        );
+        assert_eq!(
+            validator.suggested_name("login-form-ENG-123-fix"),
+            Some("fix/ENG-123-login-form".to_string())
+        );
+        assert_eq!(
+            validator.suggested_name("Fix_Login_Form/ENG-123"),
+            Some("fix/ENG-123-login-form".to_string())
+        );
+        assert_eq!(validator.suggested_name("login-form"), None);
+    }
+}
@@ -1,7 +1,7 @@
 use core::ops::Range;
 
 use crate::branch::Branch;
-use crate::issue::{Context, Issue, Position};
+use crate::issue::{Context, Edit, Issue, Position, Suggestion};
 use crate::rule::Rule;
 use crate::rule::RuleValidator;
 use crate::utils::{character_count_for_bytes_index, is_punctuation};
@@ -21,20 +21,31 @@ impl RuleValidator<Branch> for BranchNamePunctuation {
             Some(character) => {
                 if is_punctuation(*character) {
                     let branch = &branch.name;
+                    let range = Range {
+                        start: 0,
+                        end: character.len_utf8(),
+                    };
                     let context = vec![Context::branch_removal_suggestion(
                         branch.to_string(),
-                        Range {
-                            start: 0,
-                            end: character.len_utf8(),
-                        },
+                        range.clone(),
                         "Remove punctuation from the start of the branch name".to_string(),
                     )];
-                    issues.push(Issue::error(
-                        Rule::BranchNamePunctuation,
-                        "The branch name starts with a punctuation character".to_string(),
-                        Position::Branch { column: 1 },
-                        context,
-                    ));
+                    let suggestion = Suggestion::safe(
+                        "Remove punctuation from the start of the branch name".to_string(),
+                        vec![Edit::ReplaceBranch {
+                            range,
+                            text: "".to_string(),
+                        }],
+                    );
+                    issues.push(
+                        Issue::error(
+                            Rule::BranchNamePunctuation,
+                            "The branch name starts with a punctuation character".to_string(),
+                            Position::Branch { column: 1 },
+                            context,
+                        )
+                        .with_suggestion(suggestion),
+                    );
                 }
             }
             None => {
@@ -49,25 +60,36 @@ impl RuleValidator<Branch> for BranchNamePunctuation {
                 if is_punctuation(*character) {
                     let branch_length = branch.name.len();
                     let branch = &branch.name;
+                    let range = Range {
+                        start: branch_length - character.len_utf8(),
+                        end: branch_length,
+                    };
                     let context = vec![Context::branch_removal_suggestion(
                         branch.to_string(),
-                        Range {
-                            start: branch_length - character.len_utf8(),
-                            end: branch_length,
-                        },
+                        range.clone(),
                         "Remove punctuation from the end of the branch name".to_string(),
                     )];
-                    issues.push(Issue::error(
-                        Rule::BranchNamePunctuation,
-                        "The branch name ends with a punctuation character".to_string(),
-                        Position::Branch {
-                            column: character_count_for_bytes_index(
-                                branch,
-                                branch.len() - character.len_utf8(),
-                            ),
-                        },
-                        context,
-                    ));
+                    let suggestion = Suggestion::safe(
+                        "Remove punctuation from the end of the branch name".to_string(),
+                        vec![Edit::ReplaceBranch {
+                            range,
+                            text: "".to_string(),
+                        }],
+                    );
+                    issues.push(
+                        Issue::error(
+                            Rule::BranchNamePunctuation,
+                            "The branch name ends with a punctuation character".to_string(),
+                            Position::Branch {
+                                column: character_count_for_bytes_index(
+                                    branch,
+                                    branch.len() - character.len_utf8(),
+                                ),
+                            },
+                            context,
+                        )
+                        .with_suggestion(suggestion),
+                    );
                 }
             }
             None => {
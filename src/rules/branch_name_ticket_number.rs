@@ -1,11 +1,15 @@
 use core::ops::Range;
 
 use crate::branch::Branch;
+use crate::config::file::TicketConfig;
 use crate::issue::{Context, Issue, Position};
 use crate::rule::Rule;
 use crate::rule::RuleValidator;
+use crate::rules::TicketReferences;
 use regex::{Regex, RegexBuilder};
 
+const BRANCH_NAME_SEPARATORS: [char; 4] = ['-', '_', '/', '.'];
+
 lazy_static! {
     static ref BRANCH_WITH_TICKET_NUMBER: Regex = {
         let mut tempregex = RegexBuilder::new(r"^(\w+[-_/\.])?\d{2,}([-_/\.]\w+)?([-_/\.]\w+)?");
@@ -15,44 +19,71 @@ lazy_static! {
     };
 }
 
-pub struct BranchNameTicketNumber {}
+pub struct BranchNameTicketNumber {
+    // Only set when ticket syntaxes are configured, so the default config keeps the behavior of
+    // the branch name pattern
+    tickets: Option<TicketReferences>,
+}
 
 impl BranchNameTicketNumber {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(config: &TicketConfig) -> Self {
+        Self {
+            tickets: config.is_configured().then(|| config.references().clone()),
+        }
+    }
+
+    // Check for references of the configured ticket syntaxes, like `JIRA-123`. The branch name
+    // needs at least two words next to the ticket reference.
+    fn has_ticket_reference_only(&self, name: &str) -> bool {
+        let tickets = match &self.tickets {
+            Some(tickets) => tickets,
+            None => return false,
+        };
+        match tickets.find_reference(name) {
+            Some(reference) => {
+                let words = name[..reference.start()]
+                    .split(BRANCH_NAME_SEPARATORS)
+                    .chain(name[reference.end()..].split(BRANCH_NAME_SEPARATORS))
+                    .filter(|word| !word.is_empty())
+                    .count();
+                words < 2
+            }
+            None => false,
+        }
     }
 }
 
 impl RuleValidator<Branch> for BranchNameTicketNumber {
     fn validate(&self, branch: &Branch) -> Option<Vec<Issue>> {
         let name = &branch.name;
+        let mut valid = true;
         if let Some(captures) = BRANCH_WITH_TICKET_NUMBER.captures(name) {
-            let valid = match (captures.get(1), captures.get(2), captures.get(3)) {
+            valid = match (captures.get(1), captures.get(2), captures.get(3)) {
                 (None, None, _) => false,
                 (Some(_prefix), None, _) => false,
                 (None, Some(_suffix), None) => false,
                 (None, Some(_suffix), Some(_suffix_more)) => true,
                 (Some(_prefix), Some(_suffix), _) => true,
             };
-            if !valid {
-                let context = vec![Context::branch_removal_suggestion(
-                    name.to_string(),
-                    Range {
-                        start: 0,
-                        end: name.len(),
-                    },
-                    "Remove the ticket number from the branch name or expand the branch name with more details".to_string(),
-                )];
-                return Some(vec![Issue::error(
-                    Rule::BranchNameTicketNumber,
-                    "A ticket number was detected in the branch name".to_string(),
-                    Position::Branch { column: 1 },
-                    context,
-                )]);
-            }
+        }
+        if valid && !self.has_ticket_reference_only(name) {
+            return None;
         }
 
-        None
+        let context = vec![Context::branch_removal_suggestion(
+            name.to_string(),
+            Range {
+                start: 0,
+                end: name.len(),
+            },
+            "Remove the ticket number from the branch name or expand the branch name with more details".to_string(),
+        )];
+        Some(vec![Issue::error(
+            Rule::BranchNameTicketNumber,
+            "A ticket number was detected in the branch name".to_string(),
+            Position::Branch { column: 1 },
+            context,
+        )])
     }
 }
 
@@ -62,7 +93,7 @@ mod tests {
     use crate::test::*;
 
     fn validate(branch: &Branch) -> Option<Vec<Issue>> {
-        BranchNameTicketNumber::new().validate(branch)
+        BranchNameTicketNumber::new(&TicketConfig::default()).validate(branch)
     }
 
     fn assert_valid(name: &str) {
@@ -90,6 +121,8 @@ mod tests {
             "elixir-1.13.2-ci",
             "erlang-20.2",
             "fix-bug",
+            "ENG-123-fix",
+            "feature/ENG-123",
         ];
         for name in names {
             assert_valid(name);
@@ -133,4 +166,24 @@ mod tests {
              | ------- Remove the ticket number from the branch name or expand the branch name with more details"
         );
     }
+
+    #[test]
+    fn configured_ticket_syntaxes() {
+        let validator = BranchNameTicketNumber::new(&ticket_config(TICKET_SYNTAXES));
+        for name in ["ENG-123", "ENG-123-fix", "fix/TKT:00042", "AB#1234"] {
+            assert!(
+                validator.validate(&branch(name)).is_some(),
+                "Branch name not invalid: {}",
+                name
+            );
+        }
+        for name in [
+            "ENG-123-fix-bug",
+            "feature/AB#1234-login",
+            "FOO-123-fix",
+            "fix-bug",
+        ] {
+            assert_eq!(validator.validate(&branch(name)), None);
+        }
+    }
 }
@@ -0,0 +1,439 @@
+use core::ops::Range;
+use regex::Regex;
+
+use crate::commit::Commit;
+use crate::config::file::{ConventionalCommitConfig, Pattern};
+use crate::issue::{Context, Edit, Issue, Position, Suggestion};
+use crate::rule::Rule;
+use crate::rule::RuleValidator;
+use crate::utils::character_count_for_bytes_index;
+
+lazy_static! {
+    static ref CONVENTIONAL_SUBJECT: Regex = Regex::new(
+        r"^(?P<type>[\w-]+)(?:\((?P<scope>[^()]*)\))?(?P<breaking>!)?: (?P<description>.*)$"
+    )
+    .unwrap();
+    static ref BREAKING_CHANGE_FOOTER: Regex =
+        Regex::new(r"(?i)^(?P<token>breaking[ -]change):(?P<description>.*)$").unwrap();
+}
+
+/// A subject in the Conventional Commits format: `<type>(<scope>)!: <description>`.
+///
+/// The ranges are the bytes ranges of each part in the subject.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConventionalSubject {
+    pub r#type: Range<usize>,
+    pub scope: Option<Range<usize>>,
+    pub breaking: bool,
+    pub description: Range<usize>,
+}
+
+impl ConventionalSubject {
+    pub fn parse(subject: &str) -> Option<Self> {
+        let captures = CONVENTIONAL_SUBJECT.captures(subject)?;
+        Some(Self {
+            r#type: captures.name("type")?.range(),
+            scope: captures.name("scope").map(|scope| scope.range()),
+            breaking: captures.name("breaking").is_some(),
+            description: captures.name("description")?.range(),
+        })
+    }
+}
+
+pub struct ConventionalCommit {
+    types: Vec<String>,
+    scope_pattern: Option<Pattern>,
+}
+
+impl ConventionalCommit {
+    pub fn new(config: &ConventionalCommitConfig) -> Self {
+        Self {
+            types: config.types.clone(),
+            scope_pattern: config.scope_pattern.clone(),
+        }
+    }
+
+    fn validate_subject(&self, commit: &Commit, issues: &mut Vec<Issue>) {
+        let conventional = match &commit.conventional_subject {
+            Some(conventional) => conventional,
+            None => {
+                let context = vec![Context::subject_error(
+                    commit.subject.to_string(),
+                    Range {
+                        start: 0,
+                        end: commit.subject.len(),
+                    },
+                    "Use the `<type>(<scope>): <description>` format".to_string(),
+                )];
+                issues.push(Issue::error(
+                    Rule::ConventionalCommit,
+                    "The subject does not use the Conventional Commits format".to_string(),
+                    Position::Subject { line: 1, column: 1 },
+                    context,
+                ));
+                return;
+            }
+        };
+
+        let commit_type = &commit.subject[conventional.r#type.clone()];
+        if !self.types.iter().any(|t| t == commit_type) {
+            let context = vec![Context::subject_error(
+                commit.subject.to_string(),
+                conventional.r#type.clone(),
+                format!("Use one of the allowed types: {}", self.types.join(", ")),
+            )];
+            issues.push(Issue::error(
+                Rule::ConventionalCommit,
+                format!("The subject uses an unknown type: `{}`", commit_type),
+                Position::Subject { line: 1, column: 1 },
+                context,
+            ));
+        }
+
+        if let (Some(scope_range), Some(pattern)) = (&conventional.scope, &self.scope_pattern) {
+            let scope = &commit.subject[scope_range.clone()];
+            if !pattern.is_match(scope) {
+                let context = vec![Context::subject_error(
+                    commit.subject.to_string(),
+                    scope_range.clone(),
+                    format!(
+                        "Use a scope that matches the pattern: `{}`",
+                        pattern.as_str()
+                    ),
+                )];
+                issues.push(Issue::error(
+                    Rule::ConventionalCommit,
+                    format!("The subject uses an invalid scope: `{}`", scope),
+                    Position::Subject {
+                        line: 1,
+                        column: character_count_for_bytes_index(&commit.subject, scope_range.start),
+                    },
+                    context,
+                ));
+            }
+        }
+    }
+
+    fn validate_breaking_change_footers(&self, commit: &Commit, issues: &mut Vec<Issue>) {
+        // Footers are only written in the last paragraph of the message. These are the trailers,
+        // or the last paragraph of the message body if Git recognized no trailers.
+        let footer_lines = if commit.trailers.is_empty() {
+            let message_lines = commit
+                .message
+                .lines()
+                .enumerate()
+                .map(|(index, line)| (index + 2, line))
+                .collect::<Vec<(usize, &str)>>();
+            let paragraph_start = message_lines
+                .iter()
+                .rposition(|(_, line)| line.trim().is_empty())
+                .map_or(0, |index| index + 1);
+            message_lines[paragraph_start..].to_vec()
+        } else {
+            // Line number of the empty line between the message and the trailers.
+            // +1 for subject line
+            // +1 for the empty line
+            let trailers_line = commit.message.lines().count() + 2;
+            commit
+                .trailers
+                .lines()
+                .enumerate()
+                .map(|(index, line)| (index + trailers_line + 1, line))
+                .collect::<Vec<(usize, &str)>>()
+        };
+
+        for (line_number, line) in footer_lines {
+            let captures = match BREAKING_CHANGE_FOOTER.captures(line) {
+                Some(captures) => captures,
+                None => continue,
+            };
+            let token = match captures.name("token") {
+                Some(token) => token,
+                None => {
+                    error!("ConventionalCommit: Unable to fetch token capture");
+                    continue;
+                }
+            };
+            if token.as_str() != "BREAKING CHANGE" && token.as_str() != "BREAKING-CHANGE" {
+                let context = vec![Context::message_line_error(
+                    line_number,
+                    line.to_string(),
+                    token.range(),
+                    "Write the footer token as `BREAKING CHANGE`".to_string(),
+                )];
+                let suggestion = Suggestion::safe(
+                    "Write the footer token in uppercase".to_string(),
+                    vec![Edit::Replace {
+                        line: line_number,
+                        range: token.range(),
+                        text: token.as_str().to_uppercase(),
+                    }],
+                );
+                issues.push(
+                    Issue::error(
+                        Rule::ConventionalCommit,
+                        "The breaking change footer is not written in uppercase".to_string(),
+                        Position::MessageLine {
+                            line: line_number,
+                            column: 1,
+                        },
+                        context,
+                    )
+                    .with_suggestion(suggestion),
+                );
+            }
+            let description = captures
+                .name("description")
+                .map(|d| d.as_str())
+                .unwrap_or("");
+            if description.trim().is_empty() {
+                let context = vec![Context::message_line_error(
+                    line_number,
+                    line.to_string(),
+                    Range {
+                        start: 0,
+                        end: line.len(),
+                    },
+                    "Describe the breaking change after the footer token".to_string(),
+                )];
+                issues.push(Issue::error(
+                    Rule::ConventionalCommit,
+                    "The breaking change footer has no description".to_string(),
+                    Position::MessageLine {
+                        line: line_number,
+                        column: 1,
+                    },
+                    context,
+                ));
+            }
+        }
+    }
+}
+
+impl RuleValidator<Commit> for ConventionalCommit {
+    fn validate(&self, commit: &Commit) -> Option<Vec<Issue>> {
+        let mut issues = vec![];
+        self.validate_subject(commit, &mut issues);
+        self.validate_breaking_change_footers(commit, &mut issues);
+
+        if issues.is_empty() {
+            None
+        } else {
+            Some(issues)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::file::Config;
+    use crate::test::*;
+
+    fn conventional_commit_with_trailers(subject: &str, message: &str, trailers: &str) -> Commit {
+        let mut commit = commit_with_trailers(subject, message, trailers);
+        commit.conventional_subject = ConventionalSubject::parse(&commit.subject);
+        commit
+    }
+
+    fn validate(commit: &Commit) -> Option<Vec<Issue>> {
+        ConventionalCommit::new(&ConventionalCommitConfig::default()).validate(commit)
+    }
+
+    fn validate_with_config(commit: &Commit, config: &str) -> Option<Vec<Issue>> {
+        let config = Config::parse(config).unwrap();
+        ConventionalCommit::new(&config.conventional_commit).validate(commit)
+    }
+
+    fn assert_subject_as_valid(subject: &str) {
+        assert_eq!(validate(&conventional_commit(subject, "")), None);
+    }
+
+    fn assert_subject_as_invalid(subject: &str) {
+        assert!(validate(&conventional_commit(subject, "")).is_some());
+    }
+
+    #[test]
+    fn parse_subject() {
+        let subject = "feat(api)!: add endpoint";
+        let conventional = ConventionalSubject::parse(subject).unwrap();
+        assert_eq!(&subject[conventional.r#type.clone()], "feat");
+        assert_eq!(&subject[conventional.scope.clone().unwrap()], "api");
+        assert!(conventional.breaking);
+        assert_eq!(&subject[conventional.description], "add endpoint");
+
+        let conventional = ConventionalSubject::parse("fix: bug").unwrap();
+        assert_eq!(conventional.scope, None);
+        assert!(!conventional.breaking);
+        assert_eq!(conventional.description, Range { start: 5, end: 8 });
+
+        assert_eq!(ConventionalSubject::parse("Fix bug"), None);
+        assert_eq!(ConventionalSubject::parse("fix:bug"), None);
+    }
+
+    #[test]
+    fn valid_subjects() {
+        let subjects = vec![
+            "feat: add endpoint",
+            "fix(api): handle empty response",
+            "refactor!: drop support for Node 12",
+            "chore(deps)!: update dependencies",
+        ];
+        for subject in subjects {
+            assert_subject_as_valid(subject);
+        }
+    }
+
+    #[test]
+    fn invalid_subjects() {
+        let subjects = vec![
+            "Add endpoint",
+            "feat:add endpoint",
+            "feat(api: add endpoint",
+            "feature: add endpoint",
+            "Feat: add endpoint",
+        ];
+        for subject in subjects {
+            assert_subject_as_invalid(subject);
+        }
+    }
+
+    #[test]
+    fn invalid_format() {
+        let issue = first_issue(validate(&conventional_commit("Add endpoint", "")));
+        assert_eq!(
+            issue.message,
+            "The subject does not use the Conventional Commits format"
+        );
+        assert_eq!(issue.position, subject_position(1));
+        assert_contains_issue_output(
+            &issue,
+            "1 | Add endpoint\n\
+               | ^^^^^^^^^^^^ Use the `<type>(<scope>): <description>` format",
+        );
+    }
+
+    #[test]
+    fn unknown_type() {
+        let issue = first_issue(validate_with_config(
+            &conventional_commit("feature(api): add endpoint", ""),
+            "[rules.ConventionalCommit]\ntypes = [\"feat\", \"fix\"]",
+        ));
+        assert_eq!(issue.message, "The subject uses an unknown type: `feature`");
+        assert_eq!(issue.position, subject_position(1));
+        assert_contains_issue_output(
+            &issue,
+            "1 | feature(api): add endpoint\n\
+               | ^^^^^^^ Use one of the allowed types: feat, fix",
+        );
+    }
+
+    #[test]
+    fn invalid_scope() {
+        let config = "[rules.ConventionalCommit]\nscope_pattern = \"^[a-z]+$\"";
+        let issue = first_issue(validate_with_config(
+            &conventional_commit("feat(API): add endpoint", ""),
+            config,
+        ));
+        assert_eq!(issue.message, "The subject uses an invalid scope: `API`");
+        assert_eq!(issue.position, subject_position(6));
+        assert_contains_issue_output(
+            &issue,
+            "1 | feat(API): add endpoint\n\
+               |      ^^^ Use a scope that matches the pattern: `^[a-z]+$`",
+        );
+
+        assert_eq!(
+            validate_with_config(&conventional_commit("feat(api): add endpoint", ""), config),
+            None
+        );
+        assert_eq!(
+            validate_with_config(&conventional_commit("feat: add endpoint", ""), config),
+            None
+        );
+    }
+
+    #[test]
+    fn valid_breaking_change_footers() {
+        let commit = conventional_commit_with_trailers(
+            "feat!: drop old endpoint",
+            "\nThe endpoint was deprecated.\n\nBREAKING CHANGE: The v1 endpoint is removed.",
+            "",
+        );
+        assert_eq!(validate(&commit), None);
+
+        let commit = conventional_commit_with_trailers(
+            "feat: drop old endpoint",
+            "\nThe endpoint was deprecated.",
+            "BREAKING-CHANGE: The v1 endpoint is removed.",
+        );
+        assert_eq!(validate(&commit), None);
+    }
+
+    #[test]
+    fn breaking_change_in_message_body() {
+        let commit = conventional_commit(
+            "feat: drop old endpoint",
+            "\nBreaking change: the v1 endpoint is removed, as announced in\n\
+            the previous release.\n\nThe endpoint was deprecated.",
+        );
+        assert_eq!(validate(&commit), None);
+
+        let commit = conventional_commit_with_trailers(
+            "feat: drop old endpoint",
+            "\nThe endpoint was deprecated.\n\nBreaking change: the v1 endpoint is removed.",
+            "Co-authored-by: Person A <a@example.com>",
+        );
+        assert_eq!(validate(&commit), None);
+    }
+
+    #[test]
+    fn lowercase_breaking_change_footer() {
+        let commit = conventional_commit(
+            "feat: drop old endpoint",
+            "\nThe endpoint was deprecated.\n\nBreaking change: The v1 endpoint is removed.",
+        );
+        let issue = first_issue(validate(&commit));
+        assert_eq!(
+            issue.message,
+            "The breaking change footer is not written in uppercase"
+        );
+        assert_eq!(issue.position, message_position(5, 1));
+        assert_contains_issue_output(
+            &issue,
+            "5 | Breaking change: The v1 endpoint is removed.\n\
+               | ^^^^^^^^^^^^^^^ Write the footer token as `BREAKING CHANGE`",
+        );
+        assert_eq!(
+            issue.suggestions,
+            vec![Suggestion::safe(
+                "Write the footer token in uppercase".to_string(),
+                vec![Edit::Replace {
+                    line: 5,
+                    range: Range { start: 0, end: 15 },
+                    text: "BREAKING CHANGE".to_string(),
+                }],
+            )]
+        );
+    }
+
+    #[test]
+    fn empty_breaking_change_trailer() {
+        let commit = conventional_commit_with_trailers(
+            "feat: drop old endpoint",
+            "\nThe endpoint was deprecated.",
+            "BREAKING-CHANGE:",
+        );
+        let issue = first_issue(validate(&commit));
+        assert_eq!(
+            issue.message,
+            "The breaking change footer has no description"
+        );
+        assert_eq!(issue.position, message_position(5, 1));
+        assert_contains_issue_output(
+            &issue,
+            "5 | BREAKING-CHANGE:\n\
+               | ^^^^^^^^^^^^^^^^ Describe the breaking change after the footer token",
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use core::ops::Range;
 
 use crate::commit::Commit;
-use crate::issue::{Context, Issue, Position};
+use crate::issue::{Context, Edit, Issue, Position, Suggestion};
 use crate::rule::Rule;
 use crate::rule::RuleValidator;
 
@@ -32,7 +32,14 @@ impl RuleValidator<Commit> for MessageEmptyFirstLine {
                     "No empty line found below the subject".to_string(),
                     Position::MessageLine { line: 2, column: 1 },
                     context,
-                )]);
+                )
+                .with_suggestion(Suggestion::safe(
+                    "Add an empty line below the subject line".to_string(),
+                    vec![Edit::InsertLine {
+                        line: 2,
+                        text: "".to_string(),
+                    }],
+                ))]);
             }
         }
 
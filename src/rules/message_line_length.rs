@@ -2,6 +2,7 @@ use core::ops::Range;
 use regex::Regex;
 
 use crate::commit::Commit;
+use crate::config::file::MessageLineLengthConfig;
 use crate::issue::{Context, Issue, Position};
 use crate::rule::Rule;
 use crate::rule::RuleValidator;
@@ -21,11 +22,15 @@ enum CodeBlockStyle {
     Indenting,
 }
 
-pub struct MessageLineLength {}
+pub struct MessageLineLength {
+    max_width: usize,
+}
 
 impl MessageLineLength {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(config: &MessageLineLengthConfig) -> Self {
+        Self {
+            max_width: config.max_width,
+        }
     }
 }
 
@@ -36,7 +41,7 @@ impl RuleValidator<Commit> for MessageLineLength {
         let mut issues = vec![];
         for (index, raw_line) in commit.message.lines().enumerate() {
             let line = raw_line.trim_end();
-            let (width, line_stats) = line_length_stats(line, 72);
+            let (width, line_stats) = line_length_stats(line, self.max_width);
             match code_block_style {
                 CodeBlockStyle::Fenced => {
                     if CODE_BLOCK_LINE_END.is_match(line) {
@@ -60,7 +65,7 @@ impl RuleValidator<Commit> for MessageLineLength {
                 // When in a code block, skip line length validation
                 continue;
             }
-            if width > 72 {
+            if width > self.max_width {
                 if URL_REGEX.is_match(line) {
                     continue;
                 }
@@ -72,13 +77,13 @@ impl RuleValidator<Commit> for MessageLineLength {
                         start: line_stats.bytes_index,
                         end: line.len(),
                     },
-                    "Shorten line to maximum 72 characters".to_string(),
+                    format!("Shorten line to maximum {} characters", self.max_width),
                 );
                 issues.push(Issue::error(
                     Rule::MessageLineLength,
                     format!(
-                        "Line {} in the message body is longer than 72 characters",
-                        line_number
+                        "Line {} in the message body is longer than {} characters",
+                        line_number, self.max_width
                     ),
                     Position::MessageLine {
                         line: line_number,
@@ -104,7 +109,7 @@ mod tests {
     use crate::test::*;
 
     fn validate(commit: &Commit) -> Option<Vec<Issue>> {
-        MessageLineLength::new().validate(commit)
+        MessageLineLength::new(&MessageLineLengthConfig::default()).validate(commit)
     }
 
     fn assert_valid(message: &str) {
@@ -358,4 +363,28 @@ mod tests {
             assert_not_url(string);
         }
     }
+
+    #[test]
+    fn configured_max_width() {
+        let validator = MessageLineLength::new(&MessageLineLengthConfig { max_width: 80 });
+        let message = ["".to_string(), "a".repeat(80)].join("\n");
+        assert_eq!(validator.validate(&commit("Subject", &message)), None);
+
+        let message = ["".to_string(), "a".repeat(81)].join("\n");
+        let issue = first_issue(validator.validate(&commit("Subject", &message)));
+        assert_eq!(
+            issue.message,
+            "Line 3 in the message body is longer than 80 characters"
+        );
+        assert_eq!(issue.position, message_position(3, 81));
+        assert_contains_issue_output(
+            &issue,
+            &format!(
+                "3 | {}\n\
+                   | {}^ Shorten line to maximum 80 characters",
+                "a".repeat(81),
+                " ".repeat(80)
+            ),
+        );
+    }
 }
@@ -1,17 +1,24 @@
 use core::ops::Range;
 
 use crate::commit::Commit;
+use crate::config::file::{MessagePresenceConfig, TicketConfig};
 use crate::issue::{Context, Issue, Position};
 use crate::rule::Rule;
 use crate::rule::RuleValidator;
-use crate::rules::CONTAINS_FIX_TICKET;
+use crate::rules::TicketReferences;
 use crate::utils::display_width;
 
-pub struct MessagePresence {}
+pub struct MessagePresence {
+    min_length: usize,
+    tickets: TicketReferences,
+}
 
 impl MessagePresence {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(config: &MessagePresenceConfig, tickets: &TicketConfig) -> Self {
+        Self {
+            min_length: config.min_length,
+            tickets: tickets.references().clone(),
+        }
     }
 }
 
@@ -46,15 +53,15 @@ impl RuleValidator<Commit> for MessagePresence {
 
         // The message body is only ticket numbers. Return those issues. No need to check for the
         // message length after this.
-        let issues = issues_for_lines_with_only_ticket_numbers(&commit.message);
+        let issues = issues_for_lines_with_only_ticket_numbers(&commit.message, &self.tickets);
         if issues.is_some() {
             return issues;
         }
 
         // Do not count ticket references towards message body length/width
-        width -= ticket_number_reference_length(&commit.message);
+        width -= ticket_number_reference_length(&commit.message, &self.tickets);
 
-        if width < 10 {
+        if width < self.min_length {
             let mut context = vec![];
             let message = commit.message.trim_end();
             let line_length = message.lines().count();
@@ -118,7 +125,10 @@ impl RuleValidator<Commit> for MessagePresence {
     }
 }
 
-fn issues_for_lines_with_only_ticket_numbers(message: &str) -> Option<Vec<Issue>> {
+fn issues_for_lines_with_only_ticket_numbers(
+    message: &str,
+    tickets: &TicketReferences,
+) -> Option<Vec<Issue>> {
     let mut context = vec![];
     let mut ticket_starting_line_number = None;
     let lines = message.lines();
@@ -129,7 +139,7 @@ fn issues_for_lines_with_only_ticket_numbers(message: &str) -> Option<Vec<Issue>
         }
 
         {
-            let capture = scan_for_ticket_number(line)?;
+            let capture = tickets.find_fix_ticket(line)?;
             let line_label = line_number + 2;
             let capture_str = capture.as_str();
             let capture_len = capture_str.len();
@@ -173,22 +183,8 @@ fn issues_for_lines_with_only_ticket_numbers(message: &str) -> Option<Vec<Issue>
     }
 }
 
-// Helper function to cleanly return ticket number captures without having to do the debug logging
-// in the loop.
-fn scan_for_ticket_number(message: &str) -> Option<regex::Match<'_>> {
-    if let Some(captures) = CONTAINS_FIX_TICKET.captures(message) {
-        match captures.get(0) {
-            Some(capture) => return Some(capture),
-            None => {
-                error!("MessagePresence: Unable to fetch ticket number match from message.");
-            }
-        }
-    }
-    None
-}
-
 // Return the length of all ticket number references from the message body.
-fn ticket_number_reference_length(message: &str) -> usize {
+fn ticket_number_reference_length(message: &str, tickets: &TicketReferences) -> usize {
     let mut length = 0;
     let lines = message.lines();
     for line in lines {
@@ -197,7 +193,7 @@ fn ticket_number_reference_length(message: &str) -> usize {
             continue;
         }
 
-        if let Some(capture) = scan_for_ticket_number(line) {
+        if let Some(capture) = tickets.find_fix_ticket(line) {
             let capture_width = display_width(capture.as_str());
             length += capture_width;
         }
@@ -211,7 +207,8 @@ mod tests {
     use crate::test::*;
 
     fn validate(commit: &Commit) -> Option<Vec<Issue>> {
-        MessagePresence::new().validate(commit)
+        MessagePresence::new(&MessagePresenceConfig::default(), &TicketConfig::default())
+            .validate(commit)
     }
 
     #[test]
@@ -391,4 +388,36 @@ mod tests {
                | ^^^^^^^^^^^^^^^^^^^^ Add more detail about the change and why it was made",
         );
     }
+
+    #[test]
+    fn with_configured_min_length() {
+        let validator = MessagePresence::new(
+            &MessagePresenceConfig { min_length: 5 },
+            &TicketConfig::default(),
+        );
+        assert_eq!(validator.validate(&commit("Subject", "\nShort.")), None);
+
+        let validator = MessagePresence::new(
+            &MessagePresenceConfig { min_length: 30 },
+            &TicketConfig::default(),
+        );
+        let issue = first_issue(validator.validate(&commit("Subject", "\nHello I am a message.")));
+        assert_eq!(issue.message, "The message body is too short");
+    }
+
+    #[test]
+    fn with_only_configured_ticket_syntax() {
+        let validator = MessagePresence::new(
+            &MessagePresenceConfig::default(),
+            &ticket_config(TICKET_SYNTAXES),
+        );
+        let issue = first_issue(validator.validate(&commit("Subject", "\nCloses AB#1234\n")));
+        assert_eq!(
+            issue.message,
+            "The message body is only a reference to a ticket number"
+        );
+        // Not a ticket reference of a configured project, so it counts as a message body
+        let issues = validator.validate(&commit("Subject", "\nCloses FOO-123\n"));
+        assert_eq!(issues, None);
+    }
 }
@@ -2,9 +2,10 @@ use core::ops::Range;
 use regex::{Regex, RegexBuilder};
 
 use crate::commit::Commit;
-use crate::issue::{Context, Issue, Position};
+use crate::issue::{Context, Issue, Position, Suggestion};
 use crate::rule::Rule;
 use crate::rule::RuleValidator;
+use crate::rules::message_body_addition;
 
 lazy_static! {
     static ref TEXT_FILES: Regex = {
@@ -74,6 +75,10 @@ impl RuleValidator<Commit> for MessageSkipBuildTag {
             let new_line_count = if line_count == 0 { 3 } else { line_count + 2 };
             let tag = "[skip ci]".to_string();
             let tag_len = tag.len();
+            let suggestion = Suggestion::maybe_incorrect(
+                "Add the skip build tag to the commit message".to_string(),
+                message_body_addition(commit, tag.to_string()),
+            );
             context.push(Context::message_line_addition(
                 new_line_count,
                 tag,
@@ -89,7 +94,8 @@ impl RuleValidator<Commit> for MessageSkipBuildTag {
                     .to_string(),
                 Position::Diff,
                 context,
-            )])
+            )
+            .with_suggestion(suggestion)])
         } else {
             None
         }
@@ -1,29 +1,154 @@
 use core::ops::Range;
-use regex::Regex;
 
 use crate::commit::Commit;
-use crate::issue::{Context, Issue, Position};
+use crate::config::file::{MessageTicketNumberConfig, TicketConfig};
+use crate::issue::{Context, Issue, Position, Suggestion};
 use crate::rule::Rule;
 use crate::rule::RuleValidator;
 
-use crate::rules::{CONTAINS_FIX_TICKET, ISSUE_NUMBER_REFERENCE};
-
-lazy_static! {
-    // Match "Part of #123" and friends
-    static ref LINK_TO_TICKET: Regex = Regex::new(&format!(r"(?xi)
-        (part\sof|part\sof\s(issue|epic|project)|related) # Keywords
-        :? # Optional colon
-        \s+ # Required space
-        {ISSUE_NUMBER_REFERENCE}
-    "
-    )).unwrap();
-}
+use crate::rules::{message_body_addition, TicketReferences};
+use crate::utils::character_count_for_bytes_index;
 
-pub struct MessageTicketNumber {}
+pub struct MessageTicketNumber {
+    required: bool,
+    tickets: TicketReferences,
+    branch_ticket: Option<String>,
+}
 
 impl MessageTicketNumber {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(
+        config: &MessageTicketNumberConfig,
+        tickets: &TicketConfig,
+        branch: Option<&str>,
+    ) -> Self {
+        let tickets = tickets.references().clone();
+        // Only look up the ticket in the branch name when tickets are required
+        let branch_ticket = if config.required {
+            branch
+                .and_then(|name| tickets.find_reference(name))
+                .map(|ticket| ticket.as_str().to_string())
+        } else {
+            None
+        };
+        Self {
+            required: config.required,
+            tickets,
+            branch_ticket,
+        }
+    }
+
+    fn missing_ticket_issue(&self, commit: &Commit) -> Issue {
+        let message = &commit.message;
+        let line_count = message.lines().count() + 1; // + 1 for subject
+        let last_line = if line_count == 1 {
+            commit.subject.to_string()
+        } else {
+            message.lines().last().unwrap_or("").to_string()
+        };
+        let (issue_message, reference, hint) = match &self.branch_ticket {
+            Some(ticket) => (
+                format!(
+                    "The message body does not contain a reference to the branch's ticket {}",
+                    ticket
+                ),
+                format!("Part of {}", ticket),
+                "Add a reference to the ticket of the branch",
+            ),
+            None => (
+                "The message body does not contain a ticket or issue number".to_string(),
+                "Fixes #123".to_string(),
+                "Consider adding a reference to a ticket or issue",
+            ),
+        };
+        let context = vec![
+            Context::message_line(line_count, last_line),
+            // Add empty line for spacing
+            Context::message_line(line_count + 1, "".to_string()),
+            // Suggestion because it indicates a suggested change?
+            Context::message_line_addition(
+                line_count + 2,
+                reference.to_string(),
+                Range {
+                    start: 0,
+                    end: reference.len(),
+                },
+                hint.to_string(),
+            ),
+        ];
+        let position = Position::MessageLine {
+            line: line_count + 2,
+            column: 1,
+        };
+        if !self.required {
+            return Issue::hint(Rule::MessageTicketNumber, issue_message, position, context);
+        }
+
+        let issue = Issue::error(Rule::MessageTicketNumber, issue_message, position, context);
+        if self.branch_ticket.is_some() {
+            // Add the reference as a separate paragraph at the end of the message body
+            let mut edits = vec![];
+            if !message.trim().is_empty() {
+                edits.append(&mut message_body_addition(commit, "".to_string()));
+            }
+            edits.append(&mut message_body_addition(commit, reference));
+            issue.with_suggestion(Suggestion::safe(hint.to_string(), edits))
+        } else {
+            issue
+        }
+    }
+
+    // Report every reference to another ticket than the ticket of the branch
+    fn other_ticket_issues(&self, commit: &Commit, branch_ticket: &str) -> Vec<Issue> {
+        let references_branch_ticket = self
+            .tickets
+            .referenced_tickets(&commit.message)
+            .iter()
+            .any(|ticket| ticket.as_str() == branch_ticket);
+        let mut issues = vec![];
+        for (index, line) in commit.message.lines().enumerate() {
+            let tickets = self.tickets.referenced_tickets(line);
+            for ticket in tickets
+                .iter()
+                .filter(|ticket| ticket.as_str() != branch_ticket)
+            {
+                let line_number = index + 2; // + 1 for subject, + 1 for zero index
+                let (message, hint) = if references_branch_ticket {
+                    (
+                        format!(
+                            "The message body references ticket {} besides the branch's ticket {}",
+                            ticket.as_str(),
+                            branch_ticket
+                        ),
+                        "Only reference the ticket of the branch".to_string(),
+                    )
+                } else {
+                    (
+                        format!(
+                            "The message body references ticket {} instead of the branch's ticket {}",
+                            ticket.as_str(),
+                            branch_ticket
+                        ),
+                        format!("Reference the ticket of the branch: {}", branch_ticket),
+                    )
+                };
+                let context = vec![Context::message_line_error(
+                    line_number,
+                    line.to_string(),
+                    ticket.range(),
+                    hint,
+                )];
+                issues.push(Issue::error(
+                    Rule::MessageTicketNumber,
+                    message,
+                    Position::MessageLine {
+                        line: line_number,
+                        column: character_count_for_bytes_index(line, ticket.start()),
+                    },
+                    context,
+                ));
+            }
+        }
+        issues
     }
 }
 
@@ -34,49 +159,33 @@ impl RuleValidator<Commit> for MessageTicketNumber {
         }
 
         let message = &commit.message.to_string();
-        if CONTAINS_FIX_TICKET.captures(message).is_none()
-            && LINK_TO_TICKET.captures(message).is_none()
-        {
-            let line_count = message.lines().count() + 1; // + 1 for subject
-            let last_line = if line_count == 1 {
-                commit.subject.to_string()
-            } else {
-                message.lines().last().unwrap_or("").to_string()
-            };
-            let context = vec![
-                Context::message_line(line_count, last_line),
-                // Add empty line for spacing
-                Context::message_line(line_count + 1, "".to_string()),
-                // Suggestion because it indicates a suggested change?
-                Context::message_line_addition(
-                    line_count + 2,
-                    "Fixes #123".to_string(),
-                    Range { start: 0, end: 10 },
-                    "Consider adding a reference to a ticket or issue".to_string(),
-                ),
-            ];
-            Some(vec![Issue::hint(
-                Rule::MessageTicketNumber,
-                "The message body does not contain a ticket or issue number".to_string(),
-                Position::MessageLine {
-                    line: line_count + 2,
-                    column: 1,
-                },
-                context,
-            )])
-        } else {
-            None
+        let tickets = self.tickets.referenced_tickets(message);
+        if tickets.is_empty() {
+            return Some(vec![self.missing_ticket_issue(commit)]);
         }
+        if let Some(branch_ticket) = &self.branch_ticket {
+            let issues = self.other_ticket_issues(commit, branch_ticket);
+            if !issues.is_empty() {
+                return Some(issues);
+            }
+        }
+        None
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::issue::{Edit, IssueType};
     use crate::test::*;
 
     fn validate(commit: &Commit) -> Option<Vec<Issue>> {
-        MessageTicketNumber::new().validate(commit)
+        MessageTicketNumber::new(
+            &MessageTicketNumberConfig::default(),
+            &TicketConfig::default(),
+            None,
+        )
+        .validate(commit)
     }
 
     fn assert_valid(message: &str) {
@@ -210,4 +319,174 @@ mod tests {
         let issues = validate(&commit);
         assert_eq!(issues, None);
     }
+
+    #[test]
+    fn configured_ticket_syntaxes() {
+        let validator = MessageTicketNumber::new(
+            &MessageTicketNumberConfig::default(),
+            &ticket_config(TICKET_SYNTAXES),
+            None,
+        );
+        for message in ["Fixes AB#1234", "Part of TKT:00042", "Closes ENG-123"] {
+            let commit = commit("Subject", &format!("\nSome explanation.\n\n{}", message));
+            assert_eq!(validator.validate(&commit), None);
+        }
+        for message in ["Fixes FOO-123", "Part of TKT:42", "Closes org/repo#123"] {
+            let commit = commit("Subject", &format!("\nSome explanation.\n\n{}", message));
+            assert!(validator.validate(&commit).is_some());
+        }
+    }
+
+    fn validate_required(commit: &Commit, branch: Option<&str>) -> Option<Vec<Issue>> {
+        MessageTicketNumber::new(
+            &MessageTicketNumberConfig { required: true },
+            &ticket_config(TICKET_SYNTAXES),
+            branch,
+        )
+        .validate(commit)
+    }
+
+    #[test]
+    fn required_ticket_number() {
+        let issue = first_issue(validate_required(
+            &commit("Subject", "\nSome explanation."),
+            Some("fix-bug"),
+        ));
+        assert_eq!(issue.r#type, IssueType::Error);
+        assert_eq!(
+            issue.message,
+            "The message body does not contain a ticket or issue number"
+        );
+        assert!(issue.suggestions.is_empty());
+
+        let valid = commit("Subject", "\nSome explanation.\n\nFixes #123");
+        assert_eq!(validate_required(&valid, Some("fix-bug")), None);
+        assert_eq!(validate_required(&valid, None), None);
+    }
+
+    #[test]
+    fn required_ticket_number_of_branch() {
+        let branch = Some("feature/ENG-123-login");
+        let issue = first_issue(validate_required(
+            &commit("Subject", "\nSome explanation."),
+            branch,
+        ));
+        assert_eq!(issue.r#type, IssueType::Error);
+        assert_eq!(
+            issue.message,
+            "The message body does not contain a reference to the branch's ticket ENG-123"
+        );
+        assert_eq!(issue.position, message_position(5, 1));
+        assert_contains_issue_output(
+            &issue,
+            "3 | Some explanation.\n\
+             4 | \n\
+             5 | Part of ENG-123\n\
+               | +++++++++++++++ Add a reference to the ticket of the branch",
+        );
+        assert_eq!(
+            issue.suggestions,
+            vec![Suggestion::safe(
+                "Add a reference to the ticket of the branch".to_string(),
+                vec![
+                    Edit::InsertLine {
+                        line: 4,
+                        text: "".to_string(),
+                    },
+                    Edit::InsertLine {
+                        line: 4,
+                        text: "Part of ENG-123".to_string(),
+                    },
+                ],
+            )]
+        );
+
+        let valid = commit("Subject", "\nSome explanation.\n\nPart of ENG-123");
+        assert_eq!(validate_required(&valid, branch), None);
+    }
+
+    #[test]
+    fn required_ticket_number_of_branch_without_message() {
+        let issue = first_issue(validate_required(
+            &commit("Subject", ""),
+            Some("ENG-123-login"),
+        ));
+        assert_eq!(
+            issue.suggestions[0].edits,
+            vec![
+                Edit::InsertLine {
+                    line: 2,
+                    text: "".to_string(),
+                },
+                Edit::InsertLine {
+                    line: 2,
+                    text: "Part of ENG-123".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn other_ticket_number_than_branch() {
+        let issue = first_issue(validate_required(
+            &commit("Subject", "\nSome explanation.\n\nFixes ENG-124"),
+            Some("feature/ENG-123-login"),
+        ));
+        assert_eq!(issue.r#type, IssueType::Error);
+        assert_eq!(
+            issue.message,
+            "The message body references ticket ENG-124 instead of the branch's ticket ENG-123"
+        );
+        assert_eq!(issue.position, message_position(5, 7));
+        assert_contains_issue_output(
+            &issue,
+            "5 | Fixes ENG-124\n\
+               |       ^^^^^^^ Reference the ticket of the branch: ENG-123",
+        );
+    }
+
+    #[test]
+    fn other_ticket_numbers_besides_branch() {
+        let issues = validate_required(
+            &commit(
+                "Subject",
+                "\nSome explanation.\n\nFixes ENG-124\nPart of ENG-123, related ENG-125",
+            ),
+            Some("feature/ENG-123-login"),
+        )
+        .expect("No issues found");
+        assert_eq!(issues.len(), 2);
+        assert_eq!(
+            issues[0].message,
+            "The message body references ticket ENG-124 besides the branch's ticket ENG-123"
+        );
+        assert_eq!(issues[0].position, message_position(5, 7));
+        assert_contains_issue_output(
+            &issues[0],
+            "5 | Fixes ENG-124\n\
+               |       ^^^^^^^ Only reference the ticket of the branch",
+        );
+        assert_eq!(
+            issues[1].message,
+            "The message body references ticket ENG-125 besides the branch's ticket ENG-123"
+        );
+        assert_eq!(issues[1].position, message_position(6, 26));
+    }
+
+    #[test]
+    fn branch_ticket_number_when_not_required() {
+        let validator = MessageTicketNumber::new(
+            &MessageTicketNumberConfig::default(),
+            &TicketConfig::default(),
+            Some("feature/ENG-123-login"),
+        );
+        let issue = first_issue(validator.validate(&commit("Subject", "\nSome explanation.")));
+        assert_eq!(issue.r#type, IssueType::Hint);
+        assert_eq!(
+            issue.message,
+            "The message body does not contain a ticket or issue number"
+        );
+        let other_ticket = commit("Subject", "\nSome explanation.\n\nFixes ENG-124");
+        assert_eq!(validator.validate(&other_ticket), None);
+    }
 }
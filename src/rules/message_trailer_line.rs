@@ -2,7 +2,7 @@ use core::ops::Range;
 use regex::Regex;
 
 use crate::commit::Commit;
-use crate::issue::{Context, Issue, Position};
+use crate::issue::{Context, Edit, Issue, Position, Suggestion};
 use crate::rule::Rule;
 use crate::rule::RuleValidator;
 
@@ -29,6 +29,9 @@ impl RuleValidator<Commit> for MessageTrailerLine {
 
         let mut context = vec![];
         let mut context_additions = vec![];
+        let mut edits = vec![];
+        // The trailers can only be moved safely if they are on a line of their own
+        let mut whole_lines = true;
         // Track where the first co-authored-by line was found that is not in the right place
         let mut first_line_issue_occurrence = None;
         // Track where the last issue occurred
@@ -76,6 +79,16 @@ impl RuleValidator<Commit> for MessageTrailerLine {
                     type_capture.as_str().to_lowercase(),
                     full_capture.range(),
                 ));
+                if full_capture.end() == line.trim_end().len() {
+                    edits.push(Edit::RemoveLine { line: line_number });
+                } else {
+                    whole_lines = false;
+                    edits.push(Edit::Replace {
+                        line: line_number,
+                        range: full_capture.range(),
+                        text: "".to_string(),
+                    });
+                }
                 last_issue_occurrence = Some(line_index);
             }
         }
@@ -92,6 +105,9 @@ impl RuleValidator<Commit> for MessageTrailerLine {
         if commit.trailers.is_empty() {
             // Add new trailer line, which is an empty line, because none was found
             new_last_line += 1;
+            edits.push(Edit::AppendLine {
+                text: "".to_string(),
+            });
             context.push(Context::message_line_addition(
                 new_last_line,
                 "".to_string(),
@@ -107,6 +123,9 @@ impl RuleValidator<Commit> for MessageTrailerLine {
         // were found.
         for (line, trailer_type, range) in context_additions.drain(..) {
             new_last_line += 1;
+            edits.push(Edit::AppendLine {
+                text: line[range.clone()].to_string(),
+            });
             context.push(Context::message_line_addition(
                 new_last_line,
                 line.to_string(),
@@ -118,6 +137,12 @@ impl RuleValidator<Commit> for MessageTrailerLine {
             ));
         }
 
+        let suggestion_message = "Move the trailer lines to the end of the message".to_string();
+        let suggestion = if whole_lines {
+            Suggestion::safe(suggestion_message, edits)
+        } else {
+            Suggestion::maybe_incorrect(suggestion_message, edits)
+        };
         Some(vec![Issue::error(
             Rule::MessageTrailerLine,
             "Trailer line is not at the end of the message body".to_string(),
@@ -126,7 +151,8 @@ impl RuleValidator<Commit> for MessageTrailerLine {
                 column: 1,
             },
             context,
-        )])
+        )
+        .with_suggestion(suggestion)])
     }
 }
 
@@ -306,4 +332,59 @@ mod tests {
                | +++++++++++++++++++++++++++++++++++++++ Move helped-by reference to the end of the message body",
         );
     }
+
+    #[test]
+    fn suggestion_moves_trailer_lines() {
+        let commit = commit(
+            "Subject",
+            "\n\
+            Co-authored-by: Person A <other@example.com>\n\
+            Some other line at the end.",
+        );
+        let issue = first_issue(validate(&commit));
+        assert_eq!(
+            issue.suggestions,
+            vec![Suggestion::safe(
+                "Move the trailer lines to the end of the message".to_string(),
+                vec![
+                    Edit::RemoveLine { line: 3 },
+                    Edit::AppendLine {
+                        text: "".to_string()
+                    },
+                    Edit::AppendLine {
+                        text: "Co-authored-by: Person A <other@example.com>".to_string()
+                    },
+                ],
+            )]
+        );
+    }
+
+    #[test]
+    fn suggestion_with_text_after_trailer() {
+        let commit = commit(
+            "Subject",
+            "\n\
+            Co-authored-by: Person A <other@example.com> and some text.",
+        );
+        let issue = first_issue(validate(&commit));
+        assert_eq!(
+            issue.suggestions,
+            vec![Suggestion::maybe_incorrect(
+                "Move the trailer lines to the end of the message".to_string(),
+                vec![
+                    Edit::Replace {
+                        line: 3,
+                        range: Range { start: 0, end: 44 },
+                        text: "".to_string()
+                    },
+                    Edit::AppendLine {
+                        text: "".to_string()
+                    },
+                    Edit::AppendLine {
+                        text: "Co-authored-by: Person A <other@example.com>".to_string()
+                    },
+                ],
+            )]
+        );
+    }
 }
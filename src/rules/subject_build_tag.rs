@@ -2,9 +2,10 @@ use core::ops::Range;
 use regex::{Regex, RegexBuilder};
 
 use crate::commit::Commit;
-use crate::issue::{Context, Issue, Position};
+use crate::issue::{Context, Edit, Issue, Position, Suggestion};
 use crate::rule::Rule;
 use crate::rule::RuleValidator;
+use crate::rules::message_body_addition;
 use crate::utils::character_count_for_bytes_index;
 
 lazy_static! {
@@ -48,6 +49,10 @@ impl RuleValidator<Commit> for SubjectBuildTag {
                     "Move build tag to message body".to_string(),
                 ),
             ];
+            let mut edits = vec![Edit::subject_removal(subject, tag.range())];
+            edits.append(&mut message_body_addition(commit, tag.as_str().to_string()));
+            let suggestion =
+                Suggestion::maybe_incorrect("Move build tag to message body".to_string(), edits);
             Some(vec![Issue::error(
                 Rule::SubjectBuildTag,
                 format!("The `{}` build tag was found in the subject", tag.as_str()),
@@ -56,7 +61,8 @@ impl RuleValidator<Commit> for SubjectBuildTag {
                     column: character_count_for_bytes_index(&commit.subject, tag.start()),
                 },
                 context,
-            )])
+            )
+            .with_suggestion(suggestion)])
         } else {
             None
         }
@@ -140,5 +146,26 @@ mod tests {
              3 | [skip ci]\n\
                | +++++++++ Move build tag to message body",
         );
+        assert_eq!(
+            issue.suggestions,
+            vec![Suggestion::maybe_incorrect(
+                "Move build tag to message body".to_string(),
+                vec![
+                    Edit::Replace {
+                        line: 1,
+                        range: Range { start: 14, end: 24 },
+                        text: "".to_string(),
+                    },
+                    Edit::InsertLine {
+                        line: 2,
+                        text: "".to_string(),
+                    },
+                    Edit::InsertLine {
+                        line: 2,
+                        text: "[skip ci]".to_string(),
+                    },
+                ],
+            )]
+        );
     }
 }
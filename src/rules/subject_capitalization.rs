@@ -2,7 +2,7 @@ use core::ops::Range;
 use regex::Regex;
 
 use crate::commit::Commit;
-use crate::issue::{Context, Issue, Position};
+use crate::issue::{Context, Edit, Issue, Position, Suggestion};
 use crate::rule::Rule;
 use crate::rule::RuleValidator;
 
@@ -41,6 +41,17 @@ impl RuleValidator<Commit> for SubjectCapitalization {
                     }
 
                     let char_len = character.len_utf8();
+                    let suggestion = Suggestion::safe(
+                        "Start the subject with a capital letter".to_string(),
+                        vec![Edit::Replace {
+                            line: 1,
+                            range: Range {
+                                start: 0,
+                                end: char_len,
+                            },
+                            text: character.to_uppercase().to_string(),
+                        }],
+                    );
                     let context = vec![
                         Context::subject_removal_suggestion(
                             commit.subject.to_string(),
@@ -64,7 +75,8 @@ impl RuleValidator<Commit> for SubjectCapitalization {
                         "The subject does not start with a capital letter".to_string(),
                         Position::Subject { line: 1, column: 1 },
                         context,
-                    )])
+                    )
+                    .with_suggestion(suggestion)])
                 } else {
                     None
                 }
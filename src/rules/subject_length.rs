@@ -1,16 +1,23 @@
 use core::ops::Range;
 
 use crate::commit::Commit;
+use crate::config::file::SubjectLengthConfig;
 use crate::issue::{Context, Issue, Position};
 use crate::rule::Rule;
 use crate::rule::RuleValidator;
-use crate::utils::line_length_stats;
+use crate::utils::{character_count_for_bytes_index, line_length_stats};
 
-pub struct SubjectLength {}
+pub struct SubjectLength {
+    max_width: usize,
+    min_width: usize,
+}
 
 impl SubjectLength {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(config: &SubjectLengthConfig) -> Self {
+        Self {
+            max_width: config.max_width,
+            min_width: config.min_width,
+        }
     }
 }
 
@@ -20,48 +27,60 @@ impl RuleValidator<Commit> for SubjectLength {
             return None;
         }
 
-        let (width, line_stats) = line_length_stats(&commit.subject, 50);
+        let (offset, subject) = commit.subject_description();
+        // Column of the first character of the description in the subject, minus one.
+        let column_offset = character_count_for_bytes_index(&commit.subject, offset) - 1;
+        let (width, line_stats) = line_length_stats(subject, self.max_width);
 
         match width {
             0 => {
                 let context = Context::subject_addition_suggestion(
                     commit.subject.to_string(),
-                    Range { start: 0, end: 3 },
+                    Range {
+                        start: offset,
+                        end: offset + 3,
+                    },
                     "Add a subject to describe the change".to_string(),
                 );
                 Some(vec![Issue::error(
                     Rule::SubjectLength,
                     "The commit has no subject".to_string(),
-                    Position::Subject { line: 1, column: 1 },
+                    Position::Subject {
+                        line: 1,
+                        column: column_offset + 1,
+                    },
                     vec![context],
                 )])
             }
-            x if x > 50 => {
+            x if x > self.max_width => {
                 let total_width_index = commit.subject.len();
                 let context = Context::subject_error(
                     commit.subject.to_string(),
                     Range {
-                        start: line_stats.bytes_index,
+                        start: offset + line_stats.bytes_index,
                         end: total_width_index,
                     },
-                    "Shorten the subject to a maximum width of 50 characters".to_string(),
+                    format!(
+                        "Shorten the subject to a maximum width of {} characters",
+                        self.max_width
+                    ),
                 );
                 Some(vec![Issue::error(
                     Rule::SubjectLength,
                     format!("The subject of `{}` characters wide is too long", width),
                     Position::Subject {
                         line: 1,
-                        column: line_stats.char_count + 1,
+                        column: column_offset + line_stats.char_count + 1,
                     },
                     vec![context],
                 )])
             }
-            x if x < 5 => {
+            x if x < self.min_width => {
                 let total_width_index = commit.subject.len();
                 let context = Context::subject_error(
                     commit.subject.to_string(),
                     Range {
-                        start: 0,
+                        start: offset,
                         end: total_width_index,
                     },
                     "Describe the change in more detail".to_string(),
@@ -69,7 +88,10 @@ impl RuleValidator<Commit> for SubjectLength {
                 Some(vec![Issue::error(
                     Rule::SubjectLength,
                     format!("The subject of `{}` characters wide is too short", width),
-                    Position::Subject { line: 1, column: 1 },
+                    Position::Subject {
+                        line: 1,
+                        column: column_offset + 1,
+                    },
                     vec![context],
                 )])
             }
@@ -85,7 +107,19 @@ mod tests {
     use crate::test::*;
 
     fn validate(commit: &Commit) -> Option<Vec<Issue>> {
-        SubjectLength::new().validate(commit)
+        SubjectLength::new(&SubjectLengthConfig::default()).validate(commit)
+    }
+
+    fn validate_with_config(
+        commit: &Commit,
+        max_width: usize,
+        min_width: usize,
+    ) -> Option<Vec<Issue>> {
+        SubjectLength::new(&SubjectLengthConfig {
+            max_width,
+            min_width,
+        })
+        .validate(commit)
     }
 
     fn assert_subject_as_valid(subject: &str) {
@@ -218,4 +252,66 @@ mod tests {
                |                                                   ^^ Shorten the subject to a maximum width of 50 characters",
         );
     }
+
+    #[test]
+    fn with_configured_lengths() {
+        assert_eq!(
+            validate_with_config(&commit("a".repeat(60), "".to_string()), 60, 5),
+            None
+        );
+        assert_eq!(validate_with_config(&commit("Add", ""), 50, 3), None);
+
+        let issue = first_issue(validate_with_config(
+            &commit("a".repeat(61), "".to_string()),
+            60,
+            5,
+        ));
+        assert_eq!(
+            issue.message,
+            "The subject of `61` characters wide is too long"
+        );
+        assert_eq!(issue.position, subject_position(61));
+        assert_contains_issue_output(
+            &issue,
+            &format!(
+                "1 | {}\n\
+                   | {}^ Shorten the subject to a maximum width of 60 characters",
+                "a".repeat(61),
+                " ".repeat(60)
+            ),
+        );
+    }
+
+    #[test]
+    fn with_conventional_commit_description() {
+        assert_eq!(
+            validate(&conventional_commit(
+                format!("feat(api): {}", "a".repeat(50)),
+                "".to_string()
+            )),
+            None
+        );
+
+        let issue = first_issue(validate(&conventional_commit(
+            format!("feat(api): {}", "a".repeat(51)),
+            "".to_string(),
+        )));
+        assert_eq!(
+            issue.message,
+            "The subject of `51` characters wide is too long"
+        );
+        assert_eq!(issue.position, subject_position(62));
+
+        let issue = first_issue(validate(&conventional_commit("fix: Add", "")));
+        assert_eq!(
+            issue.message,
+            "The subject of `3` characters wide is too short"
+        );
+        assert_eq!(issue.position, subject_position(6));
+        assert_contains_issue_output(
+            &issue,
+            "1 | fix: Add\n\
+               |      ^^^ Describe the change in more detail",
+        );
+    }
 }
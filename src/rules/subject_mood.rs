@@ -1,101 +1,537 @@
 use core::ops::Range;
+use regex::Regex;
+use std::collections::BTreeMap;
 
 use crate::commit::Commit;
-use crate::issue::{Context, Issue, Position};
+use crate::config::file::SubjectMoodConfig;
+use crate::issue::{Context, Edit, Issue, Position, Suggestion};
 use crate::rule::Rule;
 use crate::rule::RuleValidator;
+use crate::utils::character_count_for_bytes_index;
 
-const MOOD_WORDS: [&str; 41] = [
-    "fixed",
-    "fixes",
-    "fixing",
-    "solved",
-    "solves",
-    "solving",
-    "resolved",
-    "resolves",
-    "resolving",
-    "closed",
-    "closes",
-    "closing",
-    "added",
-    "adding",
-    "updated",
-    "updates",
-    "updating",
-    "removed",
-    "removes",
-    "removing",
-    "deleted",
-    "deletes",
-    "deleting",
-    "changed",
-    "changes",
-    "changing",
-    "moved",
-    "moves",
-    "moving",
-    "refactored",
-    "refactors",
-    "refactoring",
-    "checked",
-    "checks",
-    "checking",
-    "adjusted",
-    "adjusts",
-    "adjusting",
-    "tests",
-    "tested",
-    "testing",
+lazy_static! {
+    // Tags at the start of the subject, like `[Api]`, are skipped to find the first word
+    static ref SUBJECT_TAGS: Regex = Regex::new(r"^(\[[^\]]*\]\s*)+").unwrap();
+}
+
+// Verbs in the imperative mood that are commonly used in commit subjects. The other forms of these
+// verbs are detected and the imperative form is suggested.
+const VERBS: [&str; 166] = [
+    "accept",
+    "add",
+    "adjust",
+    "align",
+    "allow",
+    "apply",
+    "archive",
+    "assert",
+    "avoid",
+    "bump",
+    "cache",
+    "call",
+    "change",
+    "check",
+    "clarify",
+    "clean",
+    "clear",
+    "close",
+    "collect",
+    "combine",
+    "comment",
+    "commit",
+    "compile",
+    "configure",
+    "connect",
+    "convert",
+    "copy",
+    "correct",
+    "create",
+    "debug",
+    "decrease",
+    "default",
+    "define",
+    "delete",
+    "deploy",
+    "deprecate",
+    "describe",
+    "detect",
+    "disable",
+    "display",
+    "document",
+    "downgrade",
+    "drop",
+    "edit",
+    "embed",
+    "emit",
+    "enable",
+    "enforce",
+    "ensure",
+    "exclude",
+    "expand",
+    "explain",
+    "export",
+    "expose",
+    "extend",
+    "extract",
+    "fetch",
+    "filter",
+    "finish",
+    "fix",
+    "format",
+    "generate",
+    "handle",
+    "hide",
+    "ignore",
+    "implement",
+    "import",
+    "improve",
+    "include",
+    "increase",
+    "initialize",
+    "inline",
+    "insert",
+    "install",
+    "integrate",
+    "introduce",
+    "invert",
+    "limit",
+    "link",
+    "lint",
+    "load",
+    "lock",
+    "log",
+    "look",
+    "lower",
+    "make",
+    "mark",
+    "merge",
+    "migrate",
+    "mock",
+    "modify",
+    "move",
+    "normalize",
+    "open",
+    "optimize",
+    "output",
+    "parse",
+    "pass",
+    "patch",
+    "pin",
+    "polish",
+    "prefer",
+    "prepare",
+    "prevent",
+    "print",
+    "process",
+    "provide",
+    "publish",
+    "pull",
+    "push",
+    "raise",
+    "read",
+    "rebase",
+    "redirect",
+    "reduce",
+    "refactor",
+    "refresh",
+    "register",
+    "release",
+    "reload",
+    "remove",
+    "rename",
+    "reorder",
+    "replace",
+    "report",
+    "request",
+    "require",
+    "reset",
+    "resolve",
+    "restore",
+    "restrict",
+    "retry",
+    "return",
+    "reuse",
+    "revert",
+    "rework",
+    "run",
+    "save",
+    "search",
+    "select",
+    "send",
+    "separate",
+    "set",
+    "show",
+    "simplify",
+    "skip",
+    "solve",
+    "sort",
+    "specify",
+    "split",
+    "start",
+    "stop",
+    "store",
+    "support",
+    "switch",
+    "sync",
+    "test",
+    "track",
+    "tweak",
+    "update",
+    "upgrade",
+    "use",
+    "validate",
+    "verify",
+    "wrap",
+    "write",
+];
+
+// Irregular verb forms that are not in the imperative mood, with their imperative form
+const IRREGULAR_VERBS: [(&str, &str); 76] = [
+    ("began", "begin"),
+    ("begun", "begin"),
+    ("bent", "bend"),
+    ("broke", "break"),
+    ("broken", "break"),
+    ("brought", "bring"),
+    ("built", "build"),
+    ("bought", "buy"),
+    ("caught", "catch"),
+    ("chose", "choose"),
+    ("chosen", "choose"),
+    ("dealt", "deal"),
+    ("did", "do"),
+    ("does", "do"),
+    ("done", "do"),
+    ("drew", "draw"),
+    ("drawn", "draw"),
+    ("drove", "drive"),
+    ("driven", "drive"),
+    ("dying", "die"),
+    ("fed", "feed"),
+    ("felt", "feel"),
+    ("fought", "fight"),
+    ("forgot", "forget"),
+    ("forgotten", "forget"),
+    ("found", "find"),
+    ("froze", "freeze"),
+    ("frozen", "freeze"),
+    ("gave", "give"),
+    ("given", "give"),
+    ("goes", "go"),
+    ("gone", "go"),
+    ("got", "get"),
+    ("gotten", "get"),
+    ("grew", "grow"),
+    ("grown", "grow"),
+    ("had", "have"),
+    ("has", "have"),
+    ("held", "hold"),
+    ("hid", "hide"),
+    ("hidden", "hide"),
+    ("kept", "keep"),
+    ("knew", "know"),
+    ("led", "lead"),
+    ("lost", "lose"),
+    ("lying", "lie"),
+    ("made", "make"),
+    ("meant", "mean"),
+    ("overrode", "override"),
+    ("overridden", "override"),
+    ("paid", "pay"),
+    ("ran", "run"),
+    ("rebuilt", "rebuild"),
+    ("redid", "redo"),
+    ("rewrote", "rewrite"),
+    ("rewritten", "rewrite"),
+    ("said", "say"),
+    ("seen", "see"),
+    ("sent", "send"),
+    ("shown", "show"),
+    ("sought", "seek"),
+    ("spent", "spend"),
+    ("stood", "stand"),
+    ("stuck", "stick"),
+    ("taken", "take"),
+    ("taught", "teach"),
+    ("thought", "think"),
+    ("threw", "throw"),
+    ("thrown", "throw"),
+    ("took", "take"),
+    ("tying", "tie"),
+    ("understood", "understand"),
+    ("undid", "undo"),
+    ("undone", "undo"),
+    ("went", "go"),
+    ("wrote", "write"),
 ];
 
-pub struct SubjectMood {}
+// Words that end like a verb form that is not in the imperative mood, but are not verbs
+const EXCEPTIONS: [&str; 17] = [
+    "anything",
+    "ceiling",
+    "during",
+    "evening",
+    "everything",
+    "hatred",
+    "heading",
+    "hundred",
+    "kindred",
+    "morning",
+    "naked",
+    "nothing",
+    "padding",
+    "sacred",
+    "something",
+    "spacing",
+    "wording",
+];
+
+// The mood of the first word of the subject
+#[derive(Debug, PartialEq)]
+enum Mood {
+    Imperative,
+    // A verb form that is not in the imperative mood, with the imperative form if it is known
+    NonImperative(Option<String>),
+}
+
+pub struct SubjectMood {
+    verbs: Vec<String>,
+    irregular_verbs: BTreeMap<String, String>,
+    exceptions: Vec<String>,
+}
 
 impl SubjectMood {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(config: &SubjectMoodConfig) -> Self {
+        Self {
+            verbs: config.verbs.iter().map(|v| v.to_lowercase()).collect(),
+            irregular_verbs: config
+                .irregular_verbs
+                .iter()
+                .map(|(form, verb)| (form.to_lowercase(), verb.to_lowercase()))
+                .collect(),
+            exceptions: config.exceptions.iter().map(|e| e.to_lowercase()).collect(),
+        }
+    }
+
+    fn is_verb(&self, word: &str) -> bool {
+        VERBS.contains(&word) || self.verbs.iter().any(|verb| verb == word)
+    }
+
+    fn is_exception(&self, word: &str) -> bool {
+        EXCEPTIONS.contains(&word) || self.exceptions.iter().any(|exception| exception == word)
+    }
+
+    fn irregular_verb(&self, word: &str) -> Option<String> {
+        if let Some(verb) = self.irregular_verbs.get(word) {
+            return Some(verb.to_string());
+        }
+        IRREGULAR_VERBS
+            .iter()
+            .find(|(form, _)| *form == word)
+            .map(|(_, verb)| verb.to_string())
+    }
+
+    // Returns the first candidate that is a known verb
+    fn known_verb(&self, candidates: Vec<String>) -> Option<String> {
+        candidates
+            .into_iter()
+            .find(|candidate| self.is_verb(candidate))
+    }
+
+    // Detect the mood of the lowercase word by its suffix. Words ending in `-ed` and `-ing` are
+    // not in the imperative mood, unless they are known verbs or exceptions, like "embed" and
+    // "nothing". Words ending in `-s` can also be plural nouns, so they are only reported if the
+    // word without the suffix is a known verb, like "adds".
+    fn mood(&self, word: &str) -> Mood {
+        if self.is_exception(word) || self.is_verb(word) {
+            return Mood::Imperative;
+        }
+        if let Some(verb) = self.irregular_verb(word) {
+            return Mood::NonImperative(Some(verb));
+        }
+        if let Some(stem) = word.strip_suffix("ing") {
+            if has_vowel(stem) {
+                let mut candidates = vec![stem.to_string(), format!("{}e", stem)];
+                candidates.extend(without_double_consonant(stem));
+                return Mood::NonImperative(self.known_verb(candidates));
+            }
+        }
+        if let Some(stem) = word.strip_suffix("ed") {
+            let mut candidates = vec![format!("{}e", stem), stem.to_string()];
+            candidates.extend(without_double_consonant(stem));
+            if let Some(stem) = stem.strip_suffix('i') {
+                candidates.push(format!("{}y", stem));
+            }
+            let verb = self.known_verb(candidates);
+            // Words ending in `-eed` are often base forms, like "need" and "proceed"
+            if verb.is_some() || (has_vowel(stem) && !stem.ends_with('e')) {
+                return Mood::NonImperative(verb);
+            }
+        }
+        if let Some(stem) = word.strip_suffix('s') {
+            if stem.ends_with('s') || stem.ends_with('u') || stem.ends_with('i') {
+                return Mood::Imperative;
+            }
+            let mut candidates = vec![stem.to_string()];
+            if let Some(stem) = stem.strip_suffix('e') {
+                candidates.push(stem.to_string());
+                if let Some(stem) = stem.strip_suffix('i') {
+                    candidates.push(format!("{}y", stem));
+                }
+            }
+            if let Some(verb) = self.known_verb(candidates) {
+                return Mood::NonImperative(Some(verb));
+            }
+        }
+        Mood::Imperative
     }
 }
 
 impl RuleValidator<Commit> for SubjectMood {
     fn validate(&self, commit: &Commit) -> Option<Vec<Issue>> {
-        match commit.subject.split(' ').next() {
-            Some(raw_word) => {
-                let word = raw_word.to_lowercase();
-                if MOOD_WORDS.contains(&word.as_str()) {
-                    let context = vec![Context::subject_error(
-                        commit.subject.to_string(),
-                        Range {
-                            start: 0,
-                            end: word.len(),
-                        },
-                        "Use the imperative mood for the subject".to_string(),
-                    )];
-                    Some(vec![Issue::error(
-                        Rule::SubjectMood,
-                        "The subject does not use the imperative grammatical mood".to_string(),
-                        Position::Subject { line: 1, column: 1 },
-                        context,
-                    )])
-                } else {
-                    None
-                }
-            }
+        let (description_offset, description) = commit.subject_description();
+        let tags_length = SUBJECT_TAGS.find(description).map_or(0, |tags| tags.end());
+        let offset = description_offset + tags_length;
+        let raw_word = match description[tags_length..].split(' ').next() {
+            Some(raw_word) => raw_word,
             None => {
                 error!("SubjectMood validation failure: No first word found of commit subject.");
-                None
+                return None;
             }
+        };
+        // Only words of letters are checked, not words with punctuation, numbers or symbols
+        if raw_word.is_empty() || !raw_word.chars().all(|c| c.is_ascii_alphabetic()) {
+            return None;
+        }
+        let verb = match self.mood(&raw_word.to_lowercase()) {
+            Mood::Imperative => return None,
+            Mood::NonImperative(verb) => verb.map(|verb| with_case_of(&verb, raw_word)),
+        };
+        let range = Range {
+            start: offset,
+            end: offset + raw_word.len(),
+        };
+        let hint = match &verb {
+            Some(verb) => format!("Use `{}` instead of `{}`", verb, raw_word),
+            None => "Use the imperative mood for the subject".to_string(),
+        };
+        let context = vec![Context::subject_error(
+            commit.subject.to_string(),
+            range.clone(),
+            hint.clone(),
+        )];
+        let issue = Issue::error(
+            Rule::SubjectMood,
+            "The subject does not use the imperative grammatical mood".to_string(),
+            Position::Subject {
+                line: 1,
+                column: character_count_for_bytes_index(&commit.subject, offset),
+            },
+            context,
+        );
+        Some(vec![match verb {
+            Some(verb) => issue.with_suggestion(Suggestion::maybe_incorrect(
+                hint,
+                vec![Edit::Replace {
+                    line: 1,
+                    range,
+                    text: verb,
+                }],
+            )),
+            None => issue,
+        }])
+    }
+}
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y')
+}
+
+fn has_vowel(word: &str) -> bool {
+    word.contains(is_vowel)
+}
+
+// The stem without the last consonant if it is doubled, like "stopp" for "stopped"
+fn without_double_consonant(stem: &str) -> Option<String> {
+    let mut chars = stem.chars().rev();
+    match (chars.next(), chars.next()) {
+        (Some(last), Some(previous)) if last == previous && !is_vowel(last) => {
+            Some(stem[..stem.len() - 1].to_string())
+        }
+        _ => None,
+    }
+}
+
+// The verb with the same capitalization as the word it replaces
+fn with_case_of(verb: &str, word: &str) -> String {
+    if word.len() > 1 && word.chars().all(|c| c.is_ascii_uppercase()) {
+        return verb.to_uppercase();
+    }
+    let mut chars = verb.chars();
+    match (word.chars().next(), chars.next()) {
+        (Some(first), Some(verb_first)) if first.is_uppercase() => {
+            verb_first.to_uppercase().collect::<String>() + chars.as_str()
         }
+        _ => verb.to_string(),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::file::Config;
     use crate::test::*;
 
+    const MOOD_WORDS: [&str; 41] = [
+        "fixed",
+        "fixes",
+        "fixing",
+        "solved",
+        "solves",
+        "solving",
+        "resolved",
+        "resolves",
+        "resolving",
+        "closed",
+        "closes",
+        "closing",
+        "added",
+        "adding",
+        "updated",
+        "updates",
+        "updating",
+        "removed",
+        "removes",
+        "removing",
+        "deleted",
+        "deletes",
+        "deleting",
+        "changed",
+        "changes",
+        "changing",
+        "moved",
+        "moves",
+        "moving",
+        "refactored",
+        "refactors",
+        "refactoring",
+        "checked",
+        "checks",
+        "checking",
+        "adjusted",
+        "adjusts",
+        "adjusting",
+        "tests",
+        "tested",
+        "testing",
+    ];
+
     fn validate(commit: &Commit) -> Option<Vec<Issue>> {
-        SubjectMood::new().validate(commit)
+        SubjectMood::new(&SubjectMoodConfig::default()).validate(commit)
+    }
+
+    fn mood(word: &str) -> Mood {
+        SubjectMood::new(&SubjectMoodConfig::default()).mood(word)
     }
 
     fn assert_subject_as_valid(subject: &str) {
@@ -106,10 +542,35 @@ mod tests {
         assert!(validate(&commit(subject, "")).is_some());
     }
 
+    fn assert_imperative_form(word: &str, verb: &str) {
+        assert_eq!(
+            mood(word),
+            Mood::NonImperative(Some(verb.to_string())),
+            "Imperative form of {}",
+            word
+        );
+    }
+
     #[test]
     fn with_valid_subjects() {
         assert_subject_as_valid("Fix test");
         assert_subject_as_valid("Fix tests");
+        for subject in [
+            "Embed video",
+            "Proceed with checkout",
+            "Process payments",
+            "Bring back the menu",
+            "Focus the search field",
+            "Docs for the API",
+            "Settings page",
+            "Nothing to see here",
+            "Tests: add login form",
+            "README update",
+            "Fix-up the fixture",
+            "Ünits fixed",
+        ] {
+            assert_subject_as_valid(subject);
+        }
     }
 
     #[test]
@@ -129,6 +590,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn imperative_forms() {
+        assert_imperative_form("implemented", "implement");
+        assert_imperative_form("introduces", "introduce");
+        assert_imperative_form("bumped", "bump");
+        assert_imperative_form("reverted", "revert");
+        assert_imperative_form("added", "add");
+        assert_imperative_form("updated", "update");
+        assert_imperative_form("used", "use");
+        assert_imperative_form("stopped", "stop");
+        assert_imperative_form("copied", "copy");
+        assert_imperative_form("fixes", "fix");
+        assert_imperative_form("passes", "pass");
+        assert_imperative_form("applies", "apply");
+        assert_imperative_form("adding", "add");
+        assert_imperative_form("creating", "create");
+        assert_imperative_form("running", "run");
+        assert_imperative_form("setting", "set");
+        assert_imperative_form("built", "build");
+        assert_imperative_form("wrote", "write");
+        assert_imperative_form("does", "do");
+    }
+
+    #[test]
+    fn unknown_verb_forms() {
+        assert_eq!(mood("frobnicated"), Mood::NonImperative(None));
+        assert_eq!(mood("frobnicating"), Mood::NonImperative(None));
+        // Words ending in `-s` are only reported for known verbs
+        assert_eq!(mood("frobnicates"), Mood::Imperative);
+        assert_eq!(mood("need"), Mood::Imperative);
+        assert_eq!(mood("string"), Mood::Imperative);
+    }
+
     #[test]
     fn with_cliche_subject() {
         let issue = first_issue(validate(&commit("Fixing bug", "")));
@@ -140,7 +634,89 @@ mod tests {
         assert_contains_issue_output(
             &issue,
             "1 | Fixing bug\n\
-               | ^^^^^^ Use the imperative mood for the subject",
+               | ^^^^^^ Use `Fix` instead of `Fixing`",
+        );
+    }
+
+    #[test]
+    fn with_suggestion() {
+        let issue = first_issue(validate(&commit("Implemented login form", "")));
+        assert_contains_issue_output(
+            &issue,
+            "1 | Implemented login form\n\
+               | ^^^^^^^^^^^ Use `Implement` instead of `Implemented`",
+        );
+        assert_eq!(
+            issue.suggestions,
+            vec![Suggestion::maybe_incorrect(
+                "Use `Implement` instead of `Implemented`".to_string(),
+                vec![Edit::Replace {
+                    line: 1,
+                    range: Range { start: 0, end: 11 },
+                    text: "Implement".to_string()
+                }]
+            )]
+        );
+
+        let issue = first_issue(validate(&commit("Frobnicated the widget", "")));
+        assert_contains_issue_output(
+            &issue,
+            "1 | Frobnicated the widget\n\
+               | ^^^^^^^^^^^ Use the imperative mood for the subject",
+        );
+        assert!(issue.suggestions.is_empty());
+    }
+
+    #[test]
+    fn with_subject_tags() {
+        assert_subject_as_valid("[Api] Add endpoint");
+        let issue = first_issue(validate(&commit("[Api] [WIP] Introduces endpoint", "")));
+        assert_eq!(issue.position, subject_position(13));
+        assert_contains_issue_output(
+            &issue,
+            "1 | [Api] [WIP] Introduces endpoint\n\
+               |             ^^^^^^^^^^ Use `Introduce` instead of `Introduces`",
+        );
+    }
+
+    #[test]
+    fn with_conventional_commit_description() {
+        assert_eq!(
+            validate(&conventional_commit("fix(api): handle empty response", "")),
+            None
+        );
+
+        let issue = first_issue(validate(&conventional_commit(
+            "fix(api): fixed empty response",
+            "",
+        )));
+        assert_eq!(issue.position, subject_position(11));
+        assert_contains_issue_output(
+            &issue,
+            "1 | fix(api): fixed empty response\n\
+               |           ^^^^^ Use `fix` instead of `fixed`",
+        );
+    }
+
+    #[test]
+    fn with_configured_words() {
+        let config = Config::parse(
+            "[rules.SubjectMood]\n\
+            verbs = [\"Frobnicate\"]\n\
+            irregular_verbs = { spun = \"spin\" }\n\
+            exceptions = [\"Updates\"]",
+        )
+        .unwrap();
+        let rule = SubjectMood::new(&config.subject_mood);
+        assert_eq!(
+            rule.mood("frobnicates"),
+            Mood::NonImperative(Some("frobnicate".to_string()))
+        );
+        assert_eq!(
+            rule.mood("spun"),
+            Mood::NonImperative(Some("spin".to_string()))
         );
+        assert_eq!(rule.mood("updates"), Mood::Imperative);
+        assert_eq!(rule.validate(&commit("Updates for the API", "")), None);
     }
 }
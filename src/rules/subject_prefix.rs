@@ -1,7 +1,7 @@
 use regex::Regex;
 
 use crate::commit::Commit;
-use crate::issue::{Context, Issue, Position};
+use crate::issue::{Context, Edit, Issue, Position, Suggestion};
 use crate::rule::Rule;
 use crate::rule::RuleValidator;
 
@@ -34,7 +34,11 @@ impl RuleValidator<Commit> for SubjectPrefix {
                         format!("Remove the `{}` prefix from the subject", capture.as_str()),
                         Position::Subject { line: 1, column: 1 },
                         context,
-                    )])
+                    )
+                    .with_suggestion(Suggestion::maybe_incorrect(
+                        "Remove the prefix from the subject".to_string(),
+                        vec![Edit::subject_removal(subject, capture.range())],
+                    ))])
                 }
                 None => {
                     error!("SubjectPrefix: Unable to fetch prefix capture from subject.");
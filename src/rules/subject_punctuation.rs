@@ -2,7 +2,7 @@ use core::ops::Range;
 use regex::Regex;
 
 use crate::commit::Commit;
-use crate::issue::{Context, Issue, Position};
+use crate::issue::{Context, Edit, Issue, Position, Suggestion};
 use crate::rule::Rule;
 use crate::rule::RuleValidator;
 use crate::utils::{character_count_for_bytes_index, is_punctuation};
@@ -38,41 +38,62 @@ impl RuleValidator<Commit> for SubjectPunctuation {
         }
 
         let mut issues = vec![];
+        let (offset, subject) = commit.subject_description();
+        let column = character_count_for_bytes_index(&commit.subject, offset);
 
-        if let Some(emoji) = SUBJECT_STARTS_WITH_EMOJI.find(&commit.subject) {
+        if let Some(emoji) = SUBJECT_STARTS_WITH_EMOJI.find(subject) {
+            let range = Range {
+                start: offset + emoji.start(),
+                end: offset + emoji.end(),
+            };
             let context = vec![Context::subject_removal_suggestion(
                 commit.subject.to_string(),
-                emoji.range(),
+                range.clone(),
                 "Remove emoji from the start of the subject".to_string(),
             )];
-            issues.push(Issue::error(
-                Rule::SubjectPunctuation,
-                "The subject starts with an emoji".to_string(),
-                Position::Subject { line: 1, column: 1 },
-                context,
-            ));
+            let suggestion = Suggestion::safe(
+                "Remove emoji from the start of the subject".to_string(),
+                vec![Edit::subject_removal(&commit.subject, range)],
+            );
+            issues.push(
+                Issue::error(
+                    Rule::SubjectPunctuation,
+                    "The subject starts with an emoji".to_string(),
+                    Position::Subject { line: 1, column },
+                    context,
+                )
+                .with_suggestion(suggestion),
+            );
         }
 
-        match commit.subject.chars().next() {
+        match subject.chars().next() {
             Some(character) => {
                 if is_punctuation(character) {
+                    let range = Range {
+                        start: offset,
+                        end: offset + character.len_utf8(),
+                    };
                     let context = vec![Context::subject_removal_suggestion(
                         commit.subject.to_string(),
-                        Range {
-                            start: 0,
-                            end: character.len_utf8(),
-                        },
+                        range.clone(),
                         "Remove punctuation from the start of the subject".to_string(),
                     )];
-                    issues.push(Issue::error(
-                        Rule::SubjectPunctuation,
-                        format!(
-                            "The subject starts with a punctuation character: `{}`",
-                            character
-                        ),
-                        Position::Subject { line: 1, column: 1 },
-                        context,
-                    ));
+                    let suggestion = Suggestion::safe(
+                        "Remove punctuation from the start of the subject".to_string(),
+                        vec![Edit::subject_removal(&commit.subject, range)],
+                    );
+                    issues.push(
+                        Issue::error(
+                            Rule::SubjectPunctuation,
+                            format!(
+                                "The subject starts with a punctuation character: `{}`",
+                                character
+                            ),
+                            Position::Subject { line: 1, column },
+                            context,
+                        )
+                        .with_suggestion(suggestion),
+                    );
                 }
             }
             None => {
@@ -86,29 +107,37 @@ impl RuleValidator<Commit> for SubjectPunctuation {
             Some(character) => {
                 if is_punctuation(character) && !is_quote(character) {
                     let subject_length = commit.subject.len();
+                    let range = Range {
+                        start: subject_length - character.len_utf8(),
+                        end: subject_length,
+                    };
                     let context = Context::subject_removal_suggestion(
                         commit.subject.to_string(),
-                        Range {
-                            start: subject_length - character.len_utf8(),
-                            end: subject_length,
-                        },
+                        range.clone(),
+                        "Remove punctuation from the end of the subject".to_string(),
+                    );
+                    let suggestion = Suggestion::safe(
                         "Remove punctuation from the end of the subject".to_string(),
+                        vec![Edit::subject_removal(&commit.subject, range)],
                     );
-                    issues.push(Issue::error(
-                        Rule::SubjectPunctuation,
-                        format!(
-                            "The subject ends with a punctuation character: `{}`",
-                            character
-                        ),
-                        Position::Subject {
-                            line: 1,
-                            column: character_count_for_bytes_index(
-                                &commit.subject,
-                                subject_length - character.len_utf8(),
+                    issues.push(
+                        Issue::error(
+                            Rule::SubjectPunctuation,
+                            format!(
+                                "The subject ends with a punctuation character: `{}`",
+                                character
                             ),
-                        },
-                        vec![context],
-                    ));
+                            Position::Subject {
+                                line: 1,
+                                column: character_count_for_bytes_index(
+                                    &commit.subject,
+                                    subject_length - character.len_utf8(),
+                                ),
+                            },
+                            vec![context],
+                        )
+                        .with_suggestion(suggestion),
+                    );
                 }
             }
             None => {
@@ -274,4 +303,28 @@ mod tests {
         assert!(empty_commit.has_issue(&Rule::SubjectLength));
         assert!(!empty_commit.has_issue(&Rule::SubjectPunctuation));
     }
+
+    #[test]
+    fn with_conventional_commit_description() {
+        assert_eq!(
+            validate(&conventional_commit("feat(api): add endpoint", "")),
+            None
+        );
+
+        let issue = first_issue(validate(&conventional_commit("feat: 👍 add endpoint", "")));
+        assert_eq!(issue.message, "The subject starts with an emoji");
+        assert_eq!(issue.position, subject_position(7));
+        assert_contains_issue_output(
+            &issue,
+            "1 | feat: 👍 add endpoint\n\
+               |       -- Remove emoji from the start of the subject",
+        );
+
+        let issue = first_issue(validate(&conventional_commit("feat: .add endpoint", "")));
+        assert_eq!(
+            issue.message,
+            "The subject starts with a punctuation character: `.`"
+        );
+        assert_eq!(issue.position, subject_position(7));
+    }
 }
@@ -1,18 +1,23 @@
 use core::ops::Range;
 
 use crate::commit::Commit;
-use crate::issue::{Context, Issue, Position};
+use crate::config::file::TicketConfig;
+use crate::issue::{Context, Edit, Issue, Position, Suggestion};
 use crate::rule::Rule;
 use crate::rule::RuleValidator;
 use crate::utils::character_count_for_bytes_index;
 
-use crate::rules::CONTAINS_FIX_TICKET_OR_TICKET_REFERENCE;
+use crate::rules::{message_body_addition, TicketReferences};
 
-pub struct SubjectTicketNumber {}
+pub struct SubjectTicketNumber {
+    tickets: TicketReferences,
+}
 
 impl SubjectTicketNumber {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(config: &TicketConfig) -> Self {
+        Self {
+            tickets: config.references().clone(),
+        }
     }
 }
 
@@ -21,7 +26,7 @@ impl RuleValidator<Commit> for SubjectTicketNumber {
         let mut issues = vec![];
         let subject = &commit.subject.to_string();
 
-        for captures in CONTAINS_FIX_TICKET_OR_TICKET_REFERENCE.captures_iter(subject) {
+        for captures in self.tickets.fix_tickets_or_references(subject) {
             let capture = match captures.name("match") {
                 Some(capture) => capture,
                 None => {
@@ -71,6 +76,11 @@ fn add_subject_ticket_number_error(
     let line_count = commit.message.lines().count();
     let base_line_count = if line_count == 0 { 3 } else { line_count + 2 };
     let keyword_label = keyword.unwrap_or("");
+    let mut edits = vec![Edit::subject_removal(&subject, capture.range())];
+    edits.append(&mut message_body_addition(
+        commit,
+        format!("{}{}", keyword_label, capture.as_str()),
+    ));
     let context = vec![
         Context::subject_removal_suggestion(
             subject,
@@ -98,6 +108,10 @@ fn add_subject_ticket_number_error(
         },
         context,
     )
+    .with_suggestion(Suggestion::maybe_incorrect(
+        "Move the ticket number to the message body".to_string(),
+        edits,
+    ))
 }
 
 #[cfg(test)]
@@ -106,7 +120,7 @@ mod tests {
     use crate::test::*;
 
     fn validate(commit: &Commit) -> Option<Vec<Issue>> {
-        SubjectTicketNumber::new().validate(commit)
+        SubjectTicketNumber::new(&TicketConfig::default()).validate(commit)
     }
 
     fn assert_subject_as_valid(subject: &str) {
@@ -370,4 +384,33 @@ mod tests {
         let issues = validate(&commit("Fix #123 JIRA-123", "")).expect("No issues");
         assert_eq!(issues.len(), 1);
     }
+
+    #[test]
+    fn configured_ticket_syntaxes() {
+        let validator = SubjectTicketNumber::new(&ticket_config(TICKET_SYNTAXES));
+        let invalid_subjects = [
+            "Fix AB#1234 about email validation",
+            "Fix TKT:00042 about email validation",
+            "Fix ENG-123 about email validation",
+            "Fix #123 about email validation",
+        ];
+        for subject in invalid_subjects {
+            assert!(
+                validator.validate(&commit(subject, "")).is_some(),
+                "Subject not invalid: {}",
+                subject
+            );
+        }
+        let valid_subjects = [
+            // Project is not in the allow-list
+            "Fix JIRA-123 about email validation",
+            // Repository shorthand syntax is not configured
+            "Fix org/repo!123 about email validation",
+            // Patterns are case sensitive
+            "Fix ab#1234 about email validation",
+        ];
+        for subject in valid_subjects {
+            assert_eq!(validator.validate(&commit(subject, "")), None);
+        }
+    }
 }
@@ -1,7 +1,7 @@
 use core::ops::Range;
 
 use crate::commit::Commit;
-use crate::issue::{Context, Issue, Position};
+use crate::issue::{Context, Edit, Issue, Position, Suggestion};
 use crate::rule::Rule;
 use crate::rule::RuleValidator;
 
@@ -30,13 +30,26 @@ impl RuleValidator<Commit> for SubjectWhitespace {
                         },
                         "Remove the leading whitespace from the subject".to_string(),
                     )];
+                    let whitespace_length =
+                        commit.subject.len() - commit.subject.trim_start().len();
+                    let suggestion = Suggestion::safe(
+                        "Remove the leading whitespace from the subject".to_string(),
+                        vec![Edit::subject_removal(
+                            &commit.subject,
+                            Range {
+                                start: 0,
+                                end: whitespace_length,
+                            },
+                        )],
+                    );
                     Some(vec![Issue::error(
                         Rule::SubjectWhitespace,
                         "The subject starts with a whitespace character such as a space or a tab"
                             .to_string(),
                         Position::Subject { line: 1, column: 1 },
                         context,
-                    )])
+                    )
+                    .with_suggestion(suggestion)])
                 } else {
                     None
                 }
@@ -128,4 +141,20 @@ mod tests {
                | ---- Remove the leading whitespace from the subject",
         );
     }
+
+    #[test]
+    fn suggestion_removes_all_leading_whitespace() {
+        let issue = first_issue(validate(&commit(" \tFix test", "")));
+        assert_eq!(
+            issue.suggestions,
+            vec![Suggestion::safe(
+                "Remove the leading whitespace from the subject".to_string(),
+                vec![Edit::Replace {
+                    line: 1,
+                    range: Range { start: 0, end: 2 },
+                    text: "".to_string(),
+                }],
+            )]
+        );
+    }
 }
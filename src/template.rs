@@ -0,0 +1,214 @@
+use std::fs;
+use std::path::Path;
+
+use crate::config::file::Config;
+use crate::rule::Rule;
+
+/// Write a commit message template to the commit message file of the prepare-commit-msg hook, above
+/// the contents Git already wrote to the file.
+///
+/// The template is only written for new commits without a message. Messages given with `-m` or
+/// `-F`, templates, merge and squash messages, and commits that reuse a message, such as with
+/// `--amend`, are left as is.
+///
+/// Returns true if the template was written.
+pub fn prepare_commit_message_file(
+    path: &Path,
+    source: Option<&str>,
+    config: &Config,
+    branch: Option<&str>,
+    comment_char: &str,
+) -> Result<bool, String> {
+    if let Some(source) = source.filter(|source| !source.is_empty()) {
+        debug!(
+            "Commit message template skipped for message source: {}",
+            source
+        );
+        return Ok(false);
+    }
+    let contents = fs::read_to_string(path).map_err(|e| {
+        format!(
+            "Unable to read commit message file contents: {}\n{}",
+            path.to_string_lossy(),
+            e
+        )
+    })?;
+    let template = commit_message_template(config, branch, comment_char);
+    fs::write(path, format!("{}{}", template, contents)).map_err(|e| {
+        format!(
+            "Unable to write template to commit message file: {}\n{}",
+            path.to_string_lossy(),
+            e
+        )
+    })?;
+    Ok(true)
+}
+
+// The template starts with an empty subject and message body, followed by the ticket reference of
+// the branch and the configured trailers. The ticket reference, trailers and the guidance for the
+// enabled rules are commented out, so Git removes them from the commit message. This way Git still
+// aborts the commit when the template is saved without changes.
+fn commit_message_template(config: &Config, branch: Option<&str>, comment_char: &str) -> String {
+    let mut lines = vec![String::new(), String::new()];
+    let ticket = branch.and_then(|branch| config.tickets.references().find_reference(branch));
+    if let Some(ticket) = ticket {
+        lines.push(format!("{} Part of {}", comment_char, ticket.as_str()));
+    }
+    if !config.template.trailers.is_empty() {
+        if ticket.is_some() {
+            lines.push(comment_char.to_string());
+        }
+        for trailer in &config.template.trailers {
+            lines.push(format!("{} {}: ", comment_char, trailer));
+        }
+    }
+    if lines.len() > 2 {
+        lines.push(comment_char.to_string());
+    }
+    for guidance in rule_guidance(config) {
+        lines.push(format!("{} {}", comment_char, guidance));
+    }
+    if ticket.is_some() {
+        lines.push(format!(
+            "{} Uncomment the ticket reference to add it to the message body.",
+            comment_char
+        ));
+    }
+    if !config.template.trailers.is_empty() {
+        lines.push(format!(
+            "{} Uncomment and fill in the trailers at the end of the message.",
+            comment_char
+        ));
+    }
+    lines.push(comment_char.to_string());
+    let mut template = lines.join("\n");
+    template.push('\n');
+    template
+}
+
+// Guidance for writing a commit message that follows the enabled rules
+fn rule_guidance(config: &Config) -> Vec<String> {
+    let enabled = |rule: &Rule| config.rule_enabled(rule) != Some(false);
+    let mut guidance = vec![];
+    if config.rule_enabled(&Rule::ConventionalCommit) == Some(true) {
+        guidance.push(format!(
+            "Start the subject with a Conventional Commits prefix: <type>(<scope>): \
+            using one of the types: {}.",
+            config.conventional_commit.types.join(", ")
+        ));
+    }
+    if enabled(&Rule::SubjectLength) {
+        guidance.push(format!(
+            "Summarize the change in a subject of {} characters or less.",
+            config.subject_length.max_width
+        ));
+    }
+    if enabled(&Rule::SubjectMood) {
+        guidance.push(
+            "Use the imperative mood in the subject, like \"Fix bug\" instead of \"Fixed bug\"."
+                .to_string(),
+        );
+    }
+    if enabled(&Rule::SubjectPunctuation) {
+        guidance.push("Do not end the subject with punctuation.".to_string());
+    }
+    if enabled(&Rule::MessagePresence) {
+        guidance.push(format!(
+            "Explain why the change was made in the message body, in at least {} characters.",
+            config.message_presence.min_length
+        ));
+    }
+    if enabled(&Rule::MessageLineLength) {
+        guidance.push(format!(
+            "Wrap the lines of the message body at {} characters.",
+            config.message_line_length.max_width
+        ));
+    }
+    if enabled(&Rule::MessageTicketNumber) && config.message_ticket_number.required {
+        guidance.push("Reference the ticket of the change in the message body.".to_string());
+    }
+    guidance
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{commit_message_template, prepare_commit_message_file};
+    use crate::config::file::Config;
+    use crate::test::*;
+    use std::path::Path;
+
+    #[test]
+    fn default_template() {
+        assert_eq!(
+            commit_message_template(&Config::default(), Some("feature-login-form"), "#"),
+            "\n\
+            \n\
+            # Summarize the change in a subject of 50 characters or less.\n\
+            # Use the imperative mood in the subject, like \"Fix bug\" instead of \"Fixed bug\".\n\
+            # Do not end the subject with punctuation.\n\
+            # Explain why the change was made in the message body, in at least 10 characters.\n\
+            # Wrap the lines of the message body at 72 characters.\n\
+            #\n"
+        );
+    }
+
+    #[test]
+    fn template_with_ticket_and_trailers() {
+        let config = Config::parse(
+            "[template]\n\
+            trailers = [\"Reviewed-by\", \"Signed-off-by\"]\n\
+            [rules.SubjectLength]\n\
+            max_width = 60\n\
+            [rules.SubjectMood]\n\
+            enabled = false\n\
+            [rules.SubjectPunctuation]\n\
+            enabled = false\n\
+            [rules.MessagePresence]\n\
+            enabled = false\n\
+            [rules.MessageTicketNumber]\n\
+            required = true\n\
+            [rules.ConventionalCommit]\n\
+            enabled = true\n\
+            types = [\"feat\", \"fix\"]",
+        )
+        .unwrap();
+        assert_eq!(
+            commit_message_template(&config, Some("feature/ENG-123-login-form"), ";"),
+            "\n\
+            \n\
+            ; Part of ENG-123\n\
+            ;\n\
+            ; Reviewed-by: \n\
+            ; Signed-off-by: \n\
+            ;\n\
+            ; Start the subject with a Conventional Commits prefix: <type>(<scope>): \
+            using one of the types: feat, fix.\n\
+            ; Summarize the change in a subject of 60 characters or less.\n\
+            ; Wrap the lines of the message body at 72 characters.\n\
+            ; Reference the ticket of the change in the message body.\n\
+            ; Uncomment the ticket reference to add it to the message body.\n\
+            ; Uncomment and fill in the trailers at the end of the message.\n\
+            ;\n"
+        );
+    }
+
+    #[test]
+    fn prepare_message_file() {
+        let dir = Path::new(TEST_DIR).join("prepare_message_file");
+        prepare_test_dir(&dir);
+        let path = dir.join("COMMIT_EDITMSG");
+        let git_contents = "# Please enter the commit message for your changes.\n";
+        create_file(&path, git_contents.as_bytes());
+
+        let config = Config::default();
+        for source in ["message", "template", "merge", "squash", "commit"] {
+            assert!(!prepare_commit_message_file(&path, Some(source), &config, None, "#").unwrap());
+            assert_eq!(std::fs::read_to_string(&path).unwrap(), git_contents);
+        }
+
+        assert!(prepare_commit_message_file(&path, Some(""), &config, None, "#").unwrap());
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(contents.starts_with("\n\n# Summarize the change"));
+        assert!(contents.ends_with(&format!("#\n{}", git_contents)));
+    }
+}
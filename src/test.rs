@@ -1,7 +1,9 @@
 use crate::branch::Branch;
 use crate::commit::Commit;
+use crate::config::file::{Config, TicketConfig};
 use crate::formatter::formatted_context as formatted_context_real;
 use crate::issue::{Issue, Position};
+use crate::rules::conventional_commit::ConventionalSubject;
 use std::fs;
 use std::io::Write;
 use std::path::Path;
@@ -77,10 +79,37 @@ pub fn commit_with_trailers<S: AsRef<str>>(subject: S, message: S, trailers: S)
     )
 }
 
+pub fn conventional_commit<S: AsRef<str>>(subject: S, message: S) -> Commit {
+    let mut commit = commit(subject, message);
+    commit.conventional_subject = ConventionalSubject::parse(&commit.subject);
+    commit
+}
+
 pub fn branch(name: &str) -> Branch {
     Branch::new(name.to_string())
 }
 
+// Ticket syntaxes as configured in the config file
+pub const TICKET_SYNTAXES: &str = r#"
+[tickets]
+syntaxes = ["number", "jira", "azure", "internal"]
+
+[tickets.patterns.jira]
+projects = ["ENG", "OPS"]
+
+[tickets.patterns.azure]
+pattern = 'AB#\d+'
+
+[tickets.patterns.internal]
+pattern = 'TKT:\d{5}'
+"#;
+
+pub fn ticket_config(contents: &str) -> TicketConfig {
+    Config::parse(contents)
+        .expect("Invalid ticket config")
+        .tickets
+}
+
 pub fn first_issue(issues_option: Option<Vec<Issue>>) -> Issue {
     let issues = issues_option.expect("No issues found");
     assert_eq!(issues.len(), 1);
@@ -141,9 +141,24 @@ pub fn pluralize(label: &str, count: usize) -> String {
     format!("{}{}", label, plural)
 }
 
+/// Returns a hash of the string as a hex string, using the 64-bit FNV-1a algorithm.
+///
+/// Unlike the hasher of the standard library, the result is the same for every Lintje build, so
+/// it can be stored and compared across runs.
+pub fn stable_hash(string: &str) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in string.bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    format!("{:016x}", hash)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{character_count_for_bytes_index, display_width, line_length_stats, MarkerStats};
+    use super::{
+        character_count_for_bytes_index, display_width, line_length_stats, stable_hash, MarkerStats,
+    };
 
     #[test]
     fn test_character_index_for_bytes() {
@@ -387,4 +402,11 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn test_stable_hash() {
+        assert_eq!(stable_hash(""), "cbf29ce484222325");
+        assert_eq!(stable_hash("a"), "af63dc4c8601ec8c");
+        assert_ne!(stable_hash("ab"), stable_hash("ba"));
+    }
 }